@@ -195,10 +195,16 @@ fn lanczos_downsample(c: &mut Criterion) {
     let mut group = c.benchmark_group("Lanczos Downsample");
     group.bench_function(BenchmarkId::new("Picture", ""), |b| {
         b.iter(|| {
-            picture::processing::resize(
+            picture::processing::resize(&picture_img, black_box((512, 256)), ResizeFilter::Lanczos3)
+        })
+    });
+    #[cfg(feature = "rayon")]
+    group.bench_function(BenchmarkId::new("Picture (Parallel)", ""), |b| {
+        b.iter(|| {
+            picture::processing::resize_par(
                 &picture_img,
                 black_box((512, 256)),
-                picture::processing::ResizeFilter::Lanczos3,
+                ResizeFilter::Lanczos3,
             )
         })
     });
@@ -228,7 +234,7 @@ fn lanczos_upsample(c: &mut Criterion) {
             picture::processing::resize(
                 &picture_img,
                 black_box((4096, 2048)),
-                picture::processing::ResizeFilter::Lanczos3,
+                ResizeFilter::Lanczos3,
             )
         })
     });
@@ -244,9 +250,28 @@ fn lanczos_upsample(c: &mut Criterion) {
     group.finish();
 }
 
+fn box_blur(c: &mut Criterion) {
+    let picture_img = picture::open("examples/images/space.png").unwrap();
+    let CommonImgBuf::Rgb8(picture_img) = picture_img else {
+        unreachable!()
+    };
+
+    let mut group = c.benchmark_group("Box Blur (radius 24)");
+    group.bench_function(BenchmarkId::new("Resample", ""), |b| {
+        b.iter(|| picture::processing::box_blur(&picture_img, black_box(24.0)))
+    });
+    group.bench_function(BenchmarkId::new("Running Sums", ""), |b| {
+        b.iter(|| picture::processing::box_blur_radius(&picture_img, black_box(24)))
+    });
+    group.bench_function(BenchmarkId::new("Integral Image", ""), |b| {
+        b.iter(|| picture::processing::box_blur_integral(&picture_img, black_box(24)))
+    });
+    group.finish();
+}
+
 criterion_group! {
     name = benches;
     config = Criterion::default().measurement_time(std::time::Duration::from_secs_f32(10f32));
-    targets = diff, fractal, closest_match, lanczos_downsample, lanczos_upsample
+    targets = diff, fractal, closest_match, lanczos_downsample, lanczos_upsample, box_blur
 }
 criterion_main!(benches);
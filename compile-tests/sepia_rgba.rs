@@ -0,0 +1,6 @@
+use picture::prelude::*;
+fn main() {
+    let mut img = Rgba8Img::new(1024, 1024);
+
+    picture::processing::sepia(&mut img);
+}
@@ -9,13 +9,10 @@ fn dither<I1>(img: &mut I1)
 where
     I1: ImgMut<Pixel = RGB8>,
 {
-    const BLACK: RGB8 = RGB8::new(0, 0, 0);
-    const WHITE: RGB8 = RGB8::new(255, 255, 255);
-
     let mut error = 0i16;
     for p in img.pixels_mut() {
         // convert to grayscale
-        let avg = ((p.r as u16 + p.g as u16 + p.b as u16) / 3) as u8;
+        let avg = (p.luminance() * 255.0).round() as u8;
 
         // propagate error
         let color = if error >= 0 {
@@ -32,10 +29,10 @@ where
 
         if color >= 128 {
             error -= i16::from(color.abs_diff(255));
-            *p = WHITE;
+            *p = RGB8::WHITE;
         } else {
             error += i16::from(color);
-            *p = BLACK;
+            *p = RGB8::BLACK;
         }
     }
 }
@@ -9,7 +9,7 @@ where
     I1: ImgMut<Pixel = RGB8>,
 {
     for p in img.pixels_mut() {
-        let avg = ((p.r as u16 + p.g as u16 + p.b as u16) / 3) as u8;
+        let avg = (p.luminance() * 255.0).round() as u8;
         p.r = avg;
         p.g = avg;
         p.b = avg;
@@ -1,6 +1,7 @@
 use picture::{
     buffer::common::CommonImgBuf,
     formats::{png::Encoder, ImgEncoder},
+    prelude::*,
     processing::resize,
 };
 
@@ -10,16 +11,8 @@ fn main() {
         unreachable!()
     };
 
-    let small = resize(
-        &colorful,
-        (128, 64),
-        picture::processing::ResizeFilter::Triangle,
-    );
-    let big = resize(
-        &colorful,
-        (4096, 2048),
-        picture::processing::ResizeFilter::CatmullRom,
-    );
+    let small = resize(&colorful, (128, 64), ResizeFilter::Triangle);
+    let big = resize(&colorful, (4096, 2048), ResizeFilter::CatmullRom);
 
     let file = std::fs::File::create("examples/images/out_resize_small.png").unwrap();
     Encoder::default().encode(file, small).unwrap();
@@ -1,5 +1,6 @@
 use super::ImgBuf;
 use crate::pixel::common::*;
+use crate::view::ImgCore;
 
 macro_rules! buf_types {
     ($($pixel:ident),*) => {
@@ -13,6 +14,59 @@ macro_rules! buf_types {
                     [<$pixel:camel>]([<$pixel:camel Img>]),
                 )*
             }
+
+            /// The pixel type of the buffer held by a [`CommonImgBuf`].
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+            pub enum ColorType {
+                $(
+                    [<$pixel:camel>],
+                )*
+            }
+
+            impl CommonImgBuf {
+                /// Returns the dimensions of the buffer this holds.
+                pub fn dimensions(&self) -> (u32, u32) {
+                    match self {
+                        $(
+                            Self::[<$pixel:camel>](buf) => buf.dimensions(),
+                        )*
+                    }
+                }
+
+                /// Returns the pixel type of the buffer this holds.
+                pub fn color_type(&self) -> ColorType {
+                    match self {
+                        $(
+                            Self::[<$pixel:camel>](_) => ColorType::[<$pixel:camel>],
+                        )*
+                    }
+                }
+
+                /// Converts the buffer this holds into an [`Rgba8Img`], expanding grayscale, adding
+                /// an opaque alpha and scaling 16-bit channels as needed. See
+                /// [`Convert`][crate::pixel::common::Convert].
+                pub fn to_rgba8(&self) -> Rgba8Img {
+                    match self {
+                        $(
+                            Self::[<$pixel:camel>](buf) => ImgBuf::from_container(
+                                buf.as_pixel_slice().iter().map(|&p| p.convert()).collect(),
+                                buf.width(),
+                                buf.height(),
+                            ),
+                        )*
+                    }
+                }
+
+                /// Converts whichever buffer this holds into an [`Rgba8Img`], like
+                /// [`CommonImgBuf::to_rgba8`]. If it already holds an [`Rgba8Img`], it's returned
+                /// as-is, without copying it.
+                pub fn into_rgba8(self) -> Rgba8Img {
+                    match self {
+                        Self::Rgba8(buf) => buf,
+                        other => other.to_rgba8(),
+                    }
+                }
+            }
         }
     };
 }
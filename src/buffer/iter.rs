@@ -1,7 +1,7 @@
 use super::ImgBuf;
 use crate::{
     pixel::Pixel,
-    view::{Img, ImgMut},
+    view::{Img, ImgCore, ImgMut},
     Point,
 };
 #[cfg(feature = "unstable")]
@@ -5,19 +5,39 @@ pub mod iter;
 /// View types of the buffer.
 pub mod view;
 
+#[cfg(feature = "serde")]
+mod serde_impl;
+
 use crate::{
-    pixel::Pixel,
-    util::{checked_size, index_point, macros::debug_assertions},
-    view::{Img, ImgMut},
+    pixel::{common::Convert, Pixel, WithChannel},
+    processing::{noise::NoiseKind, Bounded},
+    util::{checked_size, index_point, macros::debug_assertions, Array},
+    view::{Img, ImgCore, ImgMut, ImgMutCore},
     Point, Rect,
 };
+use bytemuck::Pod;
 use std::{
     marker::PhantomData,
     ops::{Deref, DerefMut},
     ptr::NonNull,
 };
+use thiserror::Error;
 use view::{ImgBufView, ImgBufViewMut};
 
+/// An error returned by [`ImgBuf::verify`] when the invariants of a buffer don't hold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum IntegrityError {
+    #[error("dimensions {width}x{height} overflow usize")]
+    SizeOverflow { width: u32, height: u32 },
+    #[error("container has {len} pixels, but dimensions {width}x{height} require {expected}")]
+    LengthMismatch {
+        width: u32,
+        height: u32,
+        len: usize,
+        expected: usize,
+    },
+}
+
 /// An image buffer.
 ///
 /// `P` is it's pixel type and `C` it's container type.
@@ -42,6 +62,71 @@ impl<P> ImgBuf<P> {
 
         Self::from_container(container, width, height)
     }
+
+    /// Creates a new [`ImgBuf`] with the specified `width` and `height` filled with noise of the
+    /// given kind.
+    ///
+    /// The noise is deterministic: the same `seed` and `kind` always produce the same image.
+    ///
+    /// # Panics
+    /// Panics if `kind` is [`NoiseKind::Perlin`] and it's scale is not positive.
+    pub fn from_noise<C, const N: usize>(
+        width: u32,
+        height: u32,
+        seed: u64,
+        kind: NoiseKind,
+    ) -> Self
+    where
+        P: Pixel<Channels = [C; N]>,
+        C: Bounded,
+    {
+        crate::processing::noise::generate(width, height, seed, kind)
+    }
+
+    /// Converts this image buffer into one with a boxed slice as it's container, dropping any
+    /// excess capacity.
+    #[inline]
+    pub fn into_boxed(self) -> ImgBuf<P, Box<[P]>> {
+        ImgBuf {
+            width: self.width,
+            height: self.height,
+            data: self.data.into_boxed_slice(),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Creates a new [`ImgBuf`] by copying raw pixel data in row-major (top-left to bottom-right)
+    /// order, e.g. pixel data received through FFI.
+    ///
+    /// Returns `None` if `bytes.len()` isn't exactly `width * height * size_of::<P>()`. The bytes
+    /// don't need to be aligned to the alignment of `P`.
+    pub fn from_bytes(bytes: &[u8], width: u32, height: u32) -> Option<Self>
+    where
+        P: Pod,
+    {
+        let size = (width as usize).checked_mul(height as usize)?;
+        if size.checked_mul(std::mem::size_of::<P>())? != bytes.len() {
+            return None;
+        }
+
+        let mut container = vec![P::zeroed(); size];
+        bytemuck::cast_slice_mut(&mut container).copy_from_slice(bytes);
+
+        Some(Self::from_container(container, width, height))
+    }
+}
+
+impl<P> ImgBuf<P, Box<[P]>> {
+    /// Converts this image buffer into one with [`Vec`] as it's container.
+    #[inline]
+    pub fn into_vec(self) -> ImgBuf<P, Vec<P>> {
+        ImgBuf {
+            width: self.width,
+            height: self.height,
+            data: self.data.into_vec(),
+            _phantom: PhantomData,
+        }
+    }
 }
 
 impl<P> ImgBuf<P>
@@ -99,6 +184,31 @@ where
         }
     }
 
+    /// Checks that the invariants of this buffer hold, i.e. that `width * height` fits within a
+    /// [`usize`] and is equal to the length of the container.
+    ///
+    /// [`from_container`][ImgBuf::from_container] already checks these, so this only fails if the
+    /// container changed it's length afterwards (e.g. a container backed by memory shared through
+    /// FFI). It's a cheap self-check before trusting the data of such buffers.
+    pub fn verify(&self) -> Result<(), IntegrityError> {
+        let (width, height) = (self.width, self.height);
+        let expected = (width as usize)
+            .checked_mul(height as usize)
+            .ok_or(IntegrityError::SizeOverflow { width, height })?;
+
+        let len = self.data.len();
+        if len != expected {
+            return Err(IntegrityError::LengthMismatch {
+                width,
+                height,
+                len,
+                expected,
+            });
+        }
+
+        Ok(())
+    }
+
     /// Converts this image buffer into another by applying a mapping function to each
     /// of it's pixels.
     pub fn map<P2, C2, F>(self, f: F) -> ImgBuf<P2, C2>
@@ -128,6 +238,64 @@ where
         )
     }
 
+    /// Converts this image buffer into another with [`Vec`] as it's container by converting each
+    /// of it's pixels into `P2`, e.g. `image.convert_to::<RGB8>()`. See [`Convert`].
+    pub fn convert_to<P2>(self) -> ImgBuf<P2, Vec<P2>>
+    where
+        C: IntoIterator<Item = P>,
+        P: Convert<P2>,
+    {
+        self.map_vec(Convert::convert)
+    }
+
+    /// Converts this image buffer into another with [`Vec`] as it's container by applying
+    /// a mapping function to each of it's pixels and their coordinates.
+    ///
+    /// Pixels are visited in row-major (top-left to bottom-right) order.
+    pub fn map_with_coords<P2, F>(self, mut f: F) -> ImgBuf<P2, Vec<P2>>
+    where
+        C: IntoIterator<Item = P>,
+        F: FnMut(Point, P) -> P2,
+    {
+        let (width, height) = (self.width, self.height);
+        let coords = (0..height).flat_map(|y| (0..width).map(move |x| (x, y)));
+
+        <ImgBuf<P2, Vec<P2>>>::from_container(
+            coords
+                .zip(self.data)
+                .map(|(coords, pixel)| f(coords, pixel))
+                .collect(),
+            width,
+            height,
+        )
+    }
+
+    /// Combines this image buffer with another one into a new buffer with [`Vec`] as it's container
+    /// by applying a function to each pair of pixels with the same coordinates.
+    ///
+    /// See [`Img::zip_map_ref`] for a non-consuming alternative.
+    ///
+    /// # Panics
+    /// Panics if `self.dimensions() != other.dimensions()`
+    pub fn zip_map<P2, C2, P3, F>(self, other: ImgBuf<P2, C2>, mut f: F) -> ImgBuf<P3, Vec<P3>>
+    where
+        C: IntoIterator<Item = P>,
+        C2: IntoIterator<Item = P2>,
+        F: FnMut(P, P2) -> P3,
+    {
+        assert_eq!((self.width, self.height), (other.width, other.height));
+
+        <ImgBuf<P3, Vec<P3>>>::from_container(
+            self.data
+                .into_iter()
+                .zip(other.data)
+                .map(|(a, b)| f(a, b))
+                .collect(),
+            self.width,
+            self.height,
+        )
+    }
+
     /// Returns a slice containing the pixels of this buffer in row-major (top-left to bottom-right) order.
     #[inline]
     pub fn as_pixel_slice(&self) -> &[P] {
@@ -145,6 +313,84 @@ where
     pub fn pixels_with_coords(&self) -> iter::PixelsWithCoords<'_, P> {
         iter::PixelsWithCoords::new(self)
     }
+
+    /// Returns a slice containing the channels of the pixels of this buffer in row-major (top-left to
+    /// bottom-right) order. The slice has `width * height * N` elements.
+    #[inline]
+    pub fn channels_flat<T, const N: usize>(&self) -> &[T]
+    where
+        P: Pixel<Channels = [T; N]> + Pod,
+        T: Pod,
+    {
+        bytemuck::cast_slice(self.as_pixel_slice())
+    }
+
+    /// Returns the raw bytes of the pixels of this buffer in row-major (top-left to bottom-right)
+    /// order. Channels wider than a byte are in native endianness.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8]
+    where
+        P: Pod,
+    {
+        bytemuck::cast_slice(self.as_pixel_slice())
+    }
+
+    /// Returns a 64-bit FNV-1a hash of the dimensions and pixel bytes of this buffer, meant for
+    /// finding exact duplicates. Buffers with equal dimensions and pixels always have the same
+    /// hash.
+    ///
+    /// The hash is computed over the in-memory bytes of the pixels, so it's stable across runs but
+    /// might differ between platforms with different endianness for channels wider than a byte.
+    /// See [`phash`][crate::processing::phash] for finding similar images instead.
+    pub fn content_hash(&self) -> u64
+    where
+        P: Pod,
+    {
+        const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+        const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+        let dimensions = [self.width.to_le_bytes(), self.height.to_le_bytes()];
+        let bytes: &[u8] = bytemuck::cast_slice(self.as_pixel_slice());
+        dimensions
+            .iter()
+            .flatten()
+            .chain(bytes)
+            .fold(OFFSET_BASIS, |hash, &byte| {
+                (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+            })
+    }
+
+    /// Creates a new [`ImgBuf`] by converting the channels of the pixels of this buffer to another
+    /// channel type `D`, e.g. from [`u8`] to [`f32`].
+    ///
+    /// Channels are scaled so that the [`MIN_VALUE`][Bounded::MIN_VALUE] and
+    /// [`MAX_VALUE`][Bounded::MAX_VALUE] of one type map to the ones of the other: `0..=255`
+    /// maps to `0.0..=1.0` and back. Integer channels are rounded and clamped to their range.
+    pub fn convert_channels<D>(&self) -> ImgBuf<P::Output>
+    where
+        P: WithChannel<D>,
+        <P::Channels as Array>::Elem: Bounded,
+        D: Bounded,
+    {
+        let container = self
+            .as_pixel_slice()
+            .iter()
+            .map(|p| p.map_channels_into(|c| D::from_normalized_f32(c.to_normalized_f32())))
+            .collect();
+
+        ImgBuf::from_container(container, self.width, self.height)
+    }
+
+    /// Creates a new [`ImgBuf`] with [`f32`] channels in the `0.0..=1.0` range from this buffer.
+    /// Use [`convert_channels`][ImgBuf::convert_channels] to convert back.
+    #[inline]
+    pub fn to_f32(&self) -> ImgBuf<<P as WithChannel<f32>>::Output>
+    where
+        P: WithChannel<f32>,
+        <P::Channels as Array>::Elem: Bounded,
+    {
+        self.convert_channels()
+    }
 }
 
 impl<P, C> ImgBuf<P, C>
@@ -191,21 +437,35 @@ where
     pub fn pixels_with_coords_mut(&mut self) -> iter::PixelsWithCoordsMut<'_, P> {
         iter::PixelsWithCoordsMut::new(self)
     }
+
+    /// Returns a mutable slice containing the channels of the pixels of this buffer in row-major
+    /// (top-left to bottom-right) order. The slice has `width * height * N` elements.
+    #[inline]
+    pub fn channels_flat_mut<T, const N: usize>(&mut self) -> &mut [T]
+    where
+        P: Pixel<Channels = [T; N]> + Pod,
+        T: Pod,
+    {
+        bytemuck::cast_slice_mut(self.as_mut_pixel_slice())
+    }
+
+    /// Returns the raw bytes of the pixels of this buffer in row-major (top-left to bottom-right)
+    /// order, mutably. Channels wider than a byte are in native endianness.
+    #[inline]
+    pub fn as_bytes_mut(&mut self) -> &mut [u8]
+    where
+        P: Pod,
+    {
+        bytemuck::cast_slice_mut(self.as_mut_pixel_slice())
+    }
 }
 
-impl<P, C> Img for ImgBuf<P, C>
+impl<P, C> ImgCore for ImgBuf<P, C>
 where
     P: Pixel,
     C: Deref<Target = [P]>,
 {
     type Pixel = P;
-    type Pixels<'buffer_ref> = iter::Pixels<'buffer_ref, Self::Pixel>
-    where
-        Self: 'buffer_ref;
-    type View<'buffer_ref> = ImgBufView<'buffer_ref, Self::Pixel>
-    where
-        Self::Pixel: 'buffer_ref,
-        C: 'buffer_ref;
 
     #[inline]
     fn width(&self) -> u32 {
@@ -224,6 +484,11 @@ where
 
     #[inline]
     fn pixel(&self, coords: Point) -> Option<&Self::Pixel> {
+        // an x coordinate past the width would otherwise index into the next row
+        if !self.bounds().contains_relative(coords) {
+            return None;
+        }
+
         self.data.get(index_point(coords, self.width))
     }
 
@@ -234,6 +499,20 @@ where
             off => self.data.get_unchecked(index_point(coords, self.width))
         }
     }
+}
+
+impl<P, C> Img for ImgBuf<P, C>
+where
+    P: Pixel,
+    C: Deref<Target = [P]>,
+{
+    type Pixels<'buffer_ref> = iter::Pixels<'buffer_ref, Self::Pixel>
+    where
+        Self: 'buffer_ref;
+    type View<'buffer_ref> = ImgBufView<'buffer_ref, Self::Pixel>
+    where
+        Self::Pixel: 'buffer_ref,
+        C: 'buffer_ref;
 
     #[inline]
     fn pixels(&self) -> Self::Pixels<'_> {
@@ -245,6 +524,15 @@ where
         std::iter::once(self.as_pixel_slice())
     }
 
+    #[inline]
+    fn row_to_vec(&self, y: u32) -> Option<Vec<Self::Pixel>>
+    where
+        Self::Pixel: Clone,
+    {
+        let start = index_point((0, y), self.width);
+        (y < self.height).then(|| self.data[start..start + self.width as usize].to_vec())
+    }
+
     #[inline]
     unsafe fn view_unchecked(&self, bounds: Rect) -> Self::View<'_> {
         debug_assert!(self.bounds().contains_rect(&bounds));
@@ -252,21 +540,17 @@ where
     }
 }
 
-impl<P, C> ImgMut for ImgBuf<P, C>
+impl<P, C> ImgMutCore for ImgBuf<P, C>
 where
     P: Pixel,
     C: DerefMut<Target = [P]>,
 {
-    type PixelsMut<'buffer_ref> = iter::PixelsMut<'buffer_ref, Self::Pixel>
-    where
-        Self: 'buffer_ref;
-
-    type ViewMut<'buffer_ref> = ImgBufViewMut<'buffer_ref, Self::Pixel>
-    where
-        Self::Pixel: 'buffer_ref, C: 'buffer_ref;
-
     #[inline]
     fn pixel_mut(&mut self, coords: Point) -> Option<&mut Self::Pixel> {
+        if !self.bounds().contains_relative(coords) {
+            return None;
+        }
+
         self.data.get_mut(index_point(coords, self.width))
     }
 
@@ -277,6 +561,20 @@ where
             off => self.data.get_unchecked_mut(index_point(coords, self.width))
         }
     }
+}
+
+impl<P, C> ImgMut for ImgBuf<P, C>
+where
+    P: Pixel,
+    C: DerefMut<Target = [P]>,
+{
+    type PixelsMut<'buffer_ref> = iter::PixelsMut<'buffer_ref, Self::Pixel>
+    where
+        Self: 'buffer_ref;
+
+    type ViewMut<'buffer_ref> = ImgBufViewMut<'buffer_ref, Self::Pixel>
+    where
+        Self::Pixel: 'buffer_ref, C: 'buffer_ref;
 
     #[inline]
     fn pixels_mut(&mut self) -> Self::PixelsMut<'_> {
@@ -338,6 +636,94 @@ where
     }
 }
 
+/// Trait for views backed by an [`ImgBuf`], whose rows are contiguous in memory.
+pub trait Rows: Img {
+    /// Returns an iterator over the rows of this view, from top to bottom, each as a contiguous
+    /// slice of pixels.
+    ///
+    /// Unlike [`Img::pixel_chunks`], every item is exactly one row, which is useful for algorithms
+    /// that need per-row context (e.g. run-length encoding).
+    fn rows(&self) -> impl ExactSizeIterator<Item = &'_ [Self::Pixel]>;
+}
+
+/// Trait for mutable views backed by an [`ImgBuf`], whose rows are contiguous in memory.
+pub trait RowsMut: Rows + ImgMut {
+    /// Returns an iterator over the rows of this view, from top to bottom, each as a contiguous
+    /// mutable slice of pixels. See [`Rows::rows`].
+    fn rows_mut(&mut self) -> impl ExactSizeIterator<Item = &'_ mut [Self::Pixel]>;
+}
+
+impl<P, C> Rows for ImgBuf<P, C>
+where
+    P: Pixel,
+    C: Deref<Target = [P]>,
+{
+    #[inline]
+    fn rows(&self) -> impl ExactSizeIterator<Item = &'_ [Self::Pixel]> {
+        let (data, width) = (self.as_pixel_slice(), self.width as usize);
+        (0..self.height as usize).map(move |y| &data[y * width..(y + 1) * width])
+    }
+}
+
+impl<P, C> RowsMut for ImgBuf<P, C>
+where
+    P: Pixel,
+    C: DerefMut<Target = [P]>,
+{
+    #[inline]
+    fn rows_mut(&mut self) -> impl ExactSizeIterator<Item = &'_ mut [Self::Pixel]> {
+        let (ptr, width, bounds) = (self.as_mut_ptr(), self.width, self.bounds());
+
+        // SAFETY: the bounds of the buffer are obviously within it and it's mutably borrowed, so
+        // it's pixels can't be accessed through anything else.
+        unsafe { view::rows_mut(ptr, width, bounds) }
+    }
+}
+
+/// Trait for mutable views backed by an [`ImgBuf`] that can be split into disjoint mutable tiles.
+pub trait TilesMut {
+    /// The pixel type of the tiles.
+    type Pixel;
+
+    /// Splits this view into disjoint mutable views with the given tile dimensions, in row-major
+    /// order. See [`ImgBufViewMut::into_tiles_mut`].
+    ///
+    /// # Panics
+    /// Panics if any of the tile dimensions is zero.
+    fn tiles_mut(&mut self, tile: (u32, u32)) -> Vec<ImgBufViewMut<'_, Self::Pixel>>;
+}
+
+impl<P, C> TilesMut for ImgBuf<P, C>
+where
+    P: Pixel,
+    C: DerefMut<Target = [P]>,
+{
+    type Pixel = P;
+
+    #[inline]
+    fn tiles_mut(&mut self, tile: (u32, u32)) -> Vec<ImgBufViewMut<'_, P>> {
+        let bounds = self.bounds();
+        self.view_mut(bounds)
+            .expect("bounds of the buffer are always valid")
+            .into_tiles_mut(tile)
+    }
+}
+
+impl<'buffer_ref, P> TilesMut for ImgBufViewMut<'buffer_ref, P>
+where
+    P: Pixel,
+{
+    type Pixel = P;
+
+    #[inline]
+    fn tiles_mut(&mut self, tile: (u32, u32)) -> Vec<ImgBufViewMut<'_, P>> {
+        let bounds = self.bounds();
+        self.view_mut(bounds)
+            .expect("bounds of the view are always valid")
+            .into_tiles_mut(tile)
+    }
+}
+
 impl<P, C> IntoIterator for ImgBuf<P, C>
 where
     P: Pixel,
@@ -412,3 +798,311 @@ where
         buf_strat.boxed()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let buffer = Rgb8Img::from_fn(3, 2, |(x, y)| RGB8::new(x as u8, y as u8, 0));
+
+        let json = serde_json::to_string(&buffer).unwrap();
+        let decoded: Rgb8Img = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded.dimensions(), buffer.dimensions());
+        assert_eq!(decoded.as_pixel_slice(), buffer.as_pixel_slice());
+
+        let mismatched = r#"{"width":2,"height":2,"data":[{"r":0,"g":0,"b":0}]}"#;
+        let error = serde_json::from_str::<Rgb8Img>(mismatched).unwrap_err();
+        assert!(error.to_string().contains("require 4"));
+    }
+
+    #[test]
+    fn raw_bytes() {
+        let mut buffer = Rgb8Img::from_fn(2, 2, |(x, y)| RGB8::new(x as u8, y as u8, 7));
+        assert_eq!(&buffer.as_bytes()[..6], &[0, 0, 7, 1, 0, 7]);
+
+        buffer.as_bytes_mut()[9..].copy_from_slice(&[3, 4, 5]);
+        assert_eq!(buffer.pixel((1, 1)), Some(&RGB8::new(3, 4, 5)));
+
+        let copy = Rgb8Img::from_bytes(buffer.as_bytes(), 2, 2).unwrap();
+        assert_eq!(copy.as_pixel_slice(), buffer.as_pixel_slice());
+
+        // unaligned bytes are fine for wider channels
+        let bytes = [0u8; 13];
+        assert!(Rgb16Img::from_bytes(&bytes[1..], 1, 2).is_some());
+        assert!(Rgb16Img::from_bytes(&bytes[1..], 2, 1).is_some());
+        assert!(Rgb8Img::from_bytes(&bytes, 2, 2).is_none());
+    }
+
+    #[test]
+    fn pixel_out_of_bounds() {
+        let mut buffer = Rgb8Img::from_fn(2, 2, |(x, y)| RGB8::new(x as u8, y as u8, 0));
+        assert_eq!(buffer.pixel((1, 0)), Some(&RGB8::new(1, 0, 0)));
+        assert_eq!(buffer.pixel((2, 0)), None);
+        assert_eq!(buffer.pixel((0, 2)), None);
+        assert_eq!(buffer.pixel_mut((2, 1)), None);
+        assert_eq!(buffer.pixel_mut((u32::MAX, 0)), None);
+    }
+
+    #[test]
+    fn convert_to() {
+        use crate::buffer::common::CommonImgBuf;
+        use crate::pixel::common::Gray;
+
+        let buffer = Rgba16Img::from_fn(2, 1, |(x, _)| RGBA16::new(65535, 0, x as u16 * 257, 0));
+        let converted = buffer.convert_to::<RGB8>();
+        assert_eq!(
+            converted.as_pixel_slice(),
+            [RGB8::new(255, 0, 0), RGB8::new(255, 0, 1)]
+        );
+
+        let gray = ImgBuf::from_fn(2, 1, |(x, _)| Gray(x as u16 * 65535));
+        let rgba = CommonImgBuf::Gray16(gray).into_rgba8();
+        assert_eq!(
+            rgba.as_pixel_slice(),
+            [RGBA8::new(0, 0, 0, 255), RGBA8::new(255, 255, 255, 255)]
+        );
+    }
+
+    #[test]
+    fn common_helpers() {
+        use crate::buffer::common::{ColorType, CommonImgBuf};
+        use crate::pixel::common::GrayAlpha;
+
+        let gray = ImgBuf::from_fn(3, 2, |(x, _)| GrayAlpha(x as u8 * 100, 128));
+        let common = CommonImgBuf::Graya8(gray);
+        assert_eq!(common.dimensions(), (3, 2));
+        assert_eq!(common.color_type(), ColorType::Graya8);
+
+        let rgba = common.to_rgba8();
+        assert_eq!(rgba.dimensions(), (3, 2));
+        assert_eq!(rgba.pixel((2, 1)), Some(&RGBA8::new(200, 200, 200, 128)));
+
+        let common = CommonImgBuf::Rgb16(Rgb16Img::from_fn(1, 1, |_| RGB16::new(65535, 257, 0)));
+        assert_eq!(common.color_type(), ColorType::Rgb16);
+        assert_eq!(
+            common.to_rgba8().as_pixel_slice(),
+            [RGBA8::new(255, 1, 0, 255)]
+        );
+
+        // an Rgba8Img is handed back without reallocating
+        let rgba = Rgba8Img::from_fn(2, 2, |(x, y)| RGBA8::new(x as u8, y as u8, 0, 255));
+        let ptr = rgba.as_pixel_slice().as_ptr();
+        let common = CommonImgBuf::Rgba8(rgba);
+        assert_eq!(
+            common.to_rgba8().pixel((1, 0)),
+            Some(&RGBA8::new(1, 0, 0, 255))
+        );
+        assert_eq!(common.into_rgba8().as_pixel_slice().as_ptr(), ptr);
+    }
+
+    #[test]
+    fn boxed_round_trip() {
+        let buffer = Rgb8Img::from_fn(3, 2, |(x, y)| RGB8::new(x as u8, y as u8, 0));
+        let mut boxed = buffer.clone().into_boxed();
+
+        assert_eq!(boxed.dimensions(), (3, 2));
+        assert_eq!(boxed.pixel((2, 1)), Some(&RGB8::new(2, 1, 0)));
+        *boxed.pixel_mut((0, 0)).unwrap() = RGB8::new(9, 9, 9);
+
+        let vec = boxed.into_vec();
+        assert_eq!(vec.pixel((0, 0)), Some(&RGB8::new(9, 9, 9)));
+        assert_eq!(&vec.as_pixel_slice()[1..], &buffer.as_pixel_slice()[1..]);
+    }
+
+    #[test]
+    fn view_pixel_chunks_are_rows() {
+        let mut buffer = ImgBuf::from_fn(4, 3, |(x, y)| [(y * 4 + x) as u8]);
+
+        let view = buffer.view(Rect::new((1, 1), (2, 2))).unwrap();
+        let chunks: Vec<_> = view.pixel_chunks().collect();
+        assert_eq!(chunks, [&[[5], [6]][..], &[[9], [10]][..]]);
+
+        // full width views are contiguous
+        let view = buffer.view(Rect::new((0, 1), (4, 2))).unwrap();
+        let chunks: Vec<_> = view.pixel_chunks().collect();
+        assert_eq!(chunks, [&buffer.as_pixel_slice()[4..]]);
+
+        let view = buffer.view_mut(Rect::new((3, 0), (1, 3))).unwrap();
+        let chunks: Vec<_> = view.pixel_chunks().flatten().copied().collect();
+        assert_eq!(chunks, [[3], [7], [11]]);
+    }
+
+    #[test]
+    fn row_slices() {
+        use super::{Rows, RowsMut};
+
+        let mut buffer = ImgBuf::from_fn(3, 4, |(x, y)| [(y * 3 + x) as u8]);
+        assert_eq!(buffer.rows().len(), 4);
+        assert_eq!(buffer.rows().nth(1), Some(&[[3], [4], [5]][..]));
+
+        let mut view = buffer.view_mut(Rect::new((1, 1), (2, 3))).unwrap();
+        assert_eq!(view.rows().len(), 3);
+        assert_eq!(view.rows().last(), Some(&[[10], [11]][..]));
+
+        // horizontal prefix sums
+        for row in view.rows_mut() {
+            for x in 1..row.len() {
+                row[x][0] += row[x - 1][0];
+            }
+        }
+
+        assert_eq!(buffer.rows().nth(2), Some(&[[6], [7], [15]][..]));
+        buffer.rows_mut().for_each(|row| row[0] = [0]);
+        assert!(buffer.rows().all(|row| row[0] == [0]));
+
+        let empty = ImgBuf::from_fn(0, 2, |_| [0u8]);
+        assert!(empty.rows().all(<[_]>::is_empty));
+        assert_eq!(empty.rows().len(), 2);
+    }
+
+    #[test]
+    fn column_iterators() {
+        let buffer = ImgBuf::from_fn(3, 4, |(x, y)| [(y * 3 + x) as u8]);
+        let view = buffer.view(Rect::new((1, 1), (2, 3))).unwrap();
+
+        let mut columns = view.columns();
+        assert_eq!(columns.len(), 2);
+        let column = columns.next().unwrap();
+        assert_eq!(column.len(), 3);
+        assert!(column.eq(&[[4], [7], [10]]));
+
+        let sums: Vec<u32> = buffer
+            .columns()
+            .map(|column| column.map(|[v]| u32::from(*v)).sum())
+            .collect();
+        assert_eq!(sums, [18, 22, 26]);
+    }
+
+    #[test]
+    fn verify_integrity() {
+        use super::IntegrityError;
+        use std::{cell::Cell, ops::Deref};
+
+        // a container whose length can change behind the buffer's back
+        struct Shared {
+            data: Vec<RGB8>,
+            len: Cell<usize>,
+        }
+
+        impl Deref for Shared {
+            type Target = [RGB8];
+
+            fn deref(&self) -> &Self::Target {
+                &self.data[..self.len.get()]
+            }
+        }
+
+        let shared = Shared {
+            data: vec![RGB8::default(); 6],
+            len: Cell::new(6),
+        };
+        let buffer = ImgBuf::from_container(shared, 3, 2);
+        assert_eq!(buffer.verify(), Ok(()));
+
+        buffer.container().len.set(5);
+        assert_eq!(
+            buffer.verify(),
+            Err(IntegrityError::LengthMismatch {
+                width: 3,
+                height: 2,
+                len: 5,
+                expected: 6
+            })
+        );
+    }
+
+    #[test]
+    fn pixels_step() {
+        let buffer = ImgBuf::from_fn(4, 3, |(x, y)| [(y * 4 + x) as u8]);
+        let view = buffer.view(Rect::new((1, 1), (3, 2))).unwrap();
+        let values = |step| buffer.pixels_step(step).map(|[v]| *v).collect::<Vec<_>>();
+
+        assert_eq!(buffer.pixels_step(1).len(), 12);
+        assert_eq!(values(1), (0..12).collect::<Vec<_>>());
+        assert_eq!(buffer.pixels_step(3).len(), 4);
+        assert_eq!(values(3), [0, 3, 6, 9]);
+        assert_eq!(buffer.pixels_step(100).len(), 1);
+        assert_eq!(values(100), [0]);
+
+        // views step through their own pixels, not the ones of the underlying buffer
+        assert_eq!(view.pixels_step(2).len(), 3);
+        assert!(view.pixels_step(2).map(|[v]| *v).eq([5, 7, 10]));
+    }
+
+    #[test]
+    #[should_panic(expected = "step must be non-zero")]
+    fn pixels_step_zero() {
+        let buffer = ImgBuf::from_fn(2, 2, |_| [0u8]);
+        let _ = buffer.pixels_step(0);
+    }
+
+    #[test]
+    fn channels_flat() {
+        let mut buffer = Rgb8Img::from_fn(2, 2, |(x, y)| RGB8::new(x as u8, y as u8, 9));
+        assert_eq!(buffer.channels_flat(), [0, 0, 9, 1, 0, 9, 0, 1, 9, 1, 1, 9]);
+
+        buffer.channels_flat_mut()[4] = 42;
+        assert_eq!(buffer.pixel((1, 0)), Some(&RGB8::new(1, 42, 9)));
+        assert_eq!(buffer.channels_flat_mut().len(), 12);
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let buffer = Rgb8Img::from_fn(3, 2, |(x, y)| RGB8::new(x as u8, y as u8, 0));
+        let view = buffer.view(Rect::new((1, 0), (2, 2))).unwrap();
+
+        assert_eq!(
+            buffer.row_to_vec(1),
+            Some(vec![
+                RGB8::new(0, 1, 0),
+                RGB8::new(1, 1, 0),
+                RGB8::new(2, 1, 0)
+            ])
+        );
+        assert_eq!(
+            view.row_to_vec(0),
+            Some(vec![RGB8::new(1, 0, 0), RGB8::new(2, 0, 0)])
+        );
+        assert_eq!(
+            view.col_to_vec(1),
+            Some(vec![RGB8::new(2, 0, 0), RGB8::new(2, 1, 0)])
+        );
+        assert_eq!(buffer.row_to_vec(2), None);
+        assert_eq!(view.col_to_vec(2), None);
+    }
+
+    #[test]
+    fn content_hash_dedup() {
+        let a = ImgBuf::from_fn(4, 3, |(x, y)| RGB8::new(x as u8, y as u8, 0));
+        let b = ImgBuf::from_fn(4, 3, |(x, y)| RGB8::new(x as u8, y as u8, 0));
+        let mut c = b.clone();
+        c.pixel_mut((3, 2)).unwrap().b = 1;
+
+        assert_eq!(a.content_hash(), b.content_hash());
+        assert_ne!(a.content_hash(), c.content_hash());
+
+        // same bytes, different dimensions
+        let wide = ImgBuf::from_container(vec![[0u8]; 4], 4, 1);
+        let tall = ImgBuf::from_container(vec![[0u8]; 4], 1, 4);
+        assert_ne!(wide.content_hash(), tall.content_hash());
+    }
+
+    #[test]
+    fn convert_channels_round_trip() {
+        let buffer = Rgb8Img::from_fn(16, 16, |(x, y)| RGB8::new(x as u8, y as u8 * 16, 255));
+        let float = buffer.to_f32();
+
+        assert_eq!(float.pixel((0, 0)), Some(&RGB::new(0.0, 0.0, 1.0)));
+        assert_eq!(
+            float.convert_channels::<u8>().as_pixel_slice(),
+            buffer.as_pixel_slice()
+        );
+        assert_eq!(
+            float.convert_channels::<u16>().pixel((0, 15)),
+            Some(&RGB16::new(0, 0xF0F0, u16::MAX))
+        );
+    }
+}
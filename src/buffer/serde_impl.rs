@@ -0,0 +1,52 @@
+use super::ImgBuf;
+use serde::{
+    de::Error as _, ser::SerializeStruct, Deserialize, Deserializer, Serialize, Serializer,
+};
+use std::marker::PhantomData;
+
+impl<P> Serialize for ImgBuf<P>
+where
+    P: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("ImgBuf", 3)?;
+        state.serialize_field("width", &self.width)?;
+        state.serialize_field("height", &self.height)?;
+        state.serialize_field("data", &self.data)?;
+        state.end()
+    }
+}
+
+/// The serialized form of an [`ImgBuf`], before it's invariants are checked.
+#[derive(Deserialize)]
+#[serde(rename = "ImgBuf")]
+struct RawImgBuf<P> {
+    width: u32,
+    height: u32,
+    data: Vec<P>,
+}
+
+impl<'de, P> Deserialize<'de> for ImgBuf<P>
+where
+    P: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let raw = RawImgBuf::deserialize(deserializer)?;
+        let img = ImgBuf {
+            width: raw.width,
+            height: raw.height,
+            data: raw.data,
+            _phantom: PhantomData,
+        };
+
+        // a length mismatch is an error instead of a panic, as in from_container
+        img.verify().map_err(D::Error::custom)?;
+        Ok(img)
+    }
+}
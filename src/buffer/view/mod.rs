@@ -1,8 +1,8 @@
-use super::ImgBuf;
+use super::{ImgBuf, Rows, RowsMut};
 use crate::{
     pixel::Pixel,
     util::index_point,
-    view::{self, Img, ImgMut},
+    view::{self, Img, ImgCore, ImgMut, ImgMutCore},
     Point, Rect,
 };
 use std::{
@@ -13,6 +13,89 @@ use std::{
 
 pub mod iter;
 
+/// Returns an iterator over the rows of the pixels within `bounds` of the buffer pointed to by
+/// `ptr`. If `bounds` spans the whole width of the buffer, it's rows are contiguous and a single
+/// chunk is returned instead.
+///
+/// # Safety
+/// `bounds` must be within the buffer and the pixels within it must not be mutated during `'buffer_ref`.
+#[inline]
+unsafe fn row_chunks<'buffer_ref, P>(
+    ptr: NonNull<P>,
+    buffer_width: u32,
+    bounds: Rect,
+) -> impl Iterator<Item = &'buffer_ref [P]> + 'buffer_ref
+where
+    P: 'buffer_ref,
+{
+    let ((x, y), (width, height)) = (bounds.top_left(), bounds.dimensions());
+    let (rows, row_len) = if x == 0 && width == buffer_width {
+        (1, width as usize * height as usize)
+    } else {
+        (height, width as usize)
+    };
+
+    (0..rows).map(move |row| {
+        let start = index_point((x, y + row), buffer_width);
+
+        // SAFETY: every row of 'bounds' is within the buffer, and so are the rows following it
+        // if 'bounds' spans the whole width of the buffer. the caller guarantees that these
+        // pixels aren't mutated while the slice is alive.
+        unsafe { std::slice::from_raw_parts(ptr.as_ptr().add(start), row_len) }
+    })
+}
+
+/// Returns an iterator over the rows of the pixels within `bounds` of the buffer pointed to by
+/// `ptr`.
+///
+/// # Safety
+/// `bounds` must be within the buffer and the pixels within it must not be mutated during
+/// `'buffer_ref`.
+#[inline]
+pub(super) unsafe fn rows<'buffer_ref, P>(
+    ptr: NonNull<P>,
+    buffer_width: u32,
+    bounds: Rect,
+) -> impl ExactSizeIterator<Item = &'buffer_ref [P]> + 'buffer_ref
+where
+    P: 'buffer_ref,
+{
+    let ((x, y), (width, height)) = (bounds.top_left(), bounds.dimensions());
+    (y..y + height).map(move |row| {
+        let start = index_point((x, row), buffer_width);
+
+        // SAFETY: every row of 'bounds' is within the buffer and the caller guarantees that these
+        // pixels aren't mutated while the slice is alive.
+        unsafe { std::slice::from_raw_parts(ptr.as_ptr().add(start), width as usize) }
+    })
+}
+
+/// Returns an iterator over the mutable rows of the pixels within `bounds` of the buffer pointed
+/// to by `ptr`.
+///
+/// # Safety
+/// `bounds` must be within the buffer and the pixels within it must not be accessed through
+/// anything else during `'buffer_ref`.
+#[inline]
+pub(super) unsafe fn rows_mut<'buffer_ref, P>(
+    ptr: NonNull<P>,
+    buffer_width: u32,
+    bounds: Rect,
+) -> impl ExactSizeIterator<Item = &'buffer_ref mut [P]> + 'buffer_ref
+where
+    P: 'buffer_ref,
+{
+    let ((x, y), (width, height)) = (bounds.top_left(), bounds.dimensions());
+    (y..y + height).map(move |row| {
+        let start = index_point((x, row), buffer_width);
+
+        // SAFETY: every row of 'bounds' is within the buffer and rows don't overlap each other,
+        // so each slice is only handed out once. the caller guarantees that these pixels aren't
+        // accessed through anything else while the slice is alive.
+        unsafe { std::slice::from_raw_parts_mut(ptr.as_ptr().add(start), width as usize) }
+    })
+}
+
 /// A view into an [`ImgBuf`].
 #[derive(Clone)]
 pub struct ImgBufView<'buffer_ref, P> {
@@ -55,17 +138,11 @@ where
     }
 }
 
-impl<'buffer_ref, P> Img for ImgBufView<'buffer_ref, P>
+impl<'buffer_ref, P> ImgCore for ImgBufView<'buffer_ref, P>
 where
     P: Pixel,
 {
     type Pixel = P;
-    type Pixels<'self_ref> = view::iter::Pixels<'self_ref, Self>
-    where
-        Self: 'self_ref;
-    type View<'self_ref> = Self
-    where
-        Self: 'self_ref;
 
     #[inline]
     fn width(&self) -> u32 {
@@ -98,17 +175,30 @@ where
         // valid we are "borrowing" the buffer, and therefore no mutable reference to this pixel can exist.
         unsafe { ptr.add(index).as_ref().unwrap_unchecked() }
     }
+}
+
+impl<'buffer_ref, P> Img for ImgBufView<'buffer_ref, P>
+where
+    P: Pixel,
+{
+    type Pixels<'self_ref> = view::iter::Pixels<'self_ref, Self>
+    where
+        Self: 'self_ref;
+    type View<'self_ref> = Self
+    where
+        Self: 'self_ref;
 
     #[inline]
     fn pixels(&self) -> Self::Pixels<'_> {
         Self::Pixels::new(self)
     }
 
-    // TODO: this can be optimized to iterate over the rows of the view!
-    // #[inline]
-    // fn pixel_chunks(&self) -> impl Iterator<Item = &'_ [P]> {
-    //     todo!()
-    // }
+    #[inline]
+    fn pixel_chunks(&self) -> impl Iterator<Item = &'_ [P]> {
+        // SAFETY: 'bounds' is within the buffer and no mutable reference to it's pixels can exist
+        // while this view is borrowed.
+        unsafe { row_chunks(self.ptr, self.buffer_width, self.bounds) }
+    }
 
     #[inline]
     unsafe fn view_unchecked(&self, bounds: Rect) -> Self::View<'_> {
@@ -124,6 +214,18 @@ where
     }
 }
 
+impl<'buffer_ref, P> Rows for ImgBufView<'buffer_ref, P>
+where
+    P: Pixel,
+{
+    #[inline]
+    fn rows(&self) -> impl ExactSizeIterator<Item = &'_ [P]> {
+        // SAFETY: 'bounds' is within the buffer and no mutable reference to it's pixels can exist
+        // while this view is borrowed.
+        unsafe { rows(self.ptr, self.buffer_width, self.bounds) }
+    }
+}
+
 impl<'buffer_ref, 'view_ref, P> IntoIterator for &'view_ref ImgBufView<'buffer_ref, P>
 where
     P: Pixel,
@@ -182,6 +284,42 @@ where
         }
     }
 
+    /// Splits this view into disjoint mutable views with the given tile dimensions, in row-major
+    /// order. Tiles on the right and bottom edges are clipped to this view, so they may be smaller.
+    ///
+    /// # Panics
+    /// Panics if any of the tile dimensions is zero.
+    pub fn into_tiles_mut(self, tile: (u32, u32)) -> Vec<ImgBufViewMut<'buffer_ref, P>> {
+        let (tile_width, tile_height) = tile;
+        assert!(
+            tile_width != 0 && tile_height != 0,
+            "tile dimensions must be non-zero"
+        );
+
+        let (width, height) = self.dimensions();
+        let mut tiles = Vec::new();
+        for y in (0..height).step_by(tile_height as usize) {
+            for x in (0..width).step_by(tile_width as usize) {
+                let bounds = Rect::new(
+                    (x, y),
+                    ((width - x).min(tile_width), (height - y).min(tile_height)),
+                );
+
+                // SAFETY: tiles are clipped to this view and don't overlap with each other. since
+                // 'self' is consumed, no other view into these pixels can be created.
+                tiles.push(unsafe {
+                    ImgBufViewMut::from_ptr(
+                        self.ptr,
+                        self.buffer_width,
+                        self.bounds.abs_rect_from_relative(bounds),
+                    )
+                });
+            }
+        }
+
+        tiles
+    }
+
     /// Returns an iterator over the pixels and coordinates of this view.
     #[inline]
     pub fn pixels_with_coords(&self) -> view::iter::PixelsWithCoords<'_, Self> {
@@ -195,18 +333,11 @@ where
     }
 }
 
-impl<'buffer_ref, P> Img for ImgBufViewMut<'buffer_ref, P>
+impl<'buffer_ref, P> ImgCore for ImgBufViewMut<'buffer_ref, P>
 where
     P: Pixel,
 {
     type Pixel = P;
-    type Pixels<'self_ref> = view::iter::Pixels<'self_ref, Self>
-    where
-        Self: 'self_ref;
-
-    type View<'self_ref> = ImgBufView<'self_ref, Self::Pixel>
-    where
-        Self: 'self_ref;
 
     #[inline]
     fn width(&self) -> u32 {
@@ -241,17 +372,31 @@ where
         // pixels in this view.
         unsafe { ptr.add(index).as_ref().unwrap_unchecked() }
     }
+}
+
+impl<'buffer_ref, P> Img for ImgBufViewMut<'buffer_ref, P>
+where
+    P: Pixel,
+{
+    type Pixels<'self_ref> = view::iter::Pixels<'self_ref, Self>
+    where
+        Self: 'self_ref;
+
+    type View<'self_ref> = ImgBufView<'self_ref, Self::Pixel>
+    where
+        Self: 'self_ref;
 
     #[inline]
     fn pixels(&self) -> Self::Pixels<'_> {
         Self::Pixels::new(self)
     }
 
-    // TODO: this can be optimized to iterate over the rows of the view!
-    // #[inline]
-    // fn pixel_chunks(&self) -> impl Iterator<Item = &'_ [P]> {
-    //     todo!()
-    // }
+    #[inline]
+    fn pixel_chunks(&self) -> impl Iterator<Item = &'_ [P]> {
+        // SAFETY: 'bounds' is within the buffer and no mutable reference to it's pixels can exist
+        // while this view is borrowed.
+        unsafe { row_chunks(self.ptr, self.buffer_width, self.bounds) }
+    }
 
     #[inline]
     unsafe fn view_unchecked(&self, bounds: Rect) -> Self::View<'_> {
@@ -267,18 +412,10 @@ where
     }
 }
 
-impl<'buffer_ref, P> ImgMut for ImgBufViewMut<'buffer_ref, P>
+impl<'buffer_ref, P> ImgMutCore for ImgBufViewMut<'buffer_ref, P>
 where
     P: Pixel,
 {
-    type PixelsMut<'self_ref> = iter::PixelsMut<'self_ref, Self::Pixel>
-    where
-        Self: 'self_ref;
-
-    type ViewMut<'self_ref> = ImgBufViewMut<'self_ref, Self::Pixel>
-    where
-        Self: 'self_ref;
-
     #[inline]
     unsafe fn pixel_mut_unchecked(&mut self, coords: Point) -> &mut Self::Pixel {
         debug_assert!(self.bounds.contains_relative(coords));
@@ -297,6 +434,19 @@ where
         // pixels in this view.
         unsafe { ptr.add(index).as_mut().unwrap_unchecked() }
     }
+}
+
+impl<'buffer_ref, P> ImgMut for ImgBufViewMut<'buffer_ref, P>
+where
+    P: Pixel,
+{
+    type PixelsMut<'self_ref> = iter::PixelsMut<'self_ref, Self::Pixel>
+    where
+        Self: 'self_ref;
+
+    type ViewMut<'self_ref> = ImgBufViewMut<'self_ref, Self::Pixel>
+    where
+        Self: 'self_ref;
 
     #[inline]
     fn pixels_mut(&mut self) -> Self::PixelsMut<'_> {
@@ -382,6 +532,30 @@ where
     }
 }
 
+impl<'buffer_ref, P> Rows for ImgBufViewMut<'buffer_ref, P>
+where
+    P: Pixel,
+{
+    #[inline]
+    fn rows(&self) -> impl ExactSizeIterator<Item = &'_ [P]> {
+        // SAFETY: 'bounds' is within the buffer and no mutable reference to it's pixels can exist
+        // while this view is borrowed.
+        unsafe { rows(self.ptr, self.buffer_width, self.bounds) }
+    }
+}
+
+impl<'buffer_ref, P> RowsMut for ImgBufViewMut<'buffer_ref, P>
+where
+    P: Pixel,
+{
+    #[inline]
+    fn rows_mut(&mut self) -> impl ExactSizeIterator<Item = &'_ mut [P]> {
+        // SAFETY: 'bounds' is within the buffer and this view is mutably borrowed, so it's pixels
+        // can't be accessed through anything else.
+        unsafe { rows_mut(self.ptr, self.buffer_width, self.bounds) }
+    }
+}
+
 impl<'buffer_ref, 'view_ref, P> IntoIterator for &'view_ref ImgBufViewMut<'buffer_ref, P>
 where
     P: Pixel,
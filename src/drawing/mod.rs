@@ -0,0 +1,1435 @@
+use crate::{pixel::common::Blend, view::ImgMut, Point, Rect};
+use arrayvec::ArrayVec;
+use std::collections::HashSet;
+
+/// Returns the first and last step `k` (both inclusive) for which `origin + step * k` is within
+/// `0..size`, where `step` is `-1`, `0` or `1`. If there's no such step, the first one is greater
+/// than the last one.
+fn visible_steps(origin: i128, step: i128, size: u32) -> (i128, i128) {
+    let last = i128::from(size) - 1;
+    match step {
+        1 => (-origin, last - origin),
+        -1 => (origin - last, origin),
+        _ if (0..=last).contains(&origin) => (0, i128::MAX),
+        _ => (0, -1),
+    }
+}
+
+/// Returns `a / b` rounded towards positive infinity, for a positive `b`.
+fn div_ceil(a: i128, b: i128) -> i128 {
+    -(-a).div_euclid(b)
+}
+
+/// Returns the largest `x` such that `x * x <= value`, for a non-negative `value`.
+fn isqrt(value: i128) -> i128 {
+    let mut root = (value as f64).sqrt() as i128;
+    while root * root > value {
+        root -= 1;
+    }
+    while (root + 1) * (root + 1) <= value {
+        root += 1;
+    }
+
+    root
+}
+
+/// Calls `plot` for every point within `dimensions` of the line from `start` to `end` (both
+/// inclusive), using Bresenham's algorithm. Every point is visited exactly once.
+fn line_points(start: Point, end: Point, dimensions: (u32, u32), mut plot: impl FnMut(i64, i64)) {
+    let (start_x, start_y) = (i128::from(start.0), i128::from(start.1));
+    let (dx, dy) = (i128::from(end.0) - start_x, i128::from(end.1) - start_y);
+
+    // the major axis advances by one at every step and the minor one by `minor / major`, rounded
+    // half up. this is exactly what the error term of Bresenham's algorithm tracks, but it allows
+    // clipping the line to the view before walking it, instead of walking every point of it
+    let x_major = dx.abs() >= dy.abs();
+    let ((major, major_axis), (minor, minor_axis)) = if x_major {
+        (
+            (dx.abs(), (start_x, dx.signum(), dimensions.0)),
+            (dy.abs(), (start_y, dy.signum(), dimensions.1)),
+        )
+    } else {
+        (
+            (dy.abs(), (start_y, dy.signum(), dimensions.1)),
+            (dx.abs(), (start_x, dx.signum(), dimensions.0)),
+        )
+    };
+    let offset = |step: i128| {
+        if major == 0 {
+            0
+        } else {
+            (2 * minor * step + major) / (2 * major)
+        }
+    };
+
+    let (first, last) = visible_steps(major_axis.0, major_axis.1, major_axis.2);
+    let (mut first, mut last) = (first.max(0), last.min(major));
+
+    let (min_offset, max_offset) = visible_steps(minor_axis.0, minor_axis.1, minor_axis.2);
+    let (min_offset, max_offset) = (min_offset.max(0), max_offset.min(minor));
+    if minor == 0 {
+        if min_offset > max_offset {
+            return;
+        }
+    } else {
+        // invert `offset` to find the first step with an offset of at least `min_offset` and the
+        // last one with an offset of at most `max_offset`
+        first = first.max(div_ceil(major * (2 * min_offset - 1), 2 * minor));
+        last = last.min(div_ceil(major * (2 * max_offset + 1), 2 * minor) - 1);
+    }
+
+    for step in first..=last {
+        let major_coord = major_axis.0 + major_axis.1 * step;
+        let minor_coord = minor_axis.0 + minor_axis.1 * offset(step);
+        let (x, y) = if x_major {
+            (major_coord, minor_coord)
+        } else {
+            (minor_coord, major_coord)
+        };
+
+        plot(x as i64, y as i64);
+    }
+}
+
+/// Calls `plot` for every point within `dimensions` of the outline of a circle, using the midpoint
+/// circle algorithm. Every point is visited exactly once.
+fn circle_points(
+    center: Point,
+    radius: u32,
+    dimensions: (u32, u32),
+    mut plot: impl FnMut(i64, i64),
+) {
+    let (width, height) = (i128::from(dimensions.0), i128::from(dimensions.1));
+    let (center_x, center_y) = (i128::from(center.0), i128::from(center.1));
+    let radius = i128::from(radius);
+    let squared = radius * radius;
+
+    if radius == 0 {
+        if center_x < width && center_y < height {
+            plot(center_x as i64, center_y as i64);
+        }
+
+        return;
+    }
+
+    // the midpoint algorithm walks the octant where 0 <= x <= y, choosing the largest y such that
+    // y * (y - 1) < radius² - x² for every x, and mirrors it into the other octants. instead of
+    // walking it, the points of every visible row are computed directly from that
+    let octant_y = |x: i128| {
+        let limit = squared - x * x;
+        if limit <= 0 {
+            return 0;
+        }
+
+        let mut y = isqrt(limit);
+        while y * (y - 1) >= limit {
+            y -= 1;
+        }
+        while (y + 1) * y < limit {
+            y += 1;
+        }
+
+        y
+    };
+
+    let first_row = (center_y - radius).max(0);
+    let last_row = (center_y + radius).min(height - 1);
+    for y in first_row..=last_row {
+        let row = (y - center_y).abs();
+
+        // the column offsets of this row: the x of every octant point with this y, i.e. every x
+        // with row * (row - 1) < radius² - x² <= row * (row + 1), and the y of the mirrored octant
+        // point with this x, if it's within the octant
+        let mut offsets = ArrayVec::<(i128, i128), 2>::new();
+        let below = squared - row * (row + 1);
+        let first = if below > 0 { isqrt(below - 1) + 1 } else { 0 };
+        let last = isqrt(squared - row * (row - 1) - 1).min(row);
+        if first <= last {
+            offsets.push((first, last));
+        }
+
+        let mirrored = octant_y(row);
+        if row <= mirrored {
+            offsets.push((mirrored, mirrored));
+        }
+
+        // mirror the offsets to both sides of the center and merge them, so that points where the
+        // octants meet are only visited once
+        let mut columns = ArrayVec::<(i128, i128), 4>::new();
+        for (first, last) in offsets {
+            for (start, end) in [
+                (center_x - last, center_x - first),
+                (center_x + first, center_x + last),
+            ] {
+                let (start, end) = (start.max(0), end.min(width - 1));
+                if start <= end {
+                    columns.push((start, end));
+                }
+            }
+        }
+
+        columns.sort_unstable();
+        let mut next = 0;
+        for (start, end) in columns {
+            for x in start.max(next)..=end {
+                plot(x as i64, y as i64);
+            }
+            next = next.max(end + 1);
+        }
+    }
+}
+
+/// Calls `plot` for every point within `dimensions` that is within a circle (including it's
+/// outline). Every point is visited exactly once.
+fn disk_points(center: Point, radius: u32, dimensions: (u32, u32), mut plot: impl FnMut(i64, i64)) {
+    let (width, height) = (i128::from(dimensions.0), i128::from(dimensions.1));
+    let (center_x, center_y) = (i128::from(center.0), i128::from(center.1));
+    let radius = i128::from(radius);
+
+    // only the rows and the parts of each span that are within the view are walked
+    let first_row = (center_y - radius).max(0);
+    let last_row = (center_y + radius).min(height - 1);
+    for y in first_row..=last_row {
+        // widest offset_x such that offset_x² + offset_y² <= radius²
+        let offset_y = y - center_y;
+        let half_width = isqrt(radius * radius - offset_y * offset_y);
+
+        let first_column = (center_x - half_width).max(0);
+        let last_column = (center_x + half_width).min(width - 1);
+        for x in first_column..=last_column {
+            plot(x as i64, y as i64);
+        }
+    }
+}
+
+/// Calls `plot` for every point within `dimensions` of the Bézier curve with the given control
+/// points (either 3 or 4, for quadratic and cubic curves). The curve is flattened into line
+/// segments, whose number scales with the diagonal of the bounding box of the control points. Every
+/// point is visited exactly once.
+fn bezier_points(control: &[Point], dimensions: (u32, u32), mut plot: impl FnMut(i64, i64)) {
+    let (min, max) = control
+        .iter()
+        .fold(((u32::MAX, u32::MAX), (0, 0)), |(min, max), &(x, y)| {
+            ((min.0.min(x), min.1.min(y)), (max.0.max(x), max.1.max(y)))
+        });
+    let diagonal = f64::from(max.0 - min.0).hypot(f64::from(max.1 - min.1));
+    let segments = ((diagonal / 4.0).ceil() as u32).max(1);
+    let degree = control.len() - 1;
+
+    // evaluates the blossom of the curve using de Casteljau's algorithm, with one parameter for
+    // each of it's steps. if every parameter is t, this is the point of the curve at t
+    let blossom = |params: &[f64]| {
+        let mut points: ArrayVec<(f64, f64), 4> = control
+            .iter()
+            .map(|&(x, y)| (f64::from(x), f64::from(y)))
+            .collect();
+
+        for &t in params {
+            for i in 0..points.len() - 1 {
+                let (a, b) = (points[i], points[i + 1]);
+                points[i] = (a.0 + (b.0 - a.0) * t, a.1 + (b.1 - a.1) * t);
+            }
+            points.pop();
+        }
+
+        points[0]
+    };
+
+    let param = |segment: u32| f64::from(segment) / f64::from(segments);
+    let evaluate = |segment: u32| {
+        let (x, y) = blossom(&[param(segment); 3][..degree]);
+        (x.round() as u32, y.round() as u32)
+    };
+
+    // the part of the curve between two parameters is within the bounding box of it's own control
+    // points, which are the blossom with every combination of those parameters. if that box (plus
+    // some leeway for rounding) is outside of the view, none of it's segments are visible
+    let visible = |first: u32, last: u32| {
+        let (mut min, mut max) = ((f64::INFINITY, f64::INFINITY), (0f64, 0f64));
+        for k in 0..=degree {
+            let mut params = [param(first); 3];
+            params[..k].fill(param(last));
+
+            let (x, y) = blossom(&params[..degree]);
+            (min, max) = ((min.0.min(x), min.1.min(y)), (max.0.max(x), max.1.max(y)));
+        }
+
+        let (width, height) = (f64::from(dimensions.0), f64::from(dimensions.1));
+        max.0 >= -1.0 && max.1 >= -1.0 && min.0 <= width && min.1 <= height
+    };
+
+    // consecutive segments share their endpoints and the curve might cross itself, so deduplicate
+    // the points. only visible points are plotted, so this never holds more than the view does
+    let mut visited = HashSet::new();
+
+    // ranges of segments are split in halves until they're either not visible at all or short
+    // enough to be flattened, so that only the visible parts of huge curves are walked
+    let mut ranges = vec![(0, segments)];
+    while let Some((first, last)) = ranges.pop() {
+        if !visible(first, last) {
+            continue;
+        }
+
+        if last - first > 16 {
+            let middle = first + (last - first) / 2;
+            ranges.extend([(middle, last), (first, middle)]);
+            continue;
+        }
+
+        let mut start = evaluate(first);
+        for segment in first + 1..=last {
+            let end = evaluate(segment);
+            line_points(start, end, dimensions, |x, y| {
+                if visited.insert((x, y)) {
+                    plot(x, y);
+                }
+            });
+
+            start = end;
+        }
+    }
+}
+
+/// Calls `plot` for every point within `dimensions` of the outline of a polygon, connecting
+/// consecutive vertices and closing the loop. Zero or one vertices plot nothing and two vertices
+/// plot a single line. Every point is visited exactly once.
+fn polygon_outline_points(
+    vertices: &[Point],
+    dimensions: (u32, u32),
+    mut plot: impl FnMut(i64, i64),
+) {
+    match vertices {
+        [] | [_] => (),
+        &[start, end] => line_points(start, end, dimensions, plot),
+        _ => {
+            // edges share their endpoints and might cross each other, so deduplicate the points.
+            // the edges are clipped to the view, so this never holds more points than it does
+            let mut visited = HashSet::new();
+            let next = vertices.iter().cycle().skip(1);
+            for (&start, &end) in vertices.iter().zip(next) {
+                line_points(start, end, dimensions, |x, y| {
+                    if visited.insert((x, y)) {
+                        plot(x, y);
+                    }
+                });
+            }
+        }
+    }
+}
+
+/// Calls `plot` for every point within `dimensions` whose center is inside a polygon, according
+/// to the even-odd rule. Polygons with less than 3 vertices have no area and plot nothing. Every
+/// point is visited exactly once.
+fn polygon_fill_points(vertices: &[Point], dimensions: (u32, u32), mut plot: impl FnMut(i64, i64)) {
+    if vertices.len() < 3 {
+        return;
+    }
+
+    let (width, height) = dimensions;
+    let min_y = vertices.iter().map(|p| p.1).min().unwrap_or(0);
+    let max_y = vertices.iter().map(|p| p.1).max().unwrap_or(0).min(height);
+
+    let mut crossings = Vec::new();
+    for y in min_y..max_y {
+        let center_y = f64::from(y) + 0.5;
+
+        // x coordinates where the edges cross the horizontal line through the centers of this row
+        crossings.clear();
+        let next = vertices.iter().cycle().skip(1);
+        for (&(ax, ay), &(bx, by)) in vertices.iter().zip(next) {
+            let (ax, ay, bx, by) = (f64::from(ax), f64::from(ay), f64::from(bx), f64::from(by));
+            if (ay <= center_y) != (by <= center_y) {
+                crossings.push(ax + (center_y - ay) * (bx - ax) / (by - ay));
+            }
+        }
+
+        crossings.sort_unstable_by(f64::total_cmp);
+        for span in crossings.chunks_exact(2) {
+            // points whose center is within [start, end)
+            let start = (span[0] - 0.5).ceil().max(0.0);
+            let end = (span[1] - 0.5).ceil().min(f64::from(width));
+
+            for x in start as u32..end.max(start) as u32 {
+                plot(i64::from(x), i64::from(y));
+            }
+        }
+    }
+}
+
+/// Calls `plot` for every point within `dimensions` covered by a line from `start` to `end` with
+/// the given width and square caps, i.e. every point whose center is within the rectangle around
+/// the line extended by half the width in every direction. Every point is visited exactly once.
+fn thick_line_points(
+    start: Point,
+    end: Point,
+    width: u32,
+    dimensions: (u32, u32),
+    mut plot: impl FnMut(i64, i64),
+) {
+    let half = f64::from(width) / 2.0;
+    let (start_x, start_y) = (f64::from(start.0), f64::from(start.1));
+    let (dx, dy) = (f64::from(end.0) - start_x, f64::from(end.1) - start_y);
+    let length = dx.hypot(dy);
+    let (dir_x, dir_y) = if length > 0.0 {
+        (dx / length, dy / length)
+    } else {
+        (1.0, 0.0)
+    };
+
+    let inside = |offset_x: f64, offset_y: f64| {
+        let along = offset_x * dir_x + offset_y * dir_y;
+        let across = offset_x * dir_y - offset_y * dir_x;
+        (-half..length + half).contains(&along) && (-half..half).contains(&across)
+    };
+
+    // the range of offsets `o` for which `min <= o * factor + constant < max`
+    let solve = |factor: f64, constant: f64, min: f64, max: f64| {
+        if factor > 0.0 {
+            ((min - constant) / factor, (max - constant) / factor)
+        } else if factor < 0.0 {
+            ((max - constant) / factor, (min - constant) / factor)
+        } else if (min..max).contains(&constant) {
+            (f64::NEG_INFINITY, f64::INFINITY)
+        } else {
+            (f64::INFINITY, f64::NEG_INFINITY)
+        }
+    };
+
+    // the corners of the rectangle are at most half * sqrt(2) away from the endpoints
+    let reach = (half * std::f64::consts::SQRT_2).ceil() as i64;
+    let min_y = (i64::from(start.1.min(end.1)) - reach).max(0);
+    let max_y = (i64::from(start.1.max(end.1)) + reach).min(i64::from(dimensions.1) - 1);
+    let max_x = i64::from(dimensions.0) - 1;
+
+    for y in min_y..=max_y {
+        // the span of this row within the rectangle, widened by a point on each side so that
+        // rounding errors can't drop any point; the points of the span are then tested exactly
+        let offset_y = y as f64 - start_y;
+        let (along_start, along_end) = solve(dir_x, offset_y * dir_y, -half, length + half);
+        let (across_start, across_end) = solve(dir_y, -offset_y * dir_x, -half, half);
+        let span_start = along_start.max(across_start) + start_x;
+        let span_end = along_end.min(across_end) + start_x;
+        if span_start > span_end {
+            continue;
+        }
+
+        let first = (span_start.floor() as i64 - 1).max(0);
+        let last = (span_end.ceil() as i64 + 1).min(max_x);
+        for x in first..=last {
+            if inside(x as f64 - start_x, offset_y) {
+                plot(x, y);
+            }
+        }
+    }
+}
+
+/// Calls `plot` for every point within `dimensions` of the outline of `bounds`. Every point is
+/// visited exactly once, including the corners.
+fn rect_outline_points(bounds: Rect, dimensions: (u32, u32), mut plot: impl FnMut(Point)) {
+    let Some((right, bottom)) = bounds.inclusive_bottom_right() else {
+        return;
+    };
+
+    // each edge is clipped to the view before it's walked
+    let ((left, top), (width, height)) = (bounds.top_left(), dimensions);
+    let columns = left..right.saturating_add(1).min(width);
+    let rows = top.saturating_add(1)..bottom.min(height);
+
+    if top < height {
+        columns.clone().for_each(|x| plot((x, top)));
+    }
+
+    if bottom != top && bottom < height {
+        columns.for_each(|x| plot((x, bottom)));
+    }
+
+    if left < width {
+        rows.clone().for_each(|y| plot((left, y)));
+    }
+
+    if right != left && right < width {
+        rows.for_each(|y| plot((right, y)));
+    }
+}
+
+/// Calls `plot` for every point of a grid of lines covering a view with the given dimensions, with
+/// lines at every multiple of `spacing`. Every point is visited exactly once.
+fn grid_points(dimensions: (u32, u32), spacing: (u32, u32), mut plot: impl FnMut(i64, i64)) {
+    let ((width, height), (spacing_x, spacing_y)) = (dimensions, spacing);
+    assert!(
+        spacing_x != 0 && spacing_y != 0,
+        "grid spacing must be non-zero"
+    );
+
+    for y in 0..height {
+        if y % spacing_y == 0 {
+            (0..width).for_each(|x| plot(i64::from(x), i64::from(y)));
+        } else {
+            (0..width)
+                .step_by(spacing_x as usize)
+                .for_each(|x| plot(i64::from(x), i64::from(y)));
+        }
+    }
+}
+
+/// Trait for views that can be drawn on. This has a blanket implementation for every [`ImgMut`].
+///
+/// Every drawing method takes a closure that returns the pixel to draw at a given point, which
+/// allows drawing gradients, patterns and so on. Every shape also has a `*_with_existing` variant
+/// which passes the pixel currently at that point to the closure as well, which allows blending
+/// with it (e.g. custom alpha blending or additive drawing). Each point of a shape is passed to the
+/// closure exactly once.
+///
+/// For pixels that implement [`Blend`], every shape also has a `*_blended` variant which composites
+/// the pixel returned by the closure over the existing one using source-over alpha blending, which
+/// allows painting semi-transparent shapes.
+///
+/// Shapes may extend past the bounds of the view: parts that don't fit are clipped before any of
+/// their points are visited.
+pub trait Drawing: ImgMut {
+    /// Draws a line from `start` to `end`, both inclusive.
+    #[inline]
+    fn draw_line<F>(&mut self, start: Point, end: Point, mut f: F)
+    where
+        F: FnMut(Point) -> Self::Pixel,
+    {
+        line_points(start, end, self.dimensions(), |x, y| {
+            plot(self, x, y, |p, _| f(p));
+        });
+    }
+
+    /// Draws a line from `start` to `end`, both inclusive, passing the existing pixels to the
+    /// closure. See [`Drawing`].
+    #[inline]
+    fn draw_line_with_existing<F>(&mut self, start: Point, end: Point, mut f: F)
+    where
+        F: FnMut(Point, Self::Pixel) -> Self::Pixel,
+        Self::Pixel: Clone,
+    {
+        line_points(start, end, self.dimensions(), |x, y| {
+            plot(self, x, y, |p, existing| f(p, existing.clone()));
+        });
+    }
+
+    /// Draws a line from `start` to `end`, both inclusive, alpha blending over the existing pixels.
+    /// See [`Drawing`].
+    #[inline]
+    fn draw_line_blended<F>(&mut self, start: Point, end: Point, mut f: F)
+    where
+        F: FnMut(Point) -> Self::Pixel,
+        Self::Pixel: Blend + Copy,
+    {
+        self.draw_line_with_existing(start, end, |p, existing| f(p).over(existing));
+    }
+
+    /// Draws a line from `start` to `end` with the given width, in pixels, and square caps: the
+    /// ends of the line extend past `start` and `end` by half the width. A width of `1` is the same
+    /// as [`Drawing::draw_line`] and a width of `0` draws nothing.
+    #[inline]
+    fn draw_thick_line<F>(&mut self, start: Point, end: Point, width: u32, mut f: F)
+    where
+        F: FnMut(Point) -> Self::Pixel,
+    {
+        match width {
+            0 => (),
+            1 => self.draw_line(start, end, f),
+            _ => thick_line_points(start, end, width, self.dimensions(), |x, y| {
+                plot(self, x, y, |p, _| f(p));
+            }),
+        }
+    }
+
+    /// Draws a line from `start` to `end` with the given width, passing the existing pixels to the
+    /// closure. See [`Drawing::draw_thick_line`] and [`Drawing`].
+    #[inline]
+    fn draw_thick_line_with_existing<F>(&mut self, start: Point, end: Point, width: u32, mut f: F)
+    where
+        F: FnMut(Point, Self::Pixel) -> Self::Pixel,
+        Self::Pixel: Clone,
+    {
+        match width {
+            0 => (),
+            1 => self.draw_line_with_existing(start, end, f),
+            _ => thick_line_points(start, end, width, self.dimensions(), |x, y| {
+                plot(self, x, y, |p, existing| f(p, existing.clone()));
+            }),
+        }
+    }
+
+    /// Draws a line from `start` to `end` with the given width, alpha blending over the existing
+    /// pixels. See [`Drawing::draw_thick_line`] and [`Drawing`].
+    #[inline]
+    fn draw_thick_line_blended<F>(&mut self, start: Point, end: Point, width: u32, mut f: F)
+    where
+        F: FnMut(Point) -> Self::Pixel,
+        Self::Pixel: Blend + Copy,
+    {
+        self.draw_thick_line_with_existing(start, end, width, |p, existing| f(p).over(existing));
+    }
+
+    /// Draws a quadratic Bézier curve from `p0` to `p2`, with `p1` as it's control point.
+    #[inline]
+    fn draw_bezier<F>(&mut self, p0: Point, p1: Point, p2: Point, mut f: F)
+    where
+        F: FnMut(Point) -> Self::Pixel,
+    {
+        bezier_points(&[p0, p1, p2], self.dimensions(), |x, y| {
+            plot(self, x, y, |p, _| f(p));
+        });
+    }
+
+    /// Draws a quadratic Bézier curve from `p0` to `p2`, with `p1` as it's control point, passing
+    /// the existing pixels to the closure. See [`Drawing`].
+    #[inline]
+    fn draw_bezier_with_existing<F>(&mut self, p0: Point, p1: Point, p2: Point, mut f: F)
+    where
+        F: FnMut(Point, Self::Pixel) -> Self::Pixel,
+        Self::Pixel: Clone,
+    {
+        bezier_points(&[p0, p1, p2], self.dimensions(), |x, y| {
+            plot(self, x, y, |p, existing| f(p, existing.clone()));
+        });
+    }
+
+    /// Draws a quadratic Bézier curve from `p0` to `p2`, with `p1` as it's control point, alpha
+    /// blending over the existing pixels. See [`Drawing`].
+    #[inline]
+    fn draw_bezier_blended<F>(&mut self, p0: Point, p1: Point, p2: Point, mut f: F)
+    where
+        F: FnMut(Point) -> Self::Pixel,
+        Self::Pixel: Blend + Copy,
+    {
+        self.draw_bezier_with_existing(p0, p1, p2, |p, existing| f(p).over(existing));
+    }
+
+    /// Draws a cubic Bézier curve from `p0` to `p3`, with `p1` and `p2` as it's control points.
+    #[inline]
+    fn draw_cubic_bezier<F>(&mut self, p0: Point, p1: Point, p2: Point, p3: Point, mut f: F)
+    where
+        F: FnMut(Point) -> Self::Pixel,
+    {
+        bezier_points(&[p0, p1, p2, p3], self.dimensions(), |x, y| {
+            plot(self, x, y, |p, _| f(p));
+        });
+    }
+
+    /// Draws a cubic Bézier curve from `p0` to `p3`, with `p1` and `p2` as it's control points,
+    /// passing the existing pixels to the closure. See [`Drawing`].
+    #[inline]
+    fn draw_cubic_bezier_with_existing<F>(
+        &mut self,
+        p0: Point,
+        p1: Point,
+        p2: Point,
+        p3: Point,
+        mut f: F,
+    ) where
+        F: FnMut(Point, Self::Pixel) -> Self::Pixel,
+        Self::Pixel: Clone,
+    {
+        bezier_points(&[p0, p1, p2, p3], self.dimensions(), |x, y| {
+            plot(self, x, y, |p, existing| f(p, existing.clone()));
+        });
+    }
+
+    /// Draws a cubic Bézier curve from `p0` to `p3`, with `p1` and `p2` as it's control points,
+    /// alpha blending over the existing pixels. See [`Drawing`].
+    #[inline]
+    fn draw_cubic_bezier_blended<F>(&mut self, p0: Point, p1: Point, p2: Point, p3: Point, mut f: F)
+    where
+        F: FnMut(Point) -> Self::Pixel,
+        Self::Pixel: Blend + Copy,
+    {
+        self.draw_cubic_bezier_with_existing(p0, p1, p2, p3, |p, existing| f(p).over(existing));
+    }
+
+    /// Draws the outline of a polygon with the given vertices, connecting consecutive ones and
+    /// closing the loop. Zero or one vertices draw nothing and two vertices draw a single line.
+    #[inline]
+    fn draw_polygon<F>(&mut self, vertices: &[Point], mut f: F)
+    where
+        F: FnMut(Point) -> Self::Pixel,
+    {
+        polygon_outline_points(vertices, self.dimensions(), |x, y| {
+            plot(self, x, y, |p, _| f(p));
+        });
+    }
+
+    /// Draws the outline of a polygon with the given vertices, passing the existing pixels to the
+    /// closure. See [`Drawing::draw_polygon`] and [`Drawing`].
+    #[inline]
+    fn draw_polygon_with_existing<F>(&mut self, vertices: &[Point], mut f: F)
+    where
+        F: FnMut(Point, Self::Pixel) -> Self::Pixel,
+        Self::Pixel: Clone,
+    {
+        polygon_outline_points(vertices, self.dimensions(), |x, y| {
+            plot(self, x, y, |p, existing| f(p, existing.clone()));
+        });
+    }
+
+    /// Draws the outline of a polygon with the given vertices, alpha blending over the existing
+    /// pixels. See [`Drawing::draw_polygon`] and [`Drawing`].
+    #[inline]
+    fn draw_polygon_blended<F>(&mut self, vertices: &[Point], mut f: F)
+    where
+        F: FnMut(Point) -> Self::Pixel,
+        Self::Pixel: Blend + Copy,
+    {
+        self.draw_polygon_with_existing(vertices, |p, existing| f(p).over(existing));
+    }
+
+    /// Draws a filled polygon with the given vertices, using the even-odd rule to determine what's
+    /// inside. A point is inside if it's center is, so polygons with less than 3 vertices draw
+    /// nothing.
+    #[inline]
+    fn fill_polygon<F>(&mut self, vertices: &[Point], mut f: F)
+    where
+        F: FnMut(Point) -> Self::Pixel,
+    {
+        polygon_fill_points(vertices, self.dimensions(), |x, y| {
+            plot(self, x, y, |p, _| f(p));
+        });
+    }
+
+    /// Draws a filled polygon with the given vertices, passing the existing pixels to the closure.
+    /// See [`Drawing::fill_polygon`] and [`Drawing`].
+    #[inline]
+    fn fill_polygon_with_existing<F>(&mut self, vertices: &[Point], mut f: F)
+    where
+        F: FnMut(Point, Self::Pixel) -> Self::Pixel,
+        Self::Pixel: Clone,
+    {
+        polygon_fill_points(vertices, self.dimensions(), |x, y| {
+            plot(self, x, y, |p, existing| f(p, existing.clone()));
+        });
+    }
+
+    /// Draws a filled polygon with the given vertices, alpha blending over the existing pixels. See
+    /// [`Drawing::fill_polygon`] and [`Drawing`].
+    #[inline]
+    fn fill_polygon_blended<F>(&mut self, vertices: &[Point], mut f: F)
+    where
+        F: FnMut(Point) -> Self::Pixel,
+        Self::Pixel: Blend + Copy,
+    {
+        self.fill_polygon_with_existing(vertices, |p, existing| f(p).over(existing));
+    }
+
+    /// Draws the outline of a circle with the given center and radius.
+    #[inline]
+    fn draw_circle<F>(&mut self, center: Point, radius: u32, mut f: F)
+    where
+        F: FnMut(Point) -> Self::Pixel,
+    {
+        circle_points(center, radius, self.dimensions(), |x, y| {
+            plot(self, x, y, |p, _| f(p));
+        });
+    }
+
+    /// Draws the outline of a circle with the given center and radius, passing the existing pixels
+    /// to the closure. See [`Drawing`].
+    #[inline]
+    fn draw_circle_with_existing<F>(&mut self, center: Point, radius: u32, mut f: F)
+    where
+        F: FnMut(Point, Self::Pixel) -> Self::Pixel,
+        Self::Pixel: Clone,
+    {
+        circle_points(center, radius, self.dimensions(), |x, y| {
+            plot(self, x, y, |p, existing| f(p, existing.clone()));
+        });
+    }
+
+    /// Draws the outline of a circle with the given center and radius, alpha blending over the
+    /// existing pixels. See [`Drawing`].
+    #[inline]
+    fn draw_circle_blended<F>(&mut self, center: Point, radius: u32, mut f: F)
+    where
+        F: FnMut(Point) -> Self::Pixel,
+        Self::Pixel: Blend + Copy,
+    {
+        self.draw_circle_with_existing(center, radius, |p, existing| f(p).over(existing));
+    }
+
+    /// Draws a filled circle with the given center and radius.
+    #[inline]
+    fn fill_circle<F>(&mut self, center: Point, radius: u32, mut f: F)
+    where
+        F: FnMut(Point) -> Self::Pixel,
+    {
+        disk_points(center, radius, self.dimensions(), |x, y| {
+            plot(self, x, y, |p, _| f(p));
+        });
+    }
+
+    /// Draws a filled circle with the given center and radius, passing the existing pixels to the
+    /// closure. See [`Drawing`].
+    #[inline]
+    fn fill_circle_with_existing<F>(&mut self, center: Point, radius: u32, mut f: F)
+    where
+        F: FnMut(Point, Self::Pixel) -> Self::Pixel,
+        Self::Pixel: Clone,
+    {
+        disk_points(center, radius, self.dimensions(), |x, y| {
+            plot(self, x, y, |p, existing| f(p, existing.clone()));
+        });
+    }
+
+    /// Draws a filled circle with the given center and radius, alpha blending over the existing
+    /// pixels. See [`Drawing`].
+    #[inline]
+    fn fill_circle_blended<F>(&mut self, center: Point, radius: u32, mut f: F)
+    where
+        F: FnMut(Point) -> Self::Pixel,
+        Self::Pixel: Blend + Copy,
+    {
+        self.fill_circle_with_existing(center, radius, |p, existing| f(p).over(existing));
+    }
+
+    /// Draws a grid over the whole view, with vertical and horizontal lines at every multiple of
+    /// `spacing` (including `0`). Since the closure receives the point, the axes can be told apart
+    /// from the other lines.
+    ///
+    /// # Panics
+    /// Panics if any of the components of `spacing` is zero.
+    #[inline]
+    fn draw_grid<F>(&mut self, spacing: (u32, u32), mut f: F)
+    where
+        F: FnMut(Point) -> Self::Pixel,
+    {
+        grid_points(self.dimensions(), spacing, |x, y| {
+            plot(self, x, y, |p, _| f(p));
+        });
+    }
+
+    /// Draws a grid over the whole view, passing the existing pixels to the closure. See
+    /// [`Drawing::draw_grid`] and [`Drawing`].
+    ///
+    /// # Panics
+    /// Panics if any of the components of `spacing` is zero.
+    #[inline]
+    fn draw_grid_with_existing<F>(&mut self, spacing: (u32, u32), mut f: F)
+    where
+        F: FnMut(Point, Self::Pixel) -> Self::Pixel,
+        Self::Pixel: Clone,
+    {
+        grid_points(self.dimensions(), spacing, |x, y| {
+            plot(self, x, y, |p, existing| f(p, existing.clone()));
+        });
+    }
+
+    /// Draws a grid over the whole view, alpha blending over the existing pixels. See
+    /// [`Drawing::draw_grid`] and [`Drawing`].
+    ///
+    /// # Panics
+    /// Panics if any of the components of `spacing` is zero.
+    #[inline]
+    fn draw_grid_blended<F>(&mut self, spacing: (u32, u32), mut f: F)
+    where
+        F: FnMut(Point) -> Self::Pixel,
+        Self::Pixel: Blend + Copy,
+    {
+        self.draw_grid_with_existing(spacing, |p, existing| f(p).over(existing));
+    }
+
+    /// Draws the outline of a rectangle with the given bounds.
+    #[inline]
+    fn draw_rect<F>(&mut self, bounds: Rect, mut f: F)
+    where
+        F: FnMut(Point) -> Self::Pixel,
+    {
+        rect_outline_points(bounds, self.dimensions(), |p| {
+            plot(self, i64::from(p.0), i64::from(p.1), |p, _| f(p));
+        });
+    }
+
+    /// Draws the outline of a rectangle with the given bounds, passing the existing pixels to the
+    /// closure. See [`Drawing`].
+    #[inline]
+    fn draw_rect_with_existing<F>(&mut self, bounds: Rect, mut f: F)
+    where
+        F: FnMut(Point, Self::Pixel) -> Self::Pixel,
+        Self::Pixel: Clone,
+    {
+        rect_outline_points(bounds, self.dimensions(), |p| {
+            plot(self, i64::from(p.0), i64::from(p.1), |p, existing| {
+                f(p, existing.clone())
+            });
+        });
+    }
+
+    /// Draws the outline of a rectangle with the given bounds, alpha blending over the existing
+    /// pixels. See [`Drawing`].
+    #[inline]
+    fn draw_rect_blended<F>(&mut self, bounds: Rect, mut f: F)
+    where
+        F: FnMut(Point) -> Self::Pixel,
+        Self::Pixel: Blend + Copy,
+    {
+        self.draw_rect_with_existing(bounds, |p, existing| f(p).over(existing));
+    }
+
+    /// Draws a filled rectangle with the given bounds.
+    #[inline]
+    fn fill_rect<F>(&mut self, bounds: Rect, mut f: F)
+    where
+        F: FnMut(Point) -> Self::Pixel,
+    {
+        let Some(clipped) = bounds.clamped(&self.bounds()) else {
+            return;
+        };
+
+        for (x, y) in clipped.points() {
+            plot(self, i64::from(x), i64::from(y), |p, _| f(p));
+        }
+    }
+
+    /// Draws a filled rectangle with the given bounds, passing the existing pixels to the closure.
+    /// See [`Drawing`].
+    #[inline]
+    fn fill_rect_with_existing<F>(&mut self, bounds: Rect, mut f: F)
+    where
+        F: FnMut(Point, Self::Pixel) -> Self::Pixel,
+        Self::Pixel: Clone,
+    {
+        let Some(clipped) = bounds.clamped(&self.bounds()) else {
+            return;
+        };
+
+        for (x, y) in clipped.points() {
+            plot(self, i64::from(x), i64::from(y), |p, existing| {
+                f(p, existing.clone())
+            });
+        }
+    }
+
+    /// Draws a filled rectangle with the given bounds, alpha blending over the existing pixels. See
+    /// [`Drawing`].
+    #[inline]
+    fn fill_rect_blended<F>(&mut self, bounds: Rect, mut f: F)
+    where
+        F: FnMut(Point) -> Self::Pixel,
+        Self::Pixel: Blend + Copy,
+    {
+        self.fill_rect_with_existing(bounds, |p, existing| f(p).over(existing));
+    }
+
+    /// Replaces the 4-connected region of pixels equal to the one at `start` with `fill`, like the
+    /// paint bucket of an image editor. Does nothing if `start` is out of bounds or the pixel at it
+    /// is already equal to `fill`.
+    ///
+    /// This is a scanline fill with an explicit stack, so it doesn't recurse no matter how large
+    /// the region is.
+    fn flood_fill(&mut self, start: Point, fill: Self::Pixel)
+    where
+        Self::Pixel: PartialEq + Clone,
+    {
+        let (width, height) = self.dimensions();
+        if start.0 >= width || start.1 >= height {
+            return;
+        }
+
+        let Some(target) = self.pixel(start).cloned() else {
+            return;
+        };
+
+        if target == fill {
+            return;
+        }
+
+        let matches = |view: &Self, point| view.pixel(point) == Some(&target);
+
+        let mut stack = vec![start];
+        while let Some((x, y)) = stack.pop() {
+            if !matches(self, (x, y)) {
+                continue;
+            }
+
+            // find the run of matching pixels containing this point and fill it
+            let (mut left, mut right) = (x, x);
+            while left > 0 && matches(self, (left - 1, y)) {
+                left -= 1;
+            }
+            while right + 1 < width && matches(self, (right + 1, y)) {
+                right += 1;
+            }
+
+            for x in left..=right {
+                plot(self, i64::from(x), i64::from(y), |_, _| fill.clone());
+            }
+
+            // seed every run of matching pixels directly above and below it
+            let neighbours = [y.checked_sub(1), (y + 1 < height).then_some(y + 1)];
+            for y in neighbours.into_iter().flatten() {
+                let mut in_run = false;
+                for x in left..=right {
+                    let matching = matches(self, (x, y));
+                    if matching && !in_run {
+                        stack.push((x, y));
+                    }
+                    in_run = matching;
+                }
+            }
+        }
+    }
+}
+
+impl<T> Drawing for T where T: ImgMut {}
+
+/// Sets the pixel at `(x, y)` to the result of `f`, if it's within the bounds of the view.
+#[inline]
+fn plot<I, F>(view: &mut I, x: i64, y: i64, f: F)
+where
+    I: ImgMut + ?Sized,
+    F: FnOnce(Point, &I::Pixel) -> I::Pixel,
+{
+    let (Ok(x), Ok(y)) = (u32::try_from(x), u32::try_from(y)) else {
+        return;
+    };
+
+    if let Some(pixel) = view.pixel_mut((x, y)) {
+        *pixel = f((x, y), pixel);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn with_existing_visits_points_once() {
+        let mut img = ImgBuf::from_fn(9, 9, |_| [0u8]);
+        img.draw_circle_with_existing((4, 4), 3, |_, [v]| [v + 1]);
+        img.fill_circle_with_existing((0, 8), 4, |_, [v]| [v + 10]);
+        img.draw_line_with_existing((8, 0), (0, 8), |_, [v]| [v + 100]);
+
+        assert!(img.pixels().all(|[v]| v % 10 <= 1 && v / 10 % 10 <= 1));
+        assert_eq!(img.pixel((4, 1)), Some(&[1]));
+        assert_eq!(img.pixel((0, 4)), Some(&[10]));
+        assert_eq!(img.pixel((4, 4)), Some(&[100]));
+        assert_eq!(img.pixels().filter(|[v]| v % 10 == 1).count(), 16);
+    }
+
+    #[test]
+    fn with_existing_matches_plain_shapes() {
+        // every shape drawn with it's variant incrementing the existing pixels must touch exactly
+        // the points the plain variant draws, and each of them once
+        let check = |plain: &dyn Fn(&mut ImgBuf<[u8; 1]>),
+                     with_existing: &dyn Fn(&mut ImgBuf<[u8; 1]>)| {
+            let (mut a, mut b) = (
+                ImgBuf::from_fn(12, 10, |_| [0u8]),
+                ImgBuf::from_fn(12, 10, |_| [0u8]),
+            );
+            plain(&mut a);
+            with_existing(&mut b);
+            assert!(a.pixels().eq(b.pixels()));
+        };
+
+        let triangle = [(1, 1), (10, 3), (4, 9)];
+        check(
+            &|img| img.draw_thick_line((1, 2), (9, 7), 3, |_| [1]),
+            &|img| img.draw_thick_line_with_existing((1, 2), (9, 7), 3, |_, [v]| [v + 1]),
+        );
+        check(
+            &|img| img.draw_bezier((0, 9), (6, 0), (11, 9), |_| [1]),
+            &|img| img.draw_bezier_with_existing((0, 9), (6, 0), (11, 9), |_, [v]| [v + 1]),
+        );
+        check(
+            &|img| img.draw_cubic_bezier((0, 0), (11, 0), (0, 9), (11, 9), |_| [1]),
+            &|img| {
+                img.draw_cubic_bezier_with_existing((0, 0), (11, 0), (0, 9), (11, 9), |_, [v]| {
+                    [v + 1]
+                });
+            },
+        );
+        check(&|img| img.draw_polygon(&triangle, |_| [1]), &|img| {
+            img.draw_polygon_with_existing(&triangle, |_, [v]| [v + 1]);
+        });
+        check(&|img| img.fill_polygon(&triangle, |_| [1]), &|img| {
+            img.fill_polygon_with_existing(&triangle, |_, [v]| [v + 1]);
+        });
+        check(
+            &|img| img.draw_rect(Rect::new((2, 2), (20, 5)), |_| [1]),
+            &|img| img.draw_rect_with_existing(Rect::new((2, 2), (20, 5)), |_, [v]| [v + 1]),
+        );
+        check(
+            &|img| img.fill_rect(Rect::new((2, 2), (20, 5)), |_| [1]),
+            &|img| img.fill_rect_with_existing(Rect::new((2, 2), (20, 5)), |_, [v]| [v + 1]),
+        );
+    }
+
+    #[test]
+    fn grid_lines() {
+        let mut img = ImgBuf::from_fn(10, 7, |_| [0u8]);
+        img.draw_grid_with_existing((4, 3), |(x, y), [v]| {
+            [v + if x == 0 || y == 0 { 10 } else { 1 }]
+        });
+
+        // lines at x = 0, 4, 8 and y = 0, 3, 6, with each point drawn once
+        assert!(img.pixels().all(|[v]| *v <= 10));
+        for ((x, y), [v]) in img.pixels_with_coords() {
+            let on_grid = x % 4 == 0 || y % 3 == 0;
+            assert_eq!(*v != 0, on_grid, "({x}, {y})");
+        }
+        assert_eq!(img.pixel((0, 5)), Some(&[10]));
+        assert_eq!(img.pixel((8, 6)), Some(&[1]));
+    }
+
+    #[test]
+    fn thick_lines() {
+        let count = |img: &ImgBuf<[u8; 1]>| img.pixels().filter(|[v]| *v != 0).count();
+
+        let mut img = ImgBuf::from_fn(9, 9, |_| [0u8]);
+        img.draw_thick_line((2, 5), (6, 5), 3, |_| [1]);
+        for ((x, y), [v]) in img.pixels_with_coords() {
+            let expected = (1..=7).contains(&x) && (4..=6).contains(&y);
+            assert_eq!(*v != 0, expected, "({x}, {y})");
+        }
+
+        let mut img = ImgBuf::from_fn(9, 9, |_| [0u8]);
+        img.draw_thick_line((4, 1), (4, 6), 2, |_| [1]);
+        assert_eq!(count(&img), 2 * 7);
+
+        // a diagonal line stays within half it's width of the segment
+        let mut img = ImgBuf::from_fn(20, 20, |_| [0u8]);
+        img.draw_thick_line((3, 3), (15, 15), 4, |_| [1]);
+        for ((x, y), [v]) in img.pixels_with_coords() {
+            let distance = (f64::from(x) - f64::from(y)).abs() / std::f64::consts::SQRT_2;
+            assert!(*v == 0 || distance <= 2.0, "({x}, {y})");
+        }
+        assert!(count(&img) > 4 * 17);
+
+        // width 1 matches draw_line, width 0 draws nothing, and lines are clipped
+        let (mut thin, mut thick) = (
+            ImgBuf::from_fn(9, 9, |_| [0u8]),
+            ImgBuf::from_fn(9, 9, |_| [0u8]),
+        );
+        thin.draw_line((0, 8), (7, 1), |_| [1]);
+        thick.draw_thick_line((0, 8), (7, 1), 1, |_| [1]);
+        thick.draw_thick_line((0, 0), (8, 8), 0, |_| [1]);
+        assert!(thin.pixels().eq(thick.pixels()));
+
+        let mut img = ImgBuf::from_fn(4, 4, |_| [0u8]);
+        img.draw_thick_line((3, 0), (30, 0), 3, |_| [1]);
+        assert_eq!(count(&img), 4);
+    }
+
+    #[test]
+    fn thick_lines_cover_their_rectangle() {
+        // every point whose center is within the rectangle is drawn exactly once, and no other
+        let lines = [
+            ((2, 3), (17, 11), 4),
+            ((17, 2), (1, 13), 3),
+            ((5, 0), (6, 15), 5),
+            ((0, 7), (19, 7), 2),
+            ((9, 9), (9, 9), 6),
+            ((3, 14), (40, 1), 7),
+        ];
+
+        for (start, end, width) in lines {
+            let mut img = ImgBuf::from_fn(20, 16, |_| [0u8]);
+            img.draw_thick_line_with_existing(start, end, width, |_, [v]| [v + 1]);
+
+            let half = f64::from(width) / 2.0;
+            let (dx, dy) = (
+                f64::from(end.0) - f64::from(start.0),
+                f64::from(end.1) - f64::from(start.1),
+            );
+            let length = dx.hypot(dy);
+            let (dir_x, dir_y) = if length > 0.0 {
+                (dx / length, dy / length)
+            } else {
+                (1.0, 0.0)
+            };
+
+            for ((x, y), [v]) in img.pixels_with_coords() {
+                let offset_x = f64::from(x) - f64::from(start.0);
+                let offset_y = f64::from(y) - f64::from(start.1);
+                let along = offset_x * dir_x + offset_y * dir_y;
+                let across = offset_x * dir_y - offset_y * dir_x;
+                let inside =
+                    (-half..length + half).contains(&along) && (-half..half).contains(&across);
+                assert_eq!(*v, u8::from(inside), "({x}, {y}) of {start:?} -> {end:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn blended_shapes() {
+        let opaque = RGBA8::new(0, 0, 255, 255);
+        let half = RGBA8::new(255, 0, 0, 128);
+
+        let mut img = ImgBuf::from_fn(8, 8, |_| opaque);
+        img.fill_rect_blended(Rect::new((0, 0), (4, 8)), |_| half);
+        img.draw_line_blended((0, 0), (7, 0), |_| RGBA8::new(0, 0, 0, 0));
+        img.fill_circle_blended((6, 6), 1, |_| RGBA8::new(0, 255, 0, 255));
+
+        // semi-transparent pixels are painted over the existing ones, not stamped
+        assert_eq!(img.pixel((1, 1)), Some(&RGBA8::new(128, 0, 127, 255)));
+        assert_eq!(img.pixel((0, 0)), Some(&RGBA8::new(128, 0, 127, 255)));
+        assert_eq!(img.pixel((5, 1)), Some(&opaque));
+        assert_eq!(img.pixel((6, 6)), Some(&RGBA8::new(0, 255, 0, 255)));
+
+        // every point is composited once, even where a thick line's rows overlap
+        let mut img = ImgBuf::from_fn(6, 6, |_| RGBA8::new(0, 0, 0, 0));
+        img.draw_thick_line_blended((0, 0), (5, 5), 3, |_| half);
+        img.draw_rect_blended(Rect::new((0, 0), (6, 6)), |_| half);
+        assert!(img.pixels().all(|p| p.a == 0 || p.a == 128 || p.a == 192));
+
+        // the remaining shapes blend the same way
+        let blended = RGBA8::new(128, 0, 127, 255);
+        let mut img = ImgBuf::from_fn(10, 10, |_| opaque);
+        img.fill_polygon_blended(&[(0, 0), (4, 0), (0, 4)], |_| half);
+        img.draw_polygon_blended(&[(9, 0), (9, 4)], |_| half);
+        img.draw_bezier_blended((0, 9), (4, 9), (9, 9), |_| half);
+        img.draw_cubic_bezier_blended((9, 6), (9, 6), (9, 7), (9, 7), |_| half);
+        assert_eq!(img.pixel((1, 1)), Some(&blended));
+        assert_eq!(img.pixel((9, 2)), Some(&blended));
+        assert_eq!(img.pixel((5, 9)), Some(&blended));
+        assert_eq!(img.pixel((9, 6)), Some(&blended));
+        assert_eq!(img.pixel((5, 5)), Some(&opaque));
+
+        let mut img = ImgBuf::from_fn(5, 5, |_| opaque);
+        img.draw_grid_blended((4, 4), |_| half);
+        assert_eq!(img.pixel((0, 2)), Some(&blended));
+        assert_eq!(img.pixel((2, 2)), Some(&opaque));
+    }
+
+    #[test]
+    fn bezier_curves() {
+        let count = |img: &ImgBuf<[u8; 1]>| img.pixels().filter(|[v]| *v != 0).count();
+
+        // a degenerate curve is a straight line
+        let mut img = ImgBuf::from_fn(16, 16, |_| [0u8]);
+        img.draw_bezier((0, 3), (7, 3), (15, 3), |_| [1]);
+        assert_eq!(count(&img), 16);
+        assert!((0..16).all(|x| img.pixel((x, 3)) == Some(&[1])));
+
+        // no point is visited more than once, even where the curve crosses itself
+        let mut img = ImgBuf::from_fn(32, 32, |_| [0u8]);
+        let mut visited = Vec::new();
+        img.draw_cubic_bezier((0, 0), (31, 31), (0, 31), (31, 0), |p| {
+            visited.push(p);
+            [1]
+        });
+        let total = visited.len();
+        visited.sort_unstable();
+        visited.dedup();
+        assert_eq!(visited.len(), total);
+        assert_eq!(count(&img), total);
+
+        let mut img = ImgBuf::from_fn(32, 32, |_| [0u8]);
+        img.draw_cubic_bezier((0, 16), (10, 0), (21, 31), (31, 16), |_| [1]);
+        assert_eq!(img.pixel((0, 16)), Some(&[1]));
+        assert_eq!(img.pixel((31, 16)), Some(&[1]));
+        assert_eq!(img.pixel((16, 16)), Some(&[1]));
+
+        // the curve is connected: every column between the endpoints has a point
+        assert!((0..32).all(|x| (0..32).any(|y| img.pixel((x, y)) == Some(&[1]))));
+
+        // only the visible part of a huge curve is walked, even where it passes twice
+        let mut img = ImgBuf::from_fn(10, 10, |_| [0u8]);
+        let mut visited = 0;
+        img.draw_bezier((0, 5), (u32::MAX, 5), (0, 5), |_| {
+            visited += 1;
+            [1]
+        });
+        assert_eq!(visited, 10);
+        assert_eq!(count(&img), 10);
+        assert!((0..10).all(|x| img.pixel((x, 5)) == Some(&[1])));
+    }
+
+    #[test]
+    fn polygons() {
+        let count = |img: &ImgBuf<[u8; 1]>| img.pixels().filter(|[v]| *v != 0).count();
+
+        // degenerate polygons
+        let mut img = ImgBuf::from_fn(8, 8, |_| [0u8]);
+        img.draw_polygon(&[], |_| [1]);
+        img.draw_polygon(&[(3, 3)], |_| [1]);
+        img.fill_polygon(&[(0, 0), (7, 7)], |_| [1]);
+        assert_eq!(count(&img), 0);
+        img.draw_polygon(&[(0, 0), (7, 7)], |_| [1]);
+        assert_eq!(count(&img), 8);
+
+        // every point of the outline is visited once
+        let mut img = ImgBuf::from_fn(8, 8, |_| [0u8]);
+        let mut visited = 0;
+        img.draw_polygon(&[(1, 1), (6, 1), (6, 6), (1, 6)], |_| {
+            visited += 1;
+            [1]
+        });
+        assert_eq!(visited, 20);
+        assert_eq!(count(&img), 20);
+
+        // a square covers the points whose centers are inside it
+        let mut img = ImgBuf::from_fn(8, 8, |_| [0u8]);
+        img.fill_polygon(&[(1, 1), (6, 1), (6, 6), (1, 6)], |_| [1]);
+        assert!(img
+            .pixels_with_coords()
+            .all(|((x, y), [v])| (*v == 1) == ((1..6).contains(&x) && (1..6).contains(&y))));
+
+        // the center of a pentagram is outside according to the even-odd rule
+        let star = [(32, 2), (50, 56), (3, 23), (61, 23), (14, 56)];
+        let mut img = ImgBuf::from_fn(64, 64, |_| [0u8]);
+        img.fill_polygon(&star, |_| [1]);
+        assert_eq!(img.pixel((32, 32)), Some(&[0]));
+        assert_eq!(img.pixel((32, 10)), Some(&[1]));
+        assert_eq!(img.pixel((10, 25)), Some(&[1]));
+        assert_eq!(img.pixel((0, 0)), Some(&[0]));
+
+        // polygons are clipped
+        let mut img = ImgBuf::from_fn(4, 4, |_| [0u8]);
+        img.fill_polygon(&[(2, 2), (100, 2), (100, 100), (2, 100)], |_| [1]);
+        img.draw_polygon(&[(2, 0), (100, 0), (100, 1)], |_| [2]);
+        assert_eq!(count(&img), 6);
+
+        let mut img = ImgBuf::from_fn(4, 4, |_| [0u8]);
+        let mut visited = 0;
+        let far = u32::MAX;
+        img.draw_polygon(&[(0, 1), (far, 1), (far, 2), (0, 2)], |_| {
+            visited += 1;
+            [1]
+        });
+        assert_eq!(visited, 8);
+        assert!(img
+            .pixels_with_coords()
+            .all(|((_, y), [v])| (*v == 1) == (y == 1 || y == 2)));
+    }
+
+    #[test]
+    fn rects() {
+        let mut img = ImgBuf::from_fn(6, 5, |_| [0u8]);
+        img.draw_rect(Rect::new((1, 1), (4, 3)), |_| [1]);
+        img.fill_rect(Rect::new((2, 2), (2, 1)), |_| [2]);
+
+        for ((x, y), [v]) in img.pixels_with_coords() {
+            let expected = match (x, y) {
+                (2..=3, 2) => 2,
+                (1..=4, 1 | 3) | (1 | 4, 2) => 1,
+                _ => 0,
+            };
+            assert_eq!(*v, expected, "({x}, {y})");
+        }
+
+        // corners are drawn once, and degenerate rects are lines or points
+        let mut img = ImgBuf::from_fn(6, 5, |_| [0u8]);
+        img.draw_rect(Rect::new((0, 0), (3, 3)), |_| [1]);
+        img.draw_rect(Rect::new((0, 4), (6, 1)), |_| [1]);
+        img.draw_rect(Rect::new((5, 0), (1, 1)), |_| [1]);
+        assert_eq!(img.pixels().filter(|[v]| *v == 1).count(), 8 + 6 + 1);
+
+        // rects past the bounds are clipped
+        let mut img = ImgBuf::from_fn(4, 4, |_| [0u8]);
+        img.draw_rect(Rect::new((2, 2), (10, 10)), |_| [1]);
+        img.fill_rect(Rect::new((3, 0), (10, 1)), |_| [2]);
+        img.fill_rect(Rect::new((9, 9), (1, 1)), |_| [3]);
+        assert_eq!(img.pixels().filter(|[v]| *v == 1).count(), 3);
+        assert_eq!(img.pixels().filter(|[v]| *v == 2).count(), 1);
+        assert_eq!(img.pixel((3, 0)), Some(&[2]));
+
+        // only the visible parts of the edges are walked, each point once
+        let mut img = ImgBuf::from_fn(4, 3, |_| [0u8]);
+        let mut visited = 0;
+        img.draw_rect(Rect::new((0, 0), (u32::MAX, u32::MAX)), |_| {
+            visited += 1;
+            [1]
+        });
+        img.draw_rect(Rect::new((1, 2), (u32::MAX - 1, 1)), |_| [2]);
+        img.draw_rect(Rect::new((u32::MAX - 1, 0), (1, 1)), |_| [3]);
+        assert_eq!(visited, 6);
+        for ((x, y), [v]) in img.pixels_with_coords() {
+            let expected = match (x, y) {
+                (1..=3, 2) => 2,
+                (_, 0) | (0, _) => 1,
+                _ => 0,
+            };
+            assert_eq!(*v, expected, "({x}, {y})");
+        }
+    }
+
+    #[test]
+    fn flood_fill_regions() {
+        let mut img = ImgBuf::from_fn(9, 9, |_| [0u8]);
+        img.draw_circle((4, 4), 3, |_| [1]);
+
+        img.flood_fill((4, 4), [2]);
+        assert_eq!(img.pixels().filter(|[v]| *v == 2).count(), 21);
+        assert_eq!(img.pixel((0, 0)), Some(&[0]));
+
+        // out of bounds and already filled starts do nothing
+        img.flood_fill((9, 0), [3]);
+        img.flood_fill((4, 4), [2]);
+        assert!(img.pixels().all(|[v]| *v < 3));
+
+        // the outside wraps around the circle, without crossing it
+        img.flood_fill((0, 0), [3]);
+        assert!(img.pixels().all(|[v]| *v != 0));
+
+        // a large serpentine region doesn't overflow the stack: walls on odd columns, with a gap
+        // alternating between the top and the bottom row
+        let mut img = ImgBuf::from_fn(1000, 1000, |(x, y)| {
+            let gap = if x % 4 == 1 { 0 } else { 999 };
+            [u8::from(x % 2 == 1 && y != gap)]
+        });
+        img.flood_fill((0, 0), [2]);
+        assert!(img.pixels().all(|[v]| *v != 0));
+    }
+
+    #[test]
+    fn circles_past_the_edges() {
+        // every point of the outline is outside of the image, so nothing may be drawn
+        let mut img = ImgBuf::from_fn(6, 6, |_| [0u8]);
+        img.draw_circle((5, 5), 8, |_| [1]);
+        assert!(img.pixels().all(|[v]| *v == 0));
+
+        // the disk covers the whole image, and every point must be visited exactly once
+        img.fill_circle_with_existing((5, 5), 8, |_, [v]| [v + 1]);
+        assert!(img.pixels().all(|[v]| *v == 1));
+    }
+
+    #[test]
+    fn huge_shapes_only_walk_the_view() {
+        let mut img = ImgBuf::from_fn(10, 10, |_| [0u8]);
+        let mut visited = 0;
+        img.fill_circle_with_existing((5, 5), u32::MAX, |_, [v]| {
+            visited += 1;
+            [v + 1]
+        });
+        assert_eq!(visited, 100);
+        assert!(img.pixels().all(|[v]| *v == 1));
+
+        // an outline that passes through the view only draws the visible part of it
+        img.draw_circle((5, 5), u32::MAX, |_| [2]);
+        img.draw_circle((1_000_003, 5), 1_000_000, |_| [3]);
+        assert!(img
+            .pixels_with_coords()
+            .all(|((x, _), [v])| *v == if x == 3 { 3 } else { 1 }));
+
+        img.draw_line((0, 0), (u32::MAX, u32::MAX), |_| [4]);
+        img.draw_line((u32::MAX, 9), (0, 9), |_| [5]);
+        assert!((0..9).all(|i| img.pixel((i, i)) == Some(&[4])));
+        assert!((0..10).all(|x| img.pixel((x, 9)) == Some(&[5])));
+    }
+
+    #[test]
+    fn shapes_are_clipped() {
+        let mut img = Rgb8Img::new(4, 4);
+        img.fill_circle((3, 3), 10, |_| RGB8::new(1, 1, 1));
+        img.draw_line((2, 0), (50, 0), |_| RGB8::new(2, 2, 2));
+
+        assert_eq!(img.pixel((0, 0)), Some(&RGB8::new(1, 1, 1)));
+        assert_eq!(img.pixel((3, 0)), Some(&RGB8::new(2, 2, 2)));
+
+        // points past the right edge must not wrap around into the next row
+        let mut img = ImgBuf::from_fn(8, 8, |_| [0u8]);
+        img.draw_circle((7, 4), 3, |_| [1]);
+        img.draw_line((6, 6), (12, 6), |_| [1]);
+        img.draw_thick_line((6, 0), (12, 0), 3, |_| [1]);
+        assert!(img
+            .pixels_with_coords()
+            .all(|((x, _), [v])| x >= 4 || *v == 0));
+        assert_eq!(img.pixel((7, 1)), Some(&[1]));
+    }
+}
@@ -0,0 +1,360 @@
+use super::{CommonImgDecoder, ImgDecoder, ImgEncoder};
+use crate::buffer::common::CommonImgBuf;
+use crate::pixel::common::*;
+use crate::prelude::{Img, ImgBuf, ImgMut};
+use thiserror::Error;
+
+/// The size of the file header (`BITMAPFILEHEADER`).
+const FILE_HEADER_SIZE: usize = 14;
+/// The size of the smallest supported DIB header (`BITMAPINFOHEADER`).
+const INFO_HEADER_SIZE: usize = 40;
+/// The `BI_RGB` compression, i.e. uncompressed.
+const BI_RGB: u32 = 0;
+/// The resolution written to BMPs, in pixels per metre. Equivalent to 72 DPI.
+const PIXELS_PER_METRE: i32 = 2835;
+
+/// Errors that can happen during decoding operations.
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("invalid BMP signature")]
+    InvalidSignature,
+    #[error("BMP is truncated")]
+    Truncated,
+    #[error("unsupported DIB header of size {0}")]
+    UnsupportedHeader(u32),
+    #[error("unsupported compression: {0}")]
+    UnsupportedCompression(u32),
+    #[error("unsupported bit depth: {0}")]
+    UnsupportedBitDepth(u16),
+    #[error("wrong bit depth: {0}")]
+    WrongBitDepth(u16),
+    #[error("invalid dimensions: {width}x{height}")]
+    InvalidDimensions { width: i32, height: i32 },
+}
+
+/// A BMP whose pixel data hasn't been converted into a buffer yet.
+struct RawBmp {
+    width: u32,
+    height: u32,
+    bits_per_pixel: u16,
+    top_down: bool,
+    row_size: usize,
+    data: Vec<u8>,
+    offset: usize,
+}
+
+/// Reads a little-endian value from `bytes` at `offset`.
+macro_rules! read_le {
+    ($ty:ty, $bytes:expr, $offset:expr) => {
+        <$ty>::from_le_bytes(
+            $bytes[$offset..$offset + std::mem::size_of::<$ty>()]
+                .try_into()
+                .expect("slice has the size of the value"),
+        )
+    };
+}
+
+impl RawBmp {
+    fn read<R>(mut reader: R) -> Result<Self, Error>
+    where
+        R: std::io::Read,
+    {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+
+        if !data.starts_with(b"BM") {
+            return Err(Error::InvalidSignature);
+        }
+
+        if data.len() < FILE_HEADER_SIZE + INFO_HEADER_SIZE {
+            return Err(Error::Truncated);
+        }
+
+        let offset = read_le!(u32, data, 10) as usize;
+        let header_size = read_le!(u32, data, 14);
+        if (header_size as usize) < INFO_HEADER_SIZE {
+            return Err(Error::UnsupportedHeader(header_size));
+        }
+
+        let (width, height) = (read_le!(i32, data, 18), read_le!(i32, data, 22));
+        let bits_per_pixel = read_le!(u16, data, 28);
+        let compression = read_le!(u32, data, 30);
+
+        if compression != BI_RGB {
+            return Err(Error::UnsupportedCompression(compression));
+        }
+
+        if bits_per_pixel != 24 && bits_per_pixel != 32 {
+            return Err(Error::UnsupportedBitDepth(bits_per_pixel));
+        }
+
+        // a negative height means the rows are stored top-down instead of bottom-up
+        if width <= 0 || height == 0 || height == i32::MIN {
+            return Err(Error::InvalidDimensions { width, height });
+        }
+
+        let (width, top_down, height) = (width as u32, height < 0, height.unsigned_abs());
+        let row_size = row_size(width, bits_per_pixel);
+        let data_size = row_size.checked_mul(height as usize);
+        if data_size.map_or(true, |size| data.len().saturating_sub(offset) < size) {
+            return Err(Error::Truncated);
+        }
+
+        Ok(Self {
+            width,
+            height,
+            bits_per_pixel,
+            top_down,
+            row_size,
+            data,
+            offset,
+        })
+    }
+
+    /// Returns the bytes of the pixel at the given coordinates.
+    fn pixel(&self, (x, y): (u32, u32)) -> &[u8] {
+        let row = if self.top_down {
+            y
+        } else {
+            self.height - 1 - y
+        };
+
+        let bytes_per_pixel = usize::from(self.bits_per_pixel / 8);
+        let start = self.offset + row as usize * self.row_size + x as usize * bytes_per_pixel;
+        &self.data[start..start + bytes_per_pixel]
+    }
+
+    fn into_rgb(self) -> ImgBuf<RGB8> {
+        ImgBuf::from_fn(self.width, self.height, |coords| {
+            let bgr = self.pixel(coords);
+            RGB8::new(bgr[2], bgr[1], bgr[0])
+        })
+    }
+
+    fn into_rgba(self) -> ImgBuf<RGBA8> {
+        let mut img = ImgBuf::from_fn(self.width, self.height, |coords| {
+            let bgra = self.pixel(coords);
+            RGBA8::new(bgra[2], bgra[1], bgra[0], bgra[3])
+        });
+
+        // the fourth byte of uncompressed 32-bit BMPs is often left unused (i.e. zeroed) instead
+        // of holding alpha, in which case the image is meant to be opaque
+        if img.pixels().all(|p| p.a == 0) {
+            img.pixels_mut().for_each(|p| p.a = u8::MAX);
+        }
+
+        img
+    }
+}
+
+/// The size, in bytes, of a row of a BMP. Rows are padded to a multiple of 4 bytes.
+fn row_size(width: u32, bits_per_pixel: u16) -> usize {
+    (width as usize * usize::from(bits_per_pixel / 8) + 3) & !3
+}
+
+/// A BMP Decoder.
+///
+/// Uncompressed 24-bit BMPs are decoded as [`RGB8`] and uncompressed 32-bit ones as [`RGBA8`].
+///
+/// It doesn't take [`Limits`][crate::formats::Limits]: the dimensions in the header are checked
+/// against the size of the pixel data that was actually read before the image is allocated, so the
+/// decoded image is never much larger than the input.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Decoder;
+
+impl ImgDecoder<RGB8> for Decoder {
+    type Output = ImgBuf<RGB8>;
+    type Error = Error;
+
+    fn decode<R>(&mut self, reader: R) -> Result<Self::Output, Self::Error>
+    where
+        R: std::io::Read,
+    {
+        let raw = RawBmp::read(reader)?;
+        match raw.bits_per_pixel {
+            24 => Ok(raw.into_rgb()),
+            depth => Err(Error::WrongBitDepth(depth)),
+        }
+    }
+}
+
+impl ImgDecoder<RGBA8> for Decoder {
+    type Output = ImgBuf<RGBA8>;
+    type Error = Error;
+
+    fn decode<R>(&mut self, reader: R) -> Result<Self::Output, Self::Error>
+    where
+        R: std::io::Read,
+    {
+        let raw = RawBmp::read(reader)?;
+        match raw.bits_per_pixel {
+            32 => Ok(raw.into_rgba()),
+            depth => Err(Error::WrongBitDepth(depth)),
+        }
+    }
+}
+
+impl CommonImgDecoder for Decoder {
+    type Error = Error;
+
+    fn decode_common<R>(&mut self, reader: R) -> Result<CommonImgBuf, Self::Error>
+    where
+        R: std::io::Read,
+    {
+        let raw = RawBmp::read(reader)?;
+        match raw.bits_per_pixel {
+            24 => Ok(CommonImgBuf::Rgb8(raw.into_rgb())),
+            _ => Ok(CommonImgBuf::Rgba8(raw.into_rgba())),
+        }
+    }
+}
+
+/// A BMP Encoder. Images are written uncompressed, with their rows bottom-up.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Encoder {
+    /// Whether [`RGBA8`] images should be written as 32-bit BMPs, keeping their alpha channel.
+    /// Otherwise, their alpha channel is dropped and they're written as 24-bit BMPs, which is
+    /// what most software expects.
+    pub alpha: bool,
+}
+
+/// Writes a view as an uncompressed BMP, with the bytes of each pixel given by `to_bgra`.
+fn write_bmp<W, I, F>(
+    mut writer: W,
+    img: &I,
+    bits_per_pixel: u16,
+    to_bgra: F,
+) -> std::io::Result<()>
+where
+    W: std::io::Write,
+    I: Img,
+    F: Fn(&I::Pixel) -> [u8; 4],
+{
+    let (width, height) = img.dimensions();
+    let row_size = row_size(width, bits_per_pixel);
+    let data_size = row_size as u64 * u64::from(height);
+    let headers_size = (FILE_HEADER_SIZE + INFO_HEADER_SIZE) as u64;
+
+    let (Ok(signed_width), Ok(signed_height), Ok(file_size)) = (
+        i32::try_from(width),
+        i32::try_from(height),
+        u32::try_from(headers_size + data_size),
+    ) else {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "image is too large to be written as a BMP",
+        ));
+    };
+
+    let mut bytes = Vec::with_capacity(file_size as usize);
+
+    // file header
+    bytes.extend_from_slice(b"BM");
+    bytes.extend_from_slice(&file_size.to_le_bytes());
+    bytes.extend_from_slice(&[0; 4]);
+    bytes.extend_from_slice(&(headers_size as u32).to_le_bytes());
+
+    // info header
+    bytes.extend_from_slice(&(INFO_HEADER_SIZE as u32).to_le_bytes());
+    bytes.extend_from_slice(&signed_width.to_le_bytes());
+    bytes.extend_from_slice(&signed_height.to_le_bytes());
+    bytes.extend_from_slice(&1u16.to_le_bytes());
+    bytes.extend_from_slice(&bits_per_pixel.to_le_bytes());
+    bytes.extend_from_slice(&BI_RGB.to_le_bytes());
+    bytes.extend_from_slice(&(data_size as u32).to_le_bytes());
+    bytes.extend_from_slice(&PIXELS_PER_METRE.to_le_bytes());
+    bytes.extend_from_slice(&PIXELS_PER_METRE.to_le_bytes());
+    bytes.extend_from_slice(&[0; 8]);
+
+    // pixel data, bottom-up
+    let bytes_per_pixel = usize::from(bits_per_pixel / 8);
+    for y in (0..height).rev() {
+        let row_start = bytes.len();
+        for x in 0..width {
+            // SAFETY: x and y are within the dimensions of the view.
+            let pixel = unsafe { img.pixel_unchecked((x, y)) };
+            bytes.extend_from_slice(&to_bgra(pixel)[..bytes_per_pixel]);
+        }
+
+        bytes.resize(row_start + row_size, 0);
+    }
+
+    writer.write_all(&bytes)
+}
+
+impl ImgEncoder<RGB8> for Encoder {
+    fn encode<W, I>(&mut self, writer: W, img: I) -> std::io::Result<()>
+    where
+        W: std::io::Write,
+        I: Img<Pixel = RGB8>,
+    {
+        write_bmp(writer, &img, 24, |p| [p.b, p.g, p.r, 0])
+    }
+}
+
+impl ImgEncoder<RGBA8> for Encoder {
+    fn encode<W, I>(&mut self, writer: W, img: I) -> std::io::Result<()>
+    where
+        W: std::io::Write,
+        I: Img<Pixel = RGBA8>,
+    {
+        let bits_per_pixel = if self.alpha { 32 } else { 24 };
+        write_bmp(writer, &img, bits_per_pixel, |p| [p.b, p.g, p.r, p.a])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::ImgCore;
+
+    #[test]
+    fn rgb_round_trip() {
+        // a width of 3 needs 3 bytes of padding per row
+        let img = ImgBuf::from_fn(3, 2, |(x, y)| RGB8::new(x as u8, y as u8, 9));
+
+        let mut bytes = Vec::new();
+        Encoder::default().encode(&mut bytes, &img).unwrap();
+        assert_eq!(bytes.len(), 54 + 12 * 2);
+        // the first row stored is the bottom one
+        assert_eq!(&bytes[54..57], &[9, 1, 0]);
+
+        let CommonImgBuf::Rgb8(decoded) = Decoder.decode_common(bytes.as_slice()).unwrap() else {
+            panic!("24-bit BMP should decode as RGB8");
+        };
+
+        assert_eq!(decoded.dimensions(), img.dimensions());
+        assert!(decoded.pixels().eq(img.pixels()));
+
+        // a negative height means the rows are stored top-down
+        bytes[22..26].copy_from_slice(&(-2i32).to_le_bytes());
+        let decoded = ImgDecoder::<RGB8>::decode(&mut Decoder, bytes.as_slice()).unwrap();
+        assert_eq!(decoded.pixel((0, 0)), Some(&RGB8::new(0, 1, 9)));
+    }
+
+    #[test]
+    fn rgba_round_trip() {
+        let img = ImgBuf::from_fn(2, 2, |(x, y)| RGBA8::new(x as u8, y as u8, 9, 128));
+
+        let mut bytes = Vec::new();
+        Encoder { alpha: true }.encode(&mut bytes, &img).unwrap();
+        let decoded = ImgDecoder::<RGBA8>::decode(&mut Decoder, bytes.as_slice()).unwrap();
+        assert!(decoded.pixels().eq(img.pixels()));
+
+        let mut bytes = Vec::new();
+        Encoder::default().encode(&mut bytes, &img).unwrap();
+        let result = ImgDecoder::<RGBA8>::decode(&mut Decoder, bytes.as_slice());
+        assert!(matches!(result, Err(Error::WrongBitDepth(24))));
+    }
+
+    #[test]
+    fn invalid_data_fails() {
+        let result = Decoder.decode_common(&b"definitely not a bmp"[..]);
+        assert!(matches!(result, Err(Error::InvalidSignature)));
+
+        let result = Decoder.decode_common(&b"BM but truncated"[..]);
+        assert!(matches!(result, Err(Error::Truncated)));
+    }
+}
@@ -0,0 +1,311 @@
+use super::{CommonImgDecoder, ImgDecoder, ImgEncoder, Limits, LimitsExceeded};
+use crate::buffer::common::CommonImgBuf;
+use crate::pixel::{common::*, Pixel};
+use crate::prelude::{Img, ImgBuf};
+use thiserror::Error;
+
+pub use jpeg_decoder::{Error as DecodingError, PixelFormat};
+pub use jpeg_encoder::EncodingError;
+
+/// Errors that can happen during decoding operations.
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("decoding error: {0}")]
+    Decoding(#[from] DecodingError),
+    #[error("unsupported pixel format: {0:?}")]
+    UnsupportedPixelFormat(PixelFormat),
+    #[error("wrong pixel format: {0:?}")]
+    WrongPixelFormat(PixelFormat),
+    #[error("image of {width}x{height} exceeds the decoding limits")]
+    TooLarge { width: u32, height: u32 },
+}
+
+impl From<LimitsExceeded> for Error {
+    #[inline]
+    fn from(LimitsExceeded { width, height }: LimitsExceeded) -> Self {
+        Self::TooLarge { width, height }
+    }
+}
+
+/// Options that control how a JPEG is decoded by [`Decoder`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DecodeOptions {
+    /// The limits on the size of the decoded image. Images which exceed them fail to decode with
+    /// [`Error::TooLarge`] right after their header is read, before anything is allocated for
+    /// them.
+    pub limits: Limits,
+}
+
+/// A JPEG Decoder.
+///
+/// Baseline and progressive color JPEGs are decoded as [`RGB8`] and grayscale ones as 8-bit
+/// [`Gray`] (or 16-bit, for lossless JPEGs with a higher precision). CMYK JPEGs are unsupported.
+/// [`Decoder::default`] creates one with the default options.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Decoder {
+    pub options: DecodeOptions,
+}
+
+impl Decoder {
+    /// Creates a new [`Decoder`] with the given options.
+    #[inline]
+    pub fn with_options(options: DecodeOptions) -> Self {
+        Self { options }
+    }
+}
+
+/// A decoded JPEG whose pixel data hasn't been converted into a buffer yet.
+struct RawImage {
+    format: PixelFormat,
+    width: u32,
+    height: u32,
+    data: Vec<u8>,
+}
+
+impl RawImage {
+    fn read<R>(reader: R, options: DecodeOptions) -> Result<Self, Error>
+    where
+        R: std::io::Read,
+    {
+        let mut decoder = jpeg_decoder::Decoder::new(reader);
+        decoder.read_info()?;
+        let info = decoder
+            .info()
+            .expect("info is available after reading it successfully");
+
+        let (width, height) = (u32::from(info.width), u32::from(info.height));
+        let data_size =
+            u64::from(width) * u64::from(height) * info.pixel_format.pixel_bytes() as u64;
+        options.limits.check((width, height), data_size)?;
+
+        // jpeg-decoder checks the size of it's own output against this as well, as a backstop
+        if let Some(max_bytes) = options.limits.max_bytes {
+            decoder.set_max_decoding_buffer_size(usize::try_from(max_bytes).unwrap_or(usize::MAX));
+        }
+
+        let data = decoder.decode()?;
+        let info = decoder
+            .info()
+            .expect("info is available after decoding successfully");
+
+        Ok(Self {
+            format: info.pixel_format,
+            width: u32::from(info.width),
+            height: u32::from(info.height),
+            data,
+        })
+    }
+
+    /// Copies the pixel data, which is in native endianness, into a buffer.
+    fn into_buf<P>(self) -> ImgBuf<P>
+    where
+        P: Pixel + bytemuck::Pod + Default,
+    {
+        let mut container = vec![P::default(); self.width as usize * self.height as usize];
+        bytemuck::cast_slice_mut(&mut container).copy_from_slice(&self.data);
+
+        ImgBuf::from_container(container, self.width, self.height)
+    }
+}
+
+macro_rules! impl_decoder {
+    ($pixel_ty:ty, $format:ident) => {
+        impl ImgDecoder<$pixel_ty> for Decoder {
+            type Output = ImgBuf<$pixel_ty>;
+            type Error = Error;
+
+            fn decode<R>(&mut self, reader: R) -> Result<Self::Output, Self::Error>
+            where
+                R: std::io::Read,
+            {
+                let raw = RawImage::read(reader, self.options)?;
+                match raw.format {
+                    PixelFormat::$format => Ok(raw.into_buf()),
+                    format => Err(Error::WrongPixelFormat(format)),
+                }
+            }
+        }
+    };
+}
+
+impl_decoder!(RGB<u8>, RGB24);
+impl_decoder!(Gray<u8>, L8);
+impl_decoder!(Gray<u16>, L16);
+
+impl CommonImgDecoder for Decoder {
+    type Error = Error;
+
+    fn decode_common<R>(&mut self, reader: R) -> Result<CommonImgBuf, Self::Error>
+    where
+        R: std::io::Read,
+    {
+        let raw = RawImage::read(reader, self.options)?;
+        match raw.format {
+            PixelFormat::RGB24 => Ok(CommonImgBuf::Rgb8(raw.into_buf())),
+            PixelFormat::L8 => Ok(CommonImgBuf::Gray8(raw.into_buf())),
+            PixelFormat::L16 => Ok(CommonImgBuf::Gray16(raw.into_buf())),
+            format => Err(Error::UnsupportedPixelFormat(format)),
+        }
+    }
+}
+
+/// A JPEG Encoder.
+#[derive(Debug, Clone, Copy)]
+pub struct Encoder {
+    /// The quality of the encoded image, from 1 (worst) to 100 (best). Defaults to 90.
+    pub quality: u8,
+}
+
+impl Encoder {
+    /// Creates a new [`Encoder`] with the given quality.
+    #[inline]
+    pub fn new(quality: u8) -> Self {
+        Self { quality }
+    }
+}
+
+impl Default for Encoder {
+    fn default() -> Self {
+        Self { quality: 90 }
+    }
+}
+
+/// Converts an [`EncodingError`] into an [`std::io::Error`], unwrapping it if it's an IO error.
+fn io_error(error: EncodingError) -> std::io::Error {
+    match error {
+        EncodingError::IoError(error) => error,
+        error => std::io::Error::new(std::io::ErrorKind::InvalidInput, error),
+    }
+}
+
+macro_rules! impl_encoder {
+    ($pixel_ty:ty, $color_ty:ident) => {
+        impl ImgEncoder<$pixel_ty> for Encoder {
+            fn encode<W, I>(&mut self, writer: W, img: I) -> std::io::Result<()>
+            where
+                W: std::io::Write,
+                I: Img<Pixel = $pixel_ty>,
+            {
+                let (Ok(width), Ok(height)) =
+                    (u16::try_from(img.width()), u16::try_from(img.height()))
+                else {
+                    return Err(std::io::Error::new(
+                        std::io::ErrorKind::InvalidInput,
+                        "JPEG dimensions can't exceed 65535x65535",
+                    ));
+                };
+
+                let mut data = Vec::with_capacity(img.size() * std::mem::size_of::<$pixel_ty>());
+                for chunk in img.pixel_chunks() {
+                    data.extend_from_slice(bytemuck::must_cast_slice(chunk));
+                }
+
+                jpeg_encoder::Encoder::new(writer, self.quality.clamp(1, 100))
+                    .encode(&data, width, height, jpeg_encoder::ColorType::$color_ty)
+                    .map_err(io_error)
+            }
+        }
+    };
+}
+
+impl_encoder!(RGB<u8>, Rgb);
+impl_encoder!(Gray<u8>, Luma);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::ImgCore;
+
+    #[test]
+    fn rgb_round_trip() {
+        let img = ImgBuf::from_fn(16, 8, |(x, _)| {
+            if x < 8 {
+                RGB8::new(200, 40, 40)
+            } else {
+                RGB8::new(30, 90, 220)
+            }
+        });
+
+        let mut bytes = Vec::new();
+        Encoder::new(100).encode(&mut bytes, &img).unwrap();
+
+        let CommonImgBuf::Rgb8(decoded) =
+            Decoder::default().decode_common(bytes.as_slice()).unwrap()
+        else {
+            panic!("color JPEG should decode as RGB8");
+        };
+
+        assert_eq!(decoded.dimensions(), img.dimensions());
+        for (a, b) in decoded.pixels().zip(img.pixels()) {
+            assert!(a.r.abs_diff(b.r) <= 8 && a.g.abs_diff(b.g) <= 8 && a.b.abs_diff(b.b) <= 8);
+        }
+    }
+
+    #[test]
+    fn gray_round_trip() {
+        let img = ImgBuf::from_fn(9, 7, |(x, y)| {
+            <Gray<u8> as Pixel>::new([(x * 20 + y) as u8])
+        });
+
+        let mut bytes = Vec::new();
+        Encoder::default().encode(&mut bytes, &img).unwrap();
+
+        let decoded =
+            ImgDecoder::<Gray<u8>>::decode(&mut Decoder::default(), bytes.as_slice()).unwrap();
+        assert_eq!(decoded.dimensions(), img.dimensions());
+
+        let result = ImgDecoder::<RGB8>::decode(&mut Decoder::default(), bytes.as_slice());
+        assert!(matches!(
+            result,
+            Err(Error::WrongPixelFormat(PixelFormat::L8))
+        ));
+    }
+
+    #[test]
+    fn decoding_limits() {
+        // SOI and a frame header for a 65535x65535 color image, without any image data
+        let bytes = [
+            0xFF, 0xD8, 0xFF, 0xC0, 0x00, 0x11, 0x08, 0xFF, 0xFF, 0xFF, 0xFF, 0x03, 0x01, 0x11,
+            0x00, 0x02, 0x11, 0x00, 0x03, 0x11, 0x00,
+        ];
+
+        let mut decoder = Decoder::with_options(DecodeOptions {
+            limits: Limits {
+                max_pixels: Some(4096 * 4096),
+                ..Default::default()
+            },
+        });
+        assert!(matches!(
+            decoder.decode_common(&bytes[..]),
+            Err(Error::TooLarge {
+                width: 65535,
+                height: 65535
+            })
+        ));
+
+        let img = ImgBuf::from_fn(8, 8, |_| RGB8::new(1, 2, 3));
+        let mut bytes = Vec::new();
+        Encoder::default().encode(&mut bytes, &img).unwrap();
+
+        let mut decoder = Decoder::with_options(DecodeOptions {
+            limits: Limits {
+                max_bytes: Some(8 * 8 * 3 - 1),
+                ..Default::default()
+            },
+        });
+        let result = ImgDecoder::<RGB8>::decode(&mut decoder, bytes.as_slice());
+        assert!(matches!(result, Err(Error::TooLarge { .. })));
+
+        decoder.options.limits.max_bytes = Some(8 * 8 * 3);
+        let result = ImgDecoder::<RGB8>::decode(&mut decoder, bytes.as_slice());
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn invalid_data_fails() {
+        let result = Decoder::default().decode_common(&b"definitely not a jpeg"[..]);
+        assert!(matches!(result, Err(Error::Decoding(_))));
+    }
+}
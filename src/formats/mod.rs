@@ -1,8 +1,87 @@
+#[cfg(feature = "bmp")]
+pub mod bmp;
+#[cfg(feature = "jpeg")]
+pub mod jpeg;
 #[cfg(feature = "png")]
 pub mod png;
+#[cfg(feature = "pnm")]
+pub mod pnm;
 
 use crate::{buffer::common::CommonImgBuf, view::Img};
 
+/// An image format, as recognized by [`guess_format`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ImageFormat {
+    Png,
+    Jpeg,
+    Bmp,
+    /// Binary PGM (P5) or PPM (P6).
+    Pnm,
+}
+
+/// Guesses the format of an image from the magic bytes at it's start. Formats are recognized even
+/// if the feature needed to decode them isn't enabled.
+pub fn guess_format(bytes: &[u8]) -> Option<ImageFormat> {
+    const SIGNATURES: [(&[u8], ImageFormat); 5] = [
+        (b"\x89PNG\r\n\x1a\n", ImageFormat::Png),
+        (b"\xff\xd8\xff", ImageFormat::Jpeg),
+        (b"BM", ImageFormat::Bmp),
+        (b"P5", ImageFormat::Pnm),
+        (b"P6", ImageFormat::Pnm),
+    ];
+
+    SIGNATURES
+        .iter()
+        .find(|(signature, _)| bytes.starts_with(signature))
+        .map(|&(_, format)| format)
+}
+
+/// Limits on the size of the images a decoder allocates memory for, which guard against
+/// decompression bombs: tiny files whose headers declare huge images.
+///
+/// Every decoder that allocates memory based on the dimensions declared in a header takes these
+/// and checks them right after the header is read, before anything is allocated for the image.
+/// Decoders which only allocate in proportion to the size of their input (e.g. BMP and PNM, which
+/// check that the input actually contains every pixel) don't need them.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Limits {
+    /// The maximum amount of pixels (`width * height`) an image can have. `None` means no limit.
+    pub max_pixels: Option<u64>,
+    /// The maximum size, in bytes, of the decoded image, i.e. of the buffer holding the pixels
+    /// that are returned. Temporary buffers used while decoding aren't counted. `None` means no
+    /// limit.
+    pub max_bytes: Option<u64>,
+}
+
+impl Limits {
+    /// No limits at all. Same as [`Limits::default`].
+    pub const NONE: Self = Self {
+        max_pixels: None,
+        max_bytes: None,
+    };
+
+    /// Checks that an image with the given dimensions whose decoded buffer takes `bytes` bytes is
+    /// within these limits.
+    pub fn check(&self, (width, height): (u32, u32), bytes: u64) -> Result<(), LimitsExceeded> {
+        let pixels = u64::from(width) * u64::from(height);
+        let exceeds = |limit: Option<u64>, value| limit.map_or(false, |limit| value > limit);
+
+        if exceeds(self.max_pixels, pixels) || exceeds(self.max_bytes, bytes) {
+            return Err(LimitsExceeded { width, height });
+        }
+
+        Ok(())
+    }
+}
+
+/// The error returned by [`Limits::check`] for images which exceed the limits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("image of {width}x{height} exceeds the decoding limits")]
+pub struct LimitsExceeded {
+    pub width: u32,
+    pub height: u32,
+}
+
 /// Trait for types capable of encoding images to a specific format.
 pub trait ImgEncoder<P> {
     /// Encodes an image and writes the output to a writer.
@@ -10,6 +89,18 @@ pub trait ImgEncoder<P> {
     where
         W: std::io::Write,
         I: Img<Pixel = P>;
+
+    /// Encodes an image into a new [`Vec`] of bytes.
+    #[inline]
+    fn encode_to_vec<I>(&mut self, img: I) -> std::io::Result<Vec<u8>>
+    where
+        I: Img<Pixel = P>,
+    {
+        let mut bytes = Vec::new();
+        self.encode(&mut bytes, img)?;
+
+        Ok(bytes)
+    }
 }
 
 /// Trait for types capable of decoding images with a specific format and pixel type.
@@ -21,6 +112,12 @@ pub trait ImgDecoder<P> {
     fn decode<R>(&mut self, reader: R) -> Result<Self::Output, Self::Error>
     where
         R: std::io::Read;
+
+    /// Decodes an image from a slice of bytes.
+    #[inline]
+    fn decode_bytes(&mut self, bytes: &[u8]) -> Result<Self::Output, Self::Error> {
+        self.decode(std::io::Cursor::new(bytes))
+    }
 }
 
 /// Trait for types capable of decoding images with a specific format supporting many common pixel
@@ -32,4 +129,68 @@ pub trait CommonImgDecoder {
     fn decode_common<R>(&mut self, reader: R) -> Result<CommonImgBuf, Self::Error>
     where
         R: std::io::Read;
+
+    /// Decodes an image from a slice of bytes.
+    ///
+    /// This isn't called `decode_bytes` so that it doesn't clash with [`ImgDecoder::decode_bytes`]
+    /// on decoders implementing both traits.
+    #[inline]
+    fn decode_common_bytes(&mut self, bytes: &[u8]) -> Result<CommonImgBuf, Self::Error> {
+        self.decode_common(std::io::Cursor::new(bytes))
+    }
+
+    /// Opens the file at the given path and decodes it, reading it through a
+    /// [`BufReader`][std::io::BufReader].
+    #[inline]
+    fn decode_from_path<P>(&mut self, path: P) -> Result<CommonImgBuf, Self::Error>
+    where
+        P: AsRef<std::path::Path>,
+        Self::Error: From<std::io::Error>,
+    {
+        let file = std::fs::File::open(path)?;
+        self.decode_common(std::io::BufReader::new(file))
+    }
+}
+
+/// An error that can be returned from [`decode_async`].
+#[cfg(feature = "tokio")]
+#[derive(Debug, thiserror::Error)]
+pub enum AsyncDecodeError<E> {
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("decoding error: {0}")]
+    Decoding(E),
+    /// The blocking decoding task was cancelled before finishing, e.g. because the runtime is
+    /// shutting down.
+    #[error("decoding task was cancelled")]
+    Cancelled,
+}
+
+/// Reads the whole stream from an async reader and then decodes it with the given decoder on
+/// tokio's blocking thread pool, so that decoding doesn't block the runtime.
+///
+/// Must be called from within a tokio runtime. If the decoder panics, the panic is propagated to
+/// the caller. If the decoding task is cancelled instead, [`AsyncDecodeError::Cancelled`] is
+/// returned.
+#[cfg(feature = "tokio")]
+pub async fn decode_async<D, P, R>(
+    mut decoder: D,
+    mut reader: R,
+) -> Result<D::Output, AsyncDecodeError<D::Error>>
+where
+    D: ImgDecoder<P> + Send + 'static,
+    D::Output: Send + 'static,
+    D::Error: Send,
+    R: tokio::io::AsyncRead + Unpin,
+{
+    use tokio::io::AsyncReadExt;
+
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes).await?;
+
+    match tokio::task::spawn_blocking(move || decoder.decode(bytes.as_slice())).await {
+        Ok(decoded) => decoded.map_err(AsyncDecodeError::Decoding),
+        Err(e) if e.is_panic() => std::panic::resume_unwind(e.into_panic()),
+        Err(_) => Err(AsyncDecodeError::Cancelled),
+    }
 }
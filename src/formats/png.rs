@@ -1,53 +1,476 @@
-use super::{CommonImgDecoder, ImgDecoder, ImgEncoder};
+use super::{CommonImgDecoder, ImgDecoder, ImgEncoder, Limits, LimitsExceeded};
 use crate::buffer::common::CommonImgBuf;
-use crate::pixel::common::*;
-use crate::prelude::ImgBuf;
+use crate::pixel::{common::*, Pixel};
+use crate::prelude::{Img, ImgBuf, ImgCore, ImgMut, Rect};
 use paste::paste;
-use std::io::Write;
+use std::{io::Write, time::Duration};
 use thiserror::Error;
 
 pub use png::{
-    AdaptiveFilterType, BitDepth, ColorType, Compression, DecodingError, EncodingError, FilterType,
-    SrgbRenderingIntent,
+    AdaptiveFilterType, BitDepth, BlendOp, ColorType, Compression, DecodingError, DisposeOp,
+    EncodingError, FilterType, PixelDimensions, SrgbRenderingIntent, Unit,
 };
 
 /// Errors that can happen during encoding/decoding operations.
 #[derive(Debug, Error)]
 pub enum Error {
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
     #[error("decoding error: {0}")]
     Decoding(#[from] DecodingError),
     #[error("encoding error: {0}")]
     Encoding(#[from] EncodingError),
-    #[error("PNG is indexed - unsupported")]
-    Indexed,
+    #[error("PNG is indexed but has no palette")]
+    MissingPalette,
     #[error("wrong color type: {0:?}")]
     WrongColorType(ColorType),
     #[error("wrong bit depth: {0:?}")]
     WrongBitDepth(BitDepth),
+    #[error("image of {width}x{height} exceeds the decoding limits")]
+    TooLarge { width: u32, height: u32 },
 }
 
-/// A PNG Decoder.
+impl From<LimitsExceeded> for Error {
+    #[inline]
+    fn from(LimitsExceeded { width, height }: LimitsExceeded) -> Self {
+        Self::TooLarge { width, height }
+    }
+}
+
+/// Ancillary metadata read from a PNG by [`Decoder::decode_with_info`].
+#[derive(Debug, Clone, Default)]
+pub struct PngInfo {
+    /// The physical pixel dimensions from the `pHYs` chunk.
+    pub pixel_dims: Option<PixelDimensions>,
+    /// The gamma from the `gAMA` chunk.
+    pub gamma: Option<f32>,
+    /// The ICC profile from the `iCCP` chunk.
+    pub icc_profile: Option<Vec<u8>>,
+    /// `(keyword, text)` pairs from the `tEXt`, `zTXt` and `iTXt` chunks, in that order.
+    pub text: Vec<(String, String)>,
+}
+
+impl PngInfo {
+    fn from_png(info: &png::Info) -> Result<Self, Error> {
+        let mut text: Vec<_> = info
+            .uncompressed_latin1_text
+            .iter()
+            .map(|chunk| (chunk.keyword.clone(), chunk.text.clone()))
+            .collect();
+
+        for chunk in &info.compressed_latin1_text {
+            text.push((chunk.keyword.clone(), chunk.get_text()?));
+        }
+
+        for chunk in &info.utf8_text {
+            text.push((chunk.keyword.clone(), chunk.get_text()?));
+        }
+
+        Ok(Self {
+            pixel_dims: info.pixel_dims,
+            gamma: info.gama_chunk.map(png::ScaledFloat::into_value),
+            icc_profile: info.icc_profile.as_deref().map(<[u8]>::to_vec),
+            text,
+        })
+    }
+}
+
+/// The basic properties of a PNG, read from it's header by [`Decoder::read_info`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PngHeader {
+    pub width: u32,
+    pub height: u32,
+    pub color_type: ColorType,
+    pub bit_depth: BitDepth,
+}
+
+/// Options that control how a PNG is decoded by [`Decoder`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DecodeOptions {
+    /// Whether grayscale images should be expanded to RGB while decoding, and grayscale images
+    /// with alpha to RGBA. The expansion happens in place, in the buffer the image is decoded into,
+    /// so it avoids both a second pass and an intermediate grayscale buffer.
+    ///
+    /// When set, grayscale PNGs can be decoded as [`RGB`] and [`RGBA`] pixels, and
+    /// [`decode_common`][CommonImgDecoder::decode_common] returns the RGB variants for them.
+    pub expand_gray_to_rgb: bool,
+    /// The limits on the size of the decoded image. Images which exceed them fail to decode with
+    /// [`Error::TooLarge`] right after their header is read, before anything is allocated for
+    /// them.
+    pub limits: Limits,
+}
+
+/// A PNG Decoder. [`Decoder::default`] creates one with the default options.
 #[derive(Debug, Default, Clone, Copy)]
-pub struct Decoder;
+pub struct Decoder {
+    pub options: DecodeOptions,
+}
 
-macro_rules! impl_decoder {
-    (inner $primitive_ty:ty, $pixel_ty:ident, $color_ty:ident, $factor:literal) => {
-        impl ImgDecoder<$pixel_ty<$primitive_ty>> for Decoder {
-            type Output = ImgBuf<$pixel_ty<$primitive_ty>>;
-            type Error = Error;
+impl Decoder {
+    /// Creates a new [`Decoder`] with the given options.
+    #[inline]
+    pub fn with_options(options: DecodeOptions) -> Self {
+        Self { options }
+    }
+
+    /// Reads only the header of a PNG, without decoding it's image data. This is a cheap way to
+    /// find out the dimensions and color type of an image before deciding whether to decode it.
+    ///
+    /// The limits of the options aren't checked.
+    pub fn read_info<R>(&self, reader: R) -> Result<PngHeader, Error>
+    where
+        R: std::io::Read,
+    {
+        let reader = png::Decoder::new(reader).read_info()?;
+        let info = reader.info();
+
+        Ok(PngHeader {
+            width: info.width,
+            height: info.height,
+            color_type: info.color_type,
+            bit_depth: info.bit_depth,
+        })
+    }
+
+    /// Reads the header of a PNG and checks it against the limits of the options.
+    fn reader<R>(&self, reader: R) -> Result<png::Reader<R>, Error>
+    where
+        R: std::io::Read,
+    {
+        let reader = png::Decoder::new(reader).read_info()?;
+
+        // grayscale images expanded to RGB(A) take more space than the frame, and indexed images
+        // are expanded into RGB(A) pixels
+        let info = reader.info();
+        let output_size = reader.output_buffer_size() as u64;
+        let bytes = match info.color_type {
+            ColorType::Grayscale if self.options.expand_gray_to_rgb => output_size * 3,
+            ColorType::GrayscaleAlpha if self.options.expand_gray_to_rgb => output_size * 2,
+            ColorType::Indexed => {
+                let channels = if info.trns.is_some() { 4 } else { 3 };
+                u64::from(info.width) * u64::from(info.height) * channels
+            }
+            _ => output_size,
+        };
+
+        self.options.limits.check(info.size(), bytes)?;
+
+        Ok(reader)
+    }
+
+    /// Decodes an image just like [`ImgDecoder::decode`], but also returns the ancillary metadata
+    /// of the PNG (see [`PngInfo`]).
+    pub fn decode_with_info<P, R>(&mut self, reader: R) -> Result<(ImgBuf<P>, PngInfo), Error>
+    where
+        P: PngPixel,
+        R: std::io::Read,
+    {
+        let mut reader = self.reader(reader)?;
+        let img = P::read_frame(&mut reader, self.options)?;
+
+        // chunks after the image data (e.g. text) are only read when finishing
+        reader.finish()?;
+        let info = PngInfo::from_png(reader.info())?;
+
+        Ok((img, info))
+    }
+
+    /// Decodes the frames of an animated PNG (APNG). Every frame is composited over the previous
+    /// ones, as specified by it's blend and dispose operations, and converted to [`RGBA8`].
+    ///
+    /// A static PNG is treated as an animation with a single frame. If the PNG has a default image
+    /// which is not part of the animation, it is skipped.
+    pub fn decode_frames<R>(&mut self, reader: R) -> Result<Frames<R>, Error>
+    where
+        R: std::io::Read,
+    {
+        let mut decoder = png::Decoder::new(reader);
+        decoder.set_transformations(png::Transformations::normalize_to_color8());
+
+        let mut reader = decoder.read_info()?;
+        let info = reader.info();
+        let (width, height) = (info.width, info.height);
+
+        // frames are composited into an RGBA8 canvas
+        let canvas_size = u64::from(width) * u64::from(height) * 4;
+        self.options.limits.check((width, height), canvas_size)?;
+
+        let mut buffer = vec![0; reader.output_buffer_size()];
+        let info = reader.info();
+        let (remaining, has_default_image) = match (info.animation_control, info.frame_control) {
+            (Some(animation), control) => (animation.num_frames, control.is_none()),
+            (None, _) => (1, false),
+        };
+
+        if has_default_image {
+            reader.next_frame(&mut buffer)?;
+        }
+
+        Ok(Frames {
+            reader,
+            buffer,
+            canvas: ImgBuf::new(width, height),
+            remaining,
+            first: true,
+        })
+    }
+}
+
+/// A frame of an animated PNG. See [`Decoder::decode_frames`].
+#[derive(Debug, Clone)]
+pub struct AnimationFrame {
+    /// The composited frame, with the dimensions of the whole animation.
+    pub img: ImgBuf<RGBA8>,
+    /// The region of the animation updated by this frame.
+    pub region: Rect,
+    /// How long this frame should be displayed.
+    pub delay: Duration,
+    /// How this frame was blended over the previous one.
+    pub blend_op: BlendOp,
+    /// How the region of this frame is disposed of before the next frame is rendered.
+    pub dispose_op: DisposeOp,
+}
+
+/// Iterator over the frames of an animated PNG. See [`Decoder::decode_frames`].
+pub struct Frames<R>
+where
+    R: std::io::Read,
+{
+    reader: png::Reader<R>,
+    buffer: Vec<u8>,
+    canvas: ImgBuf<RGBA8>,
+    remaining: u32,
+    first: bool,
+}
+
+impl<R> Frames<R>
+where
+    R: std::io::Read,
+{
+    fn read_frame(&mut self) -> Result<AnimationFrame, Error> {
+        let output = self.reader.next_frame(&mut self.buffer)?;
+        let control = self.reader.info().frame_control;
+
+        let region = control.map_or(self.canvas.bounds(), |control| {
+            Rect::new(
+                (control.x_offset, control.y_offset),
+                (control.width, control.height),
+            )
+        });
+        let delay = control.map_or(Duration::ZERO, |control| {
+            // a denominator of zero means hundredths of a second
+            let den = if control.delay_den == 0 {
+                100
+            } else {
+                control.delay_den
+            };
+
+            Duration::from_secs_f64(f64::from(control.delay_num) / f64::from(den))
+        });
+        let blend_op = control.map_or(BlendOp::Source, |control| control.blend_op);
+        let dispose_op = match control.map_or(DisposeOp::None, |control| control.dispose_op) {
+            // the first frame can't be reverted to a previous one
+            DisposeOp::Previous if self.first => DisposeOp::Background,
+            op => op,
+        };
+        self.first = false;
+
+        let previous = (dispose_op == DisposeOp::Previous).then(|| {
+            self.canvas
+                .view(region)
+                .expect("png validates frame regions")
+                .to_buffer()
+        });
 
-            fn decode<R>(&mut self, reader: R) -> Result<Self::Output, Self::Error>
+        let samples = output.color_type.samples();
+        let data = &self.buffer[..output.line_size * output.height as usize];
+        let mut target = self
+            .canvas
+            .view_mut(region)
+            .expect("png validates frame regions");
+
+        for (dst, src) in target.pixels_mut().zip(data.chunks_exact(samples)) {
+            let src = match *src {
+                [v] => RGBA8::new(v, v, v, u8::MAX),
+                [v, a] => RGBA8::new(v, v, v, a),
+                [r, g, b] => RGBA8::new(r, g, b, u8::MAX),
+                [r, g, b, a] => RGBA8::new(r, g, b, a),
+                _ => unreachable!("output is normalized to 8-bit gray, gray alpha, rgb or rgba"),
+            };
+
+            *dst = match blend_op {
+                BlendOp::Source => src,
+                BlendOp::Over => src.over(*dst),
+            };
+        }
+
+        let img = self.canvas.clone();
+        let mut target = self.canvas.view_mut(region).expect("region was checked");
+        match (dispose_op, previous) {
+            (DisposeOp::Background, _) => target.pixels_mut().for_each(|p| *p = RGBA8::default()),
+            (DisposeOp::Previous, Some(previous)) => target.copy_from(&previous),
+            _ => (),
+        }
+
+        Ok(AnimationFrame {
+            img,
+            region,
+            delay,
+            blend_op,
+            dispose_op,
+        })
+    }
+}
+
+impl<R> Iterator for Frames<R>
+where
+    R: std::io::Read,
+{
+    type Item = Result<AnimationFrame, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let frame = self.read_frame();
+        self.remaining = if frame.is_ok() { self.remaining - 1 } else { 0 };
+
+        Some(frame)
+    }
+}
+
+mod sealed {
+    pub trait Sealed: Sized {
+        fn read_frame<R>(
+            reader: &mut png::Reader<R>,
+            options: super::DecodeOptions,
+        ) -> Result<crate::buffer::ImgBuf<Self>, super::Error>
+        where
+            R: std::io::Read;
+    }
+}
+
+/// Pixel types that can be decoded from a PNG. This trait is sealed.
+pub trait PngPixel: Pixel + sealed::Sealed {}
+
+impl<P> ImgDecoder<P> for Decoder
+where
+    P: PngPixel,
+{
+    type Output = ImgBuf<P>;
+    type Error = Error;
+
+    fn decode<R>(&mut self, reader: R) -> Result<Self::Output, Self::Error>
+    where
+        R: std::io::Read,
+    {
+        let mut reader = self.reader(reader)?;
+        P::read_frame(&mut reader, self.options)
+    }
+}
+
+/// Reads the next frame of a grayscale PNG (with alpha, if `P` has an alpha channel) into a buffer
+/// of RGB or RGBA pixels, expanding the gray channel in place.
+fn read_gray_expanded<P, R>(reader: &mut png::Reader<R>) -> Result<ImgBuf<P>, Error>
+where
+    P: Pixel + bytemuck::Pod + Default,
+    R: std::io::Read,
+{
+    let (width, height) = reader.info().size();
+    let pixel_count = width as usize * height as usize;
+
+    let mut container = vec![P::default(); pixel_count];
+    let bytes: &mut [u8] = bytemuck::cast_slice_mut(&mut container);
+    reader.next_frame(&mut bytes[..reader.output_buffer_size()])?;
+
+    // the gray samples are packed at the start of the buffer, so expand them from the end to
+    // avoid overwriting samples that haven't been expanded yet.
+    let channels = if P::ALPHA_CHANNEL.is_some() { 4 } else { 3 };
+    let out_size = std::mem::size_of::<P>();
+    let sample_size = out_size / channels;
+    let in_size = sample_size * (channels - 2);
+    for index in (0..pixel_count).rev() {
+        let mut pixel = [0u8; 8];
+        let (gray, alpha) = bytes[index * in_size..][..in_size].split_at(sample_size);
+        for channel in pixel.chunks_exact_mut(sample_size).take(3) {
+            channel.copy_from_slice(gray);
+        }
+        pixel[3 * sample_size..][..alpha.len()].copy_from_slice(alpha);
+
+        bytes[index * out_size..][..out_size].copy_from_slice(&pixel[..out_size]);
+    }
+
+    Ok(ImgBuf::from_container(container, width, height))
+}
+
+/// Reads the next frame of an indexed PNG, expanding it's palette into [`RGB8`] pixels, or into
+/// [`RGBA8`] pixels if the PNG has a `tRNS` chunk.
+fn read_indexed<R>(reader: &mut png::Reader<R>) -> Result<CommonImgBuf, Error>
+where
+    R: std::io::Read,
+{
+    let info = reader.info();
+    let Some(palette) = info.palette.as_deref() else {
+        return Err(Error::MissingPalette);
+    };
+
+    // entries missing from the palette are black, and entries missing from tRNS are opaque
+    let palette: Vec<_> = palette
+        .chunks_exact(3)
+        .map(|rgb| RGB8::new(rgb[0], rgb[1], rgb[2]))
+        .collect();
+    let trns = info.trns.as_deref().map(<[u8]>::to_vec);
+
+    let mut buffer = vec![0; reader.output_buffer_size()];
+    let output = reader.next_frame(&mut buffer)?;
+
+    let bits = output.bit_depth as usize;
+    let index = |(x, y): (u32, u32)| {
+        let bit = x as usize * bits;
+        let byte = buffer[y as usize * output.line_size + bit / 8];
+        let shift = 8 - bits - bit % 8;
+
+        usize::from((byte >> shift) & (u8::MAX >> (8 - bits)))
+    };
+    let color = |index| palette.get(index).copied().unwrap_or_default();
+
+    Ok(match trns {
+        Some(trns) => CommonImgBuf::Rgba8(ImgBuf::from_fn(output.width, output.height, |coords| {
+            let index = index(coords);
+            let RGB8 { r, g, b } = color(index);
+            RGBA8::new(r, g, b, trns.get(index).copied().unwrap_or(u8::MAX))
+        })),
+        None => CommonImgBuf::Rgb8(ImgBuf::from_fn(output.width, output.height, |coords| {
+            color(index(coords))
+        })),
+    })
+}
+
+macro_rules! impl_decoder {
+    (inner $primitive_ty:ty, $pixel_ty:ident, $color_ty:ident, $factor:literal $(, $gray_ty:ident)?) => {
+        impl sealed::Sealed for $pixel_ty<$primitive_ty> {
+            fn read_frame<R>(
+                reader: &mut png::Reader<R>,
+                #[allow(unused_variables)] options: DecodeOptions,
+            ) -> Result<ImgBuf<Self>, Error>
             where
                 R: std::io::Read,
             {
-                let decoder = png::Decoder::new(reader);
-                let mut reader = decoder.read_info()?;
-
                 let (width, height, color_type, bit_depth) = {
                     let info = reader.info();
                     (info.width, info.height, info.color_type, info.bit_depth)
                 };
 
+                $(
+                    if options.expand_gray_to_rgb
+                        && color_type == ColorType::$gray_ty
+                        && bit_depth as u32 == <$primitive_ty>::BITS
+                    {
+                        return read_gray_expanded(reader);
+                    }
+                )?
+
                 if color_type != ColorType::$color_ty {
                     return Err(Error::WrongColorType(color_type));
                 }
@@ -63,17 +486,19 @@ macro_rules! impl_decoder {
                 Ok(ImgBuf::from_container(container, width, height))
             }
         }
+
+        impl PngPixel for $pixel_ty<$primitive_ty> {}
     };
-    ($pixel_ty:ident, $color_ty:ident, $factor:literal) => {
-        impl_decoder!(inner u8, $pixel_ty, $color_ty, $factor);
-        impl_decoder!(inner u16, $pixel_ty, $color_ty, $factor);
+    ($pixel_ty:ident, $color_ty:ident, $factor:literal $(; expand $gray_ty:ident)?) => {
+        impl_decoder!(inner u8, $pixel_ty, $color_ty, $factor $(, $gray_ty)?);
+        impl_decoder!(inner u16, $pixel_ty, $color_ty, $factor $(, $gray_ty)?);
     };
 }
 
 impl_decoder!(Gray, Grayscale, 1);
 impl_decoder!(GrayAlpha, GrayscaleAlpha, 2);
-impl_decoder!(RGB, Rgb, 3);
-impl_decoder!(RGBA, Rgba, 4);
+impl_decoder!(RGB, Rgb, 3; expand Grayscale);
+impl_decoder!(RGBA, Rgba, 4; expand GrayscaleAlpha);
 
 impl CommonImgDecoder for Decoder {
     type Error = Error;
@@ -82,8 +507,7 @@ impl CommonImgDecoder for Decoder {
     where
         R: std::io::Read,
     {
-        let decoder = png::Decoder::new(reader);
-        let mut reader = decoder.read_info()?;
+        let mut reader = self.reader(reader)?;
 
         let (width, height, color_type, bit_depth) = {
             let info = reader.info();
@@ -116,12 +540,30 @@ impl CommonImgDecoder for Decoder {
             };
         }
 
+        if self.options.expand_gray_to_rgb {
+            match (color_type, bit_depth) {
+                (ColorType::Grayscale, BitDepth::Eight) => {
+                    return Ok(CommonImgBuf::Rgb8(read_gray_expanded(&mut reader)?))
+                }
+                (ColorType::Grayscale, BitDepth::Sixteen) => {
+                    return Ok(CommonImgBuf::Rgb16(read_gray_expanded(&mut reader)?))
+                }
+                (ColorType::GrayscaleAlpha, BitDepth::Eight) => {
+                    return Ok(CommonImgBuf::Rgba8(read_gray_expanded(&mut reader)?))
+                }
+                (ColorType::GrayscaleAlpha, BitDepth::Sixteen) => {
+                    return Ok(CommonImgBuf::Rgba16(read_gray_expanded(&mut reader)?))
+                }
+                _ => (),
+            }
+        }
+
         match color_type {
             ColorType::Grayscale => branch!(Gray, 1),
             ColorType::GrayscaleAlpha => branch!(Graya, 2),
             ColorType::Rgb => branch!(Rgb, 3),
             ColorType::Rgba => branch!(Rgba, 4),
-            ColorType::Indexed => Err(Error::Indexed),
+            ColorType::Indexed => read_indexed(&mut reader),
         }
     }
 }
@@ -132,6 +574,158 @@ pub struct Encoder {
     pub rendering_intent: SrgbRenderingIntent,
     pub filter_type: FilterType,
     pub adaptive_filter_type: AdaptiveFilterType,
+    /// The physical pixel dimensions to write in the `pHYs` chunk, if any.
+    pub pixel_dims: Option<PixelDimensions>,
+    /// `(keyword, text)` pairs to write as Latin-1 `tEXt` chunks.
+    pub text: Vec<(String, String)>,
+    /// `(keyword, text)` pairs to write as UTF-8 `iTXt` chunks.
+    pub international_text: Vec<(String, String)>,
+    /// Whether the image should be interlaced with Adam7, which allows progressive loading.
+    pub interlaced: bool,
+    /// Whether RGB(A) images whose pixels all have equal color channels should be written as
+    /// grayscale(-alpha) instead, which takes less space. This requires an extra pass over the
+    /// image.
+    pub auto_grayscale: bool,
+}
+
+impl Encoder {
+    /// Sets [`pixel_dims`][Encoder::pixel_dims] to the given resolution in dots (pixels) per inch,
+    /// for both axes.
+    pub fn set_dpi(&mut self, dpi: f64) {
+        const METERS_PER_INCH: f64 = 0.0254;
+        let ppm = (dpi / METERS_PER_INCH).round() as u32;
+
+        self.pixel_dims = Some(PixelDimensions {
+            xppu: ppm,
+            yppu: ppm,
+            unit: Unit::Meter,
+        });
+    }
+
+    /// Sets [`compression`][Encoder::compression] from a zlib-style level, where `0` is the
+    /// fastest and `9` produces the smallest output. Levels greater than `9` are treated as `9`.
+    ///
+    /// The levels map to [`Compression`] as follows:
+    ///
+    /// | level  | compression              |
+    /// |--------|--------------------------|
+    /// | `0..=2` | [`Compression::Fast`]    |
+    /// | `3..=6` | [`Compression::Default`] |
+    /// | `7..=9` | [`Compression::Best`]    |
+    ///
+    /// Note that level `0` still compresses the image, as uncompressed output isn't supported.
+    pub fn compression_level(&mut self, level: u8) {
+        self.compression = compression_from_level(level);
+    }
+
+    /// Creates a [`png::Encoder`] with the settings of this encoder, except for
+    /// [`interlaced`][Encoder::interlaced].
+    fn png_encoder<W>(
+        &self,
+        writer: W,
+        dimensions: (u32, u32),
+        (color_type, bit_depth): (ColorType, BitDepth),
+        interlaced: bool,
+    ) -> Result<png::Encoder<'static, W>, EncodingError>
+    where
+        W: Write,
+    {
+        let mut info = png::Info::with_size(dimensions.0, dimensions.1);
+        info.interlaced = interlaced;
+        let mut encoder = png::Encoder::with_info(writer, info)?;
+
+        encoder.set_color(color_type);
+        encoder.set_depth(bit_depth);
+        encoder.set_compression(self.compression);
+        encoder.set_srgb(self.rendering_intent);
+        encoder.set_filter(self.filter_type);
+        encoder.set_adaptive_filter(self.adaptive_filter_type);
+        encoder.set_pixel_dims(self.pixel_dims);
+
+        for (keyword, text) in &self.text {
+            encoder.add_text_chunk(keyword.clone(), text.clone())?;
+        }
+
+        for (keyword, text) in &self.international_text {
+            encoder.add_itxt_chunk(keyword.clone(), text.clone())?;
+        }
+
+        Ok(encoder)
+    }
+
+    /// Encodes an animated PNG (APNG) with the given frames and writes it to a writer. Each frame
+    /// is displayed for it's [`Duration`], which is rounded to milliseconds (or to hundredths of a
+    /// second for delays longer than `u16::MAX` milliseconds).
+    ///
+    /// Every frame is written in full and replaces the previous one. `loops` is the amount of
+    /// times the animation is played, where `0` means forever. Interlacing is not supported for
+    /// animations, so [`interlaced`][Encoder::interlaced] is ignored.
+    ///
+    /// Returns an error with [`InvalidInput`][std::io::ErrorKind::InvalidInput] if there are no
+    /// frames or if the frames don't all have the same dimensions.
+    pub fn encode_animation<W, I>(
+        &mut self,
+        writer: W,
+        frames: &[(I, Duration)],
+        loops: u32,
+    ) -> std::io::Result<()>
+    where
+        W: Write,
+        I: Img<Pixel = RGBA8>,
+    {
+        let invalid = |message| std::io::Error::new(std::io::ErrorKind::InvalidInput, message);
+        let Some((first, _)) = frames.first() else {
+            return Err(invalid("an animation must have at least one frame"));
+        };
+
+        let dimensions = first.dimensions();
+        if frames.iter().any(|(img, _)| img.dimensions() != dimensions) {
+            return Err(invalid("all frames must have the same dimensions"));
+        }
+
+        let frame_count = u32::try_from(frames.len())
+            .map_err(|_| invalid("an animation can have at most u32::MAX frames"))?;
+
+        let mut encoder = self.png_encoder(
+            writer,
+            dimensions,
+            (ColorType::Rgba, BitDepth::Eight),
+            false,
+        )?;
+        encoder.set_animated(frame_count, loops)?;
+
+        let mut writer = encoder.write_header()?;
+        let mut data = Vec::new();
+        for (img, delay) in frames {
+            let (num, den) = match u16::try_from(delay.as_millis()) {
+                Ok(millis) => (millis, 1000),
+                Err(_) => (
+                    u16::try_from(delay.as_millis() / 10).unwrap_or(u16::MAX),
+                    100,
+                ),
+            };
+            writer.set_frame_delay(num, den)?;
+
+            data.clear();
+            for chunk in img.pixel_chunks() {
+                data.extend_from_slice(bytemuck::must_cast_slice(chunk));
+            }
+            writer.write_image_data(&data)?;
+        }
+
+        writer.finish()?;
+        Ok(())
+    }
+}
+
+/// Maps a zlib-style compression level to the closest [`Compression`]. See
+/// [`Encoder::compression_level`].
+fn compression_from_level(level: u8) -> Compression {
+    match level {
+        0..=2 => Compression::Fast,
+        3..=6 => Compression::Default,
+        _ => Compression::Best,
+    }
 }
 
 impl Default for Encoder {
@@ -141,10 +735,145 @@ impl Default for Encoder {
             rendering_intent: SrgbRenderingIntent::Perceptual,
             filter_type: FilterType::default(),
             adaptive_filter_type: AdaptiveFilterType::default(),
+            pixel_dims: None,
+            text: Vec::new(),
+            international_text: Vec::new(),
+            interlaced: false,
+            auto_grayscale: false,
         }
     }
 }
 
+/// Filters a row of bytes with the given filter type, as specified by the PNG standard, and
+/// appends the filter type followed by the filtered row to `out`.
+fn filter_row(filter: FilterType, bpp: usize, previous: &[u8], row: &[u8], out: &mut Vec<u8>) {
+    out.push(filter as u8);
+    out.extend(row.iter().enumerate().map(|(index, &value)| {
+        let left = index.checked_sub(bpp).map_or(0, |i| row[i]);
+        let up = previous[index];
+        let up_left = index.checked_sub(bpp).map_or(0, |i| previous[i]);
+
+        let prediction = match filter {
+            FilterType::NoFilter => 0,
+            FilterType::Sub => left,
+            FilterType::Up => up,
+            FilterType::Avg => ((u16::from(left) + u16::from(up)) / 2) as u8,
+            FilterType::Paeth => {
+                let estimate = i16::from(left) + i16::from(up) - i16::from(up_left);
+                let (dist_left, dist_up, dist_up_left) = (
+                    (estimate - i16::from(left)).abs(),
+                    (estimate - i16::from(up)).abs(),
+                    (estimate - i16::from(up_left)).abs(),
+                );
+
+                if dist_left <= dist_up && dist_left <= dist_up_left {
+                    left
+                } else if dist_up <= dist_up_left {
+                    up
+                } else {
+                    up_left
+                }
+            }
+        };
+
+        value.wrapping_sub(prediction)
+    }));
+}
+
+/// Writes the image data of a view as Adam7 interlaced `IDAT` chunks.
+///
+/// The `png` crate can decode interlaced images but not encode them, so the passes are filtered
+/// and compressed here.
+fn write_interlaced<W, I>(
+    writer: &mut png::Writer<W>,
+    img: &I,
+    options: &Encoder,
+) -> Result<(), EncodingError>
+where
+    W: Write,
+    I: Img,
+    I::Pixel: bytemuck::Pod,
+{
+    // (x start, y start, x step, y step) of each pass
+    const PASSES: [(u32, u32, u32, u32); 7] = [
+        (0, 0, 8, 8),
+        (4, 0, 8, 8),
+        (0, 4, 4, 8),
+        (2, 0, 4, 4),
+        (0, 2, 2, 4),
+        (1, 0, 2, 2),
+        (0, 1, 1, 2),
+    ];
+    const FILTERS: [FilterType; 5] = [
+        FilterType::NoFilter,
+        FilterType::Sub,
+        FilterType::Up,
+        FilterType::Avg,
+        FilterType::Paeth,
+    ];
+
+    let bpp = std::mem::size_of::<I::Pixel>();
+    let mut data = Vec::new();
+    let (mut previous, mut row, mut candidate) = (Vec::new(), Vec::new(), Vec::new());
+    for (x_start, y_start, x_step, y_step) in PASSES {
+        if x_start >= img.width() || y_start >= img.height() {
+            continue;
+        }
+
+        let pass_width = (img.width() - x_start + x_step - 1) / x_step;
+        previous.clear();
+        previous.resize(pass_width as usize * bpp, 0);
+
+        for y in (y_start..img.height()).step_by(y_step as usize) {
+            row.clear();
+            for x in (x_start..img.width()).step_by(x_step as usize) {
+                // SAFETY: (x, y) is always within the bounds of the view.
+                row.extend_from_slice(bytemuck::bytes_of(unsafe { img.pixel_unchecked((x, y)) }));
+            }
+
+            match options.adaptive_filter_type {
+                AdaptiveFilterType::NonAdaptive => {
+                    filter_row(options.filter_type, bpp, &previous, &row, &mut data);
+                }
+                AdaptiveFilterType::Adaptive => {
+                    // pick the filter with the smallest sum of absolute values, like libpng does
+                    let mut best: Option<(u64, Vec<u8>)> = None;
+                    for filter in FILTERS {
+                        candidate.clear();
+                        filter_row(filter, bpp, &previous, &row, &mut candidate);
+                        let score = candidate[1..]
+                            .iter()
+                            .map(|&b| u64::from((b as i8).unsigned_abs()))
+                            .sum();
+
+                        if best
+                            .as_ref()
+                            .map_or(true, |(best_score, _)| score < *best_score)
+                        {
+                            best = Some((score, candidate.clone()));
+                        }
+                    }
+
+                    data.extend(best.expect("there is at least one filter").1);
+                }
+            }
+
+            std::mem::swap(&mut previous, &mut row);
+        }
+    }
+
+    let level = match options.compression {
+        Compression::Fast => 1,
+        Compression::Best => 9,
+        _ => 6,
+    };
+
+    writer.write_chunk(
+        png::chunk::IDAT,
+        &miniz_oxide::deflate::compress_to_vec_zlib(&data, level),
+    )
+}
+
 macro_rules! impl_encoder {
     (inner depth 8) => {
         BitDepth::Eight
@@ -152,7 +881,7 @@ macro_rules! impl_encoder {
     (inner depth 16) => {
         BitDepth::Sixteen
     };
-    (inner $depth:literal, $pixel_ty:ident, $color_ty:ident) => {
+    (inner $depth:literal, $pixel_ty:ident, $color_ty:ident $(; gray $gray_ty:ident, $to_gray:expr)?) => {
         paste! {
             impl ImgEncoder<[<$pixel_ty:upper $depth>]> for Encoder {
                 fn encode<W, I>(&mut self, writer: W, img: I) -> std::io::Result<()>
@@ -160,16 +889,31 @@ macro_rules! impl_encoder {
                     W: std::io::Write,
                     I: crate::view::Img<Pixel = [<$pixel_ty:upper $depth>]>,
                 {
-                    let mut encoder = png::Encoder::new(writer, img.width(), img.height());
+                    $(
+                        if self.auto_grayscale && img.pixels().all(|p| p.r == p.g && p.g == p.b) {
+                            let gray = ImgBuf::from_fn(img.width(), img.height(), |coords| {
+                                // SAFETY: the buffer and the view have the same dimensions.
+                                let pixel = unsafe { img.pixel_unchecked(coords) };
+                                <[<$gray_ty:upper $depth>] as Pixel>::new($to_gray(pixel))
+                            });
 
-                    encoder.set_color(ColorType::$color_ty);
-                    encoder.set_depth(impl_encoder!(inner depth $depth));
-                    encoder.set_compression(self.compression);
-                    encoder.set_srgb(self.rendering_intent);
-                    encoder.set_filter(self.filter_type);
-                    encoder.set_adaptive_filter(self.adaptive_filter_type);
+                            return self.encode(writer, gray);
+                        }
+                    )?
+
+                    let encoder = self.png_encoder(
+                        writer,
+                        img.dimensions(),
+                        (ColorType::$color_ty, impl_encoder!(inner depth $depth)),
+                        self.interlaced,
+                    )?;
 
                     let mut writer = encoder.write_header()?;
+                    if self.interlaced {
+                        write_interlaced(&mut writer, &img, self)?;
+                        writer.finish()?;
+                        return Ok(());
+                    }
 
                     // WARN: not sure what exactly can fail here
                     let mut stream_writer = writer
@@ -188,13 +932,412 @@ macro_rules! impl_encoder {
             }
         }
     };
-    ($pixel_ty:ident, $color_ty:ident) => {
-        impl_encoder!(inner 8, $pixel_ty, $color_ty);
-        impl_encoder!(inner 16, $pixel_ty, $color_ty);
+    ($pixel_ty:ident, $color_ty:ident $(; gray $gray_ty:ident, $to_gray:expr)?) => {
+        impl_encoder!(inner 8, $pixel_ty, $color_ty $(; gray $gray_ty, $to_gray)?);
+        impl_encoder!(inner 16, $pixel_ty, $color_ty $(; gray $gray_ty, $to_gray)?);
     };
 }
 
 impl_encoder!(Gray, Grayscale);
 impl_encoder!(Graya, GrayscaleAlpha);
-impl_encoder!(Rgb, Rgb);
-impl_encoder!(Rgba, Rgba);
+impl_encoder!(Rgb, Rgb; gray Gray, |p: &RGB<_>| [p.r]);
+impl_encoder!(Rgba, Rgba; gray Graya, |p: &RGBA<_>| [p.r, p.a]);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::view::ImgMutCore;
+
+    #[test]
+    fn metadata_round_trip() {
+        let img = ImgBuf::from_fn(3, 2, |(x, y)| RGB8::new(x as u8, y as u8, 7));
+
+        let mut encoder = Encoder::default();
+        encoder.set_dpi(300.0);
+        encoder.text.push(("Software".into(), "picture".into()));
+        encoder
+            .international_text
+            .push(("Title".into(), "imagem ünicode".into()));
+
+        let mut bytes = Vec::new();
+        encoder.encode(&mut bytes, img.clone()).unwrap();
+
+        let (decoded, info) = Decoder::default()
+            .decode_with_info::<RGB8, _>(bytes.as_slice())
+            .unwrap();
+        assert_eq!(decoded.as_pixel_slice(), img.as_pixel_slice());
+
+        let dims = info.pixel_dims.unwrap();
+        assert_eq!((dims.xppu, dims.yppu), (11811, 11811));
+        assert_eq!(
+            info.text,
+            [
+                ("Software".to_owned(), "picture".to_owned()),
+                ("Title".to_owned(), "imagem ünicode".to_owned())
+            ]
+        );
+    }
+
+    #[test]
+    fn expand_gray_to_rgb() {
+        let img = ImgBuf::from_fn(3, 2, |(x, y)| GrayAlpha::new(x as u8 * 10 + y as u8, 200));
+
+        let mut bytes = Vec::new();
+        Encoder::default().encode(&mut bytes, img.clone()).unwrap();
+
+        let mut decoder = Decoder::with_options(DecodeOptions {
+            expand_gray_to_rgb: true,
+            ..Default::default()
+        });
+        let expected: Vec<_> = img
+            .pixels()
+            .map(|p| {
+                let [v, a] = *p.channels();
+                RGBA8::new(v, v, v, a)
+            })
+            .collect();
+
+        let decoded: ImgBuf<RGBA8> = decoder.decode(bytes.as_slice()).unwrap();
+        assert_eq!(decoded.as_pixel_slice(), expected);
+
+        let CommonImgBuf::Rgba8(decoded) = decoder.decode_common(bytes.as_slice()).unwrap() else {
+            panic!("grayscale should be expanded to RGBA");
+        };
+        assert_eq!(decoded.as_pixel_slice(), expected);
+    }
+
+    #[test]
+    fn decode_from_path() {
+        let img = Decoder::default()
+            .decode_from_path("examples/images/star.png")
+            .unwrap();
+        assert!(matches!(img, CommonImgBuf::Rgba8(_)));
+
+        let result = Decoder::default().decode_from_path("examples/images/missing.png");
+        assert!(matches!(result, Err(Error::Io(_))));
+    }
+
+    #[test]
+    fn header_only() {
+        let img = ImgBuf::from_fn(7, 3, |(x, y)| RGBA16::new(x as u16, y as u16, 0, 0));
+        let mut bytes = Vec::new();
+        Encoder::default().encode(&mut bytes, img).unwrap();
+
+        // the image data isn't needed to read the header
+        let data_start = bytes.windows(4).position(|w| w == b"IDAT").unwrap();
+        bytes.truncate(data_start + 4);
+        let header = Decoder::default().read_info(bytes.as_slice()).unwrap();
+        assert_eq!(
+            header,
+            PngHeader {
+                width: 7,
+                height: 3,
+                color_type: ColorType::Rgba,
+                bit_depth: BitDepth::Sixteen,
+            }
+        );
+    }
+
+    #[test]
+    fn indexed_expansion() {
+        let encode = |trns: Option<&[u8]>| {
+            let mut bytes = Vec::new();
+            let mut encoder = png::Encoder::new(&mut bytes, 3, 2);
+            encoder.set_color(ColorType::Indexed);
+            encoder.set_depth(BitDepth::Two);
+            encoder.set_palette(&[255, 0, 0, 0, 255, 0, 0, 0, 255][..]);
+            if let Some(trns) = trns {
+                encoder.set_trns(trns);
+            }
+
+            // indices 0 1 2 / 2 1 0, packed 4 per byte
+            let mut writer = encoder.write_header().unwrap();
+            writer
+                .write_image_data(&[0b0001_1000, 0b1001_0000])
+                .unwrap();
+            writer.finish().unwrap();
+            bytes
+        };
+
+        let bytes = encode(None);
+        let CommonImgBuf::Rgb8(decoded) =
+            Decoder::default().decode_common(bytes.as_slice()).unwrap()
+        else {
+            panic!("indexed PNG without tRNS should be expanded to RGB");
+        };
+        let [red, green, blue] = [
+            RGB8::new(255, 0, 0),
+            RGB8::new(0, 255, 0),
+            RGB8::new(0, 0, 255),
+        ];
+        assert_eq!(
+            decoded.as_pixel_slice(),
+            [red, green, blue, blue, green, red]
+        );
+
+        let bytes = encode(Some(&[0, 128]));
+        let CommonImgBuf::Rgba8(decoded) =
+            Decoder::default().decode_common(bytes.as_slice()).unwrap()
+        else {
+            panic!("indexed PNG with tRNS should be expanded to RGBA");
+        };
+        assert_eq!(
+            decoded.as_pixel_slice()[..3],
+            [
+                RGBA8::new(255, 0, 0, 0),
+                RGBA8::new(0, 255, 0, 128),
+                RGBA8::new(0, 0, 255, 255)
+            ]
+        );
+    }
+
+    #[cfg(feature = "tokio")]
+    #[test]
+    fn decode_async_round_trip() {
+        let img = ImgBuf::from_fn(5, 4, |(x, y)| RGBA8::new(x as u8, y as u8, 7, 255));
+        let mut bytes = Vec::new();
+        Encoder::default().encode(&mut bytes, img.clone()).unwrap();
+
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        let decoded: ImgBuf<RGBA8> = runtime
+            .block_on(crate::formats::decode_async(
+                Decoder::default(),
+                bytes.as_slice(),
+            ))
+            .unwrap();
+        assert_eq!(decoded.as_pixel_slice(), img.as_pixel_slice());
+
+        let truncated = runtime.block_on(crate::formats::decode_async::<_, RGBA8, _>(
+            Decoder::default(),
+            &bytes[..20],
+        ));
+        assert!(matches!(
+            truncated,
+            Err(crate::formats::AsyncDecodeError::Decoding(_))
+        ));
+    }
+
+    #[test]
+    fn decoding_limits() {
+        // a header for a huge image, without any image data
+        let mut bytes = Vec::new();
+        let mut writer = png::Encoder::new(&mut bytes, 100_000, 100_000)
+            .write_header()
+            .unwrap();
+        writer.write_chunk(png::chunk::IDAT, &[]).unwrap();
+        drop(writer);
+
+        let mut decoder = Decoder::with_options(DecodeOptions {
+            limits: Limits {
+                max_pixels: Some(4096 * 4096),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        let result: Result<ImgBuf<GRAY8>, _> = decoder.decode(bytes.as_slice());
+        assert!(matches!(
+            result,
+            Err(Error::TooLarge {
+                width: 100_000,
+                height: 100_000
+            })
+        ));
+        assert!(matches!(
+            decoder.decode_common(bytes.as_slice()),
+            Err(Error::TooLarge { .. })
+        ));
+
+        let img = ImgBuf::from_fn(8, 8, |_| RGB8::new(1, 2, 3));
+        let mut bytes = Vec::new();
+        Encoder::default().encode(&mut bytes, img).unwrap();
+
+        let mut decoder = Decoder::with_options(DecodeOptions {
+            limits: Limits {
+                max_bytes: Some(8 * 8 * 3 - 1),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        let result: Result<ImgBuf<RGB8>, _> = decoder.decode(bytes.as_slice());
+        assert!(matches!(result, Err(Error::TooLarge { .. })));
+
+        decoder.options.limits.max_bytes = Some(8 * 8 * 3);
+        let result: Result<ImgBuf<RGB8>, _> = decoder.decode(bytes.as_slice());
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn animation_round_trip() {
+        let frames: Vec<_> = (0..3u8)
+            .map(|i| {
+                let img = ImgBuf::from_fn(4, 3, |(x, y)| RGBA8::new(x as u8, y as u8, i, 255));
+                (img, Duration::from_millis(40 * (u64::from(i) + 1)))
+            })
+            .collect();
+
+        let mut bytes = Vec::new();
+        Encoder::default()
+            .encode_animation(&mut bytes, &frames, 0)
+            .unwrap();
+
+        let decoded: Vec<_> = Decoder::default()
+            .decode_frames(bytes.as_slice())
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        assert_eq!(decoded.len(), frames.len());
+        for (decoded, (img, delay)) in decoded.iter().zip(&frames) {
+            assert_eq!(decoded.img.as_pixel_slice(), img.as_pixel_slice());
+            assert_eq!(decoded.delay, *delay);
+        }
+
+        let mismatched = [
+            (ImgBuf::from_fn(2, 2, |_| RGBA8::default()), Duration::ZERO),
+            (ImgBuf::from_fn(3, 2, |_| RGBA8::default()), Duration::ZERO),
+        ];
+        let error = Encoder::default()
+            .encode_animation(Vec::new(), &mismatched, 0)
+            .unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn compression_levels() {
+        let expected = [
+            Compression::Fast,
+            Compression::Fast,
+            Compression::Fast,
+            Compression::Default,
+            Compression::Default,
+            Compression::Default,
+            Compression::Default,
+            Compression::Best,
+            Compression::Best,
+            Compression::Best,
+        ];
+
+        let mut encoder = Encoder::default();
+        for (level, expected) in (0..).zip(expected) {
+            encoder.compression_level(level);
+            assert_eq!(
+                format!("{:?}", encoder.compression),
+                format!("{expected:?}"),
+                "level {level}"
+            );
+        }
+
+        encoder.compression_level(200);
+        assert!(matches!(encoder.compression, Compression::Best));
+    }
+
+    #[test]
+    fn auto_grayscale() {
+        let gray = ImgBuf::from_fn(6, 5, |(x, y)| {
+            let v = (x * 40 + y) as u8;
+            RGBA8::new(v, v, v, 200)
+        });
+        let mut color = gray.clone();
+        color.pixel_mut((5, 4)).unwrap().g += 1;
+
+        let mut encoder = Encoder {
+            auto_grayscale: true,
+            ..Default::default()
+        };
+
+        let color_type = |bytes: &[u8]| {
+            let reader = png::Decoder::new(bytes).read_info().unwrap();
+            reader.info().color_type
+        };
+
+        let mut bytes = Vec::new();
+        encoder.encode(&mut bytes, gray.clone()).unwrap();
+        assert_eq!(color_type(&bytes), ColorType::GrayscaleAlpha);
+
+        let decoded: ImgBuf<GRAYA8> = Decoder::default().decode(bytes.as_slice()).unwrap();
+        assert!(decoded
+            .pixels()
+            .zip(gray.pixels())
+            .all(|(a, b)| a.channels() == &[b.r, b.a]));
+
+        let mut bytes = Vec::new();
+        encoder.encode(&mut bytes, color).unwrap();
+        assert_eq!(color_type(&bytes), ColorType::Rgba);
+    }
+
+    #[test]
+    fn interlaced_round_trip() {
+        let img = ImgBuf::from_fn(13, 11, |(x, y)| {
+            RGBA16::new(x as u16 * 5000, y as u16 * 300, (x * y) as u16, 65535)
+        });
+
+        for adaptive_filter_type in [
+            AdaptiveFilterType::NonAdaptive,
+            AdaptiveFilterType::Adaptive,
+        ] {
+            let mut encoder = Encoder {
+                interlaced: true,
+                filter_type: FilterType::Paeth,
+                adaptive_filter_type,
+                ..Default::default()
+            };
+
+            let mut bytes = Vec::new();
+            encoder.encode(&mut bytes, img.clone()).unwrap();
+
+            let reader = png::Decoder::new(bytes.as_slice()).read_info().unwrap();
+            assert!(reader.info().interlaced);
+
+            let decoded: ImgBuf<RGBA16> = Decoder::default().decode(bytes.as_slice()).unwrap();
+            assert_eq!(decoded.as_pixel_slice(), img.as_pixel_slice());
+        }
+    }
+
+    #[test]
+    fn apng_compositing() {
+        let mut bytes = Vec::new();
+        let mut encoder = png::Encoder::new(&mut bytes, 2, 2);
+        encoder.set_color(ColorType::Rgba);
+        encoder.set_animated(3, 0).unwrap();
+        let mut writer = encoder.write_header().unwrap();
+
+        writer.set_frame_delay(1, 10).unwrap();
+        writer
+            .write_image_data(&[255, 0, 0, 255].repeat(4))
+            .unwrap();
+
+        writer.set_frame_dimension(1, 1).unwrap();
+        writer.set_frame_position(1, 1).unwrap();
+        writer.set_blend_op(BlendOp::Over).unwrap();
+        writer.set_dispose_op(DisposeOp::Previous).unwrap();
+        writer.write_image_data(&[0, 0, 255, 255]).unwrap();
+
+        writer.set_frame_position(0, 0).unwrap();
+        writer.set_blend_op(BlendOp::Source).unwrap();
+        writer.set_dispose_op(DisposeOp::None).unwrap();
+        writer.write_image_data(&[0, 255, 0, 255]).unwrap();
+        writer.finish().unwrap();
+
+        let frames: Vec<_> = Decoder::default()
+            .decode_frames(bytes.as_slice())
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        let red = RGBA8::new(255, 0, 0, 255);
+
+        assert_eq!(frames.len(), 3);
+        assert_eq!(frames[0].delay, Duration::from_millis(100));
+        assert_eq!(frames[0].img.as_pixel_slice(), [red; 4]);
+        assert_eq!(frames[1].region, Rect::new((1, 1), (1, 1)));
+        assert_eq!(
+            frames[1].img.as_pixel_slice(),
+            [red, red, red, RGBA8::new(0, 0, 255, 255)]
+        );
+        assert_eq!(
+            frames[2].img.as_pixel_slice(),
+            [RGBA8::new(0, 255, 0, 255), red, red, red]
+        );
+    }
+}
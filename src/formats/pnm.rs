@@ -0,0 +1,300 @@
+use super::{CommonImgDecoder, ImgDecoder, ImgEncoder};
+use crate::buffer::common::CommonImgBuf;
+use crate::pixel::{common::*, Pixel};
+use crate::prelude::{Img, ImgBuf};
+use std::io::Write;
+use thiserror::Error;
+
+/// Errors that can happen during decoding operations.
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("invalid or unsupported magic number - only P5 and P6 are supported")]
+    InvalidMagic,
+    #[error("invalid header")]
+    InvalidHeader,
+    #[error("unsupported maxval: {0} - must be within 1..=255")]
+    UnsupportedMaxval(u32),
+    #[error("PNM is truncated")]
+    Truncated,
+    #[error("wrong format: P{0}")]
+    WrongFormat(u8),
+}
+
+/// A decoded PNM whose samples haven't been converted into a buffer yet.
+struct RawPnm {
+    /// The number in the magic number, i.e. 5 for graymaps and 6 for pixmaps.
+    format: u8,
+    width: u32,
+    height: u32,
+    samples: Vec<u8>,
+}
+
+/// Parses the ASCII header of a PNM, keeping track of the current position.
+struct HeaderParser<'bytes> {
+    bytes: &'bytes [u8],
+    position: usize,
+}
+
+impl HeaderParser<'_> {
+    /// Skips whitespace and comments, which last until the end of the line.
+    fn skip_whitespace(&mut self) {
+        while let Some(&byte) = self.bytes.get(self.position) {
+            match byte {
+                b'#' => {
+                    while self.bytes.get(self.position).map_or(false, |&b| b != b'\n') {
+                        self.position += 1;
+                    }
+                }
+                byte if byte.is_ascii_whitespace() => self.position += 1,
+                _ => break,
+            }
+        }
+    }
+
+    /// Reads an unsigned decimal number preceded by whitespace.
+    fn number(&mut self) -> Result<u32, Error> {
+        self.skip_whitespace();
+
+        let start = self.position;
+        while self
+            .bytes
+            .get(self.position)
+            .map_or(false, u8::is_ascii_digit)
+        {
+            self.position += 1;
+        }
+
+        std::str::from_utf8(&self.bytes[start..self.position])
+            .ok()
+            .and_then(|digits| digits.parse().ok())
+            .ok_or(Error::InvalidHeader)
+    }
+}
+
+impl RawPnm {
+    fn read<R>(mut reader: R) -> Result<Self, Error>
+    where
+        R: std::io::Read,
+    {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+
+        let format = match bytes.get(..2) {
+            Some(b"P5") => 5,
+            Some(b"P6") => 6,
+            _ => return Err(Error::InvalidMagic),
+        };
+
+        let mut parser = HeaderParser {
+            bytes: &bytes,
+            position: 2,
+        };
+
+        let (width, height, maxval) = (parser.number()?, parser.number()?, parser.number()?);
+        if maxval == 0 || maxval > 255 {
+            return Err(Error::UnsupportedMaxval(maxval));
+        }
+
+        // exactly one whitespace character separates the header from the samples
+        if !bytes
+            .get(parser.position)
+            .map_or(false, u8::is_ascii_whitespace)
+        {
+            return Err(Error::Truncated);
+        }
+
+        let start = parser.position + 1;
+        let channels = if format == 5 { 1 } else { 3 };
+        let end = (width as usize)
+            .checked_mul(height as usize)
+            .and_then(|pixels| pixels.checked_mul(channels))
+            .and_then(|len| len.checked_add(start));
+
+        let Some(samples) = end.and_then(|end| bytes.get(start..end)) else {
+            return Err(Error::Truncated);
+        };
+
+        // samples are scaled from 0..=maxval to the full range of u8
+        let samples = if maxval == 255 {
+            samples.to_vec()
+        } else {
+            samples
+                .iter()
+                .map(|&sample| ((u32::from(sample).min(maxval) * 255 + maxval / 2) / maxval) as u8)
+                .collect()
+        };
+
+        Ok(Self {
+            format,
+            width,
+            height,
+            samples,
+        })
+    }
+
+    fn into_buf<P>(self) -> ImgBuf<P>
+    where
+        P: Pixel + bytemuck::Pod + Default,
+    {
+        let mut container = vec![P::default(); self.width as usize * self.height as usize];
+        bytemuck::cast_slice_mut(&mut container).copy_from_slice(&self.samples);
+
+        ImgBuf::from_container(container, self.width, self.height)
+    }
+}
+
+/// A binary PGM (P5) and PPM (P6) Decoder.
+///
+/// Graymaps are decoded as 8-bit [`Gray`] and pixmaps as [`RGB8`]. Only a maxval of up to 255
+/// is supported, and samples are scaled to the full range of [`u8`].
+///
+/// It doesn't take [`Limits`][crate::formats::Limits]: the dimensions in the header are checked
+/// against the amount of samples that were actually read before the image is allocated, so the
+/// decoded image is never larger than the input.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Decoder;
+
+macro_rules! impl_decoder {
+    ($pixel_ty:ty, $format:literal) => {
+        impl ImgDecoder<$pixel_ty> for Decoder {
+            type Output = ImgBuf<$pixel_ty>;
+            type Error = Error;
+
+            fn decode<R>(&mut self, reader: R) -> Result<Self::Output, Self::Error>
+            where
+                R: std::io::Read,
+            {
+                let raw = RawPnm::read(reader)?;
+                match raw.format {
+                    $format => Ok(raw.into_buf()),
+                    format => Err(Error::WrongFormat(format)),
+                }
+            }
+        }
+    };
+}
+
+impl_decoder!(Gray<u8>, 5);
+impl_decoder!(RGB<u8>, 6);
+
+impl CommonImgDecoder for Decoder {
+    type Error = Error;
+
+    fn decode_common<R>(&mut self, reader: R) -> Result<CommonImgBuf, Self::Error>
+    where
+        R: std::io::Read,
+    {
+        let raw = RawPnm::read(reader)?;
+        match raw.format {
+            5 => Ok(CommonImgBuf::Gray8(raw.into_buf())),
+            _ => Ok(CommonImgBuf::Rgb8(raw.into_buf())),
+        }
+    }
+}
+
+/// A binary PGM (P5) and PPM (P6) Encoder. Images are written with a maxval of 255.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Encoder;
+
+macro_rules! impl_encoder {
+    ($pixel_ty:ty, $format:literal) => {
+        impl ImgEncoder<$pixel_ty> for Encoder {
+            fn encode<W, I>(&mut self, writer: W, img: I) -> std::io::Result<()>
+            where
+                W: std::io::Write,
+                I: Img<Pixel = $pixel_ty>,
+            {
+                let mut writer = std::io::BufWriter::new(writer);
+                write!(
+                    writer,
+                    "P{}\n{} {}\n255\n",
+                    $format,
+                    img.width(),
+                    img.height()
+                )?;
+
+                for chunk in img.pixel_chunks() {
+                    writer.write_all(bytemuck::must_cast_slice(chunk))?;
+                }
+
+                writer.flush()
+            }
+        }
+    };
+}
+
+impl_encoder!(Gray<u8>, 5);
+impl_encoder!(RGB<u8>, 6);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::ImgCore;
+
+    #[test]
+    fn round_trip() {
+        let img = ImgBuf::from_fn(3, 2, |(x, y)| RGB8::new(x as u8, y as u8, 200));
+
+        let mut bytes = Vec::new();
+        Encoder.encode(&mut bytes, &img).unwrap();
+        assert!(bytes.starts_with(b"P6\n3 2\n255\n"));
+
+        let CommonImgBuf::Rgb8(decoded) = Decoder.decode_common(bytes.as_slice()).unwrap() else {
+            panic!("P6 should decode as RGB8");
+        };
+
+        assert_eq!(decoded.dimensions(), img.dimensions());
+        assert!(decoded.pixels().eq(img.pixels()));
+
+        let gray = ImgBuf::from_fn(2, 2, |(x, y)| <Gray<u8> as Pixel>::new([(x + y * 2) as u8]));
+        let mut bytes = Vec::new();
+        Encoder.encode(&mut bytes, &gray).unwrap();
+
+        let decoded = ImgDecoder::<Gray<u8>>::decode(&mut Decoder, bytes.as_slice()).unwrap();
+        assert!(decoded.pixels().eq(gray.pixels()));
+
+        let result = ImgDecoder::<RGB8>::decode(&mut Decoder, bytes.as_slice());
+        assert!(matches!(result, Err(Error::WrongFormat(5))));
+    }
+
+    #[test]
+    fn byte_slice_helpers() {
+        let img = ImgBuf::from_fn(3, 2, |(x, y)| RGB8::new(x as u8, y as u8, 200));
+
+        let bytes = Encoder.encode_to_vec(&img).unwrap();
+        let mut expected = Vec::new();
+        Encoder.encode(&mut expected, &img).unwrap();
+        assert_eq!(bytes, expected);
+
+        let CommonImgBuf::Rgb8(decoded) = Decoder.decode_common_bytes(&bytes).unwrap() else {
+            panic!("P6 should decode as RGB8");
+        };
+
+        assert!(decoded.pixels().eq(img.pixels()));
+
+        let decoded = ImgDecoder::<RGB8>::decode_bytes(&mut Decoder, &bytes).unwrap();
+        assert!(decoded.pixels().eq(img.pixels()));
+
+        let result = ImgDecoder::<Gray<u8>>::decode_bytes(&mut Decoder, &bytes);
+        assert!(matches!(result, Err(Error::WrongFormat(6))));
+    }
+
+    #[test]
+    fn header_parsing() {
+        let bytes = b"P5 # a comment\n2 # another one\n1\n15\n\x00\x0f";
+        let decoded = ImgDecoder::<Gray<u8>>::decode(&mut Decoder, &bytes[..]).unwrap();
+        assert_eq!(decoded.dimensions(), (2, 1));
+        assert!(decoded.pixels().map(|p| p.channels()[0]).eq([0, 255]));
+
+        let result = Decoder.decode_common(&b"P5\n1 1\n256\n\x00\x00"[..]);
+        assert!(matches!(result, Err(Error::UnsupportedMaxval(256))));
+        let result = Decoder.decode_common(&b"P5\n1 1\n0\n\x00"[..]);
+        assert!(matches!(result, Err(Error::UnsupportedMaxval(0))));
+        let result = Decoder.decode_common(&b"P6\n2 2\n255\n\x00"[..]);
+        assert!(matches!(result, Err(Error::Truncated)));
+        let result = Decoder.decode_common(&b"P3\n1 1\n255\n0 0 0"[..]);
+        assert!(matches!(result, Err(Error::InvalidMagic)));
+    }
+}
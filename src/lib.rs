@@ -97,12 +97,18 @@
 )]
 
 use buffer::common::CommonImgBuf;
-use formats::{png::Decoder, CommonImgDecoder};
-use std::path::Path;
+use formats::{CommonImgDecoder, ImageFormat};
+use std::{
+    io::{BufRead, BufReader},
+    iter::FusedIterator,
+    path::Path,
+};
 use thiserror::Error;
 
 /// [`ImgBuf`][buffer::ImgBuf] and everything related to it.
 pub mod buffer;
+/// [`Drawing`][drawing::Drawing] trait for drawing shapes on views.
+pub mod drawing;
 /// Modules related to common image formats.
 #[cfg(feature = "formats")]
 pub mod formats;
@@ -124,15 +130,35 @@ pub mod prelude {
         common::{Rgb16Img, Rgb8Img, Rgba16Img, Rgba8Img},
         ImgBuf,
     };
+    pub use drawing::Drawing;
     pub use pixel::{
-        common::{RGB, RGB16, RGB8, RGBA, RGBA16, RGBA8},
-        Pixel,
+        common::{Convert, ConvertWithAlpha, Luminance, RGB, RGB16, RGB8, RGBA, RGBA16, RGBA8},
+        AlphaColors, Colors, Pixel,
     };
-    pub use view::{Img, ImgMut};
+    pub use processing::ResizeFilter;
+    pub use view::{lazy::LazyImg, Img, ImgCore, ImgMut, ImgMutCore};
 }
 
 pub type Point = (u32, u32);
 
+/// [`Ord::min`] for [`u32`]s, usable in const contexts.
+const fn const_min(a: u32, b: u32) -> u32 {
+    if a < b {
+        a
+    } else {
+        b
+    }
+}
+
+/// [`Ord::max`] for [`u32`]s, usable in const contexts.
+const fn const_max(a: u32, b: u32) -> u32 {
+    if a > b {
+        a
+    } else {
+        b
+    }
+}
+
 /// Type that represents a bounding rect.
 ///
 /// This rect is top-left inclusive, bottom-right exclusive. This means that a
@@ -206,6 +232,46 @@ impl Rect {
         )
     }
 
+    /// Creates a new [`Rect`] centered at a [`Point`] that extends `half` points in each direction,
+    /// i.e. with top-left point `center - half` and dimensions `2 * half`. Returns [`None`] if the
+    /// top-left point would be negative or if the coordinates of the bottom-right point would not
+    /// fit into [`u32`]s.
+    ///
+    /// Note that `half` holds half extents, not dimensions, so the resulting [`Rect`] always has
+    /// even dimensions. Use [`Rect::centered`] to specify the full (possibly odd) dimensions
+    /// instead: `Rect::from_center(c, (w, h))` is the same as `Rect::centered(c, (2 * w, 2 * h))`.
+    #[inline]
+    pub const fn from_center(center: Point, half: (u32, u32)) -> Option<Self> {
+        let (Some(left), Some(top)) = (center.0.checked_sub(half.0), center.1.checked_sub(half.1))
+        else {
+            return None;
+        };
+
+        let (Some(width), Some(height)) = (half.0.checked_mul(2), half.1.checked_mul(2)) else {
+            return None;
+        };
+
+        Self::try_new((left, top), (width, height))
+    }
+
+    /// Creates a new [`Rect`] with the given dimensions centered at a [`Point`], such that
+    /// [`center`][Rect::center] returns that point. Returns [`None`] if the top-left point would be
+    /// negative or if the coordinates of the bottom-right point would not fit into [`u32`]s.
+    ///
+    /// Unlike [`Rect::from_center`], which takes half extents, `dimensions` are the full
+    /// dimensions of the resulting [`Rect`], so they may be odd.
+    #[inline]
+    pub const fn centered(center: Point, dimensions: (u32, u32)) -> Option<Self> {
+        let (Some(left), Some(top)) = (
+            center.0.checked_sub(dimensions.0 / 2),
+            center.1.checked_sub(dimensions.1 / 2),
+        ) else {
+            return None;
+        };
+
+        Self::try_new((left, top), dimensions)
+    }
+
     /// Creates a new empty [`Rect`] with a given top-left [`Point`].
     #[inline]
     pub const fn empty(top_left: Point) -> Self {
@@ -241,6 +307,16 @@ impl Rect {
         }
     }
 
+    /// Returns the center [`Point`] of this [`Rect`], i.e. `top_left + dimensions / 2`. Fractional
+    /// coordinates are rounded towards the top-left point.
+    #[inline]
+    pub const fn center(&self) -> Point {
+        (
+            self.top_left.0 + self.dimensions.0 / 2,
+            self.top_left.1 + self.dimensions.1 / 2,
+        )
+    }
+
     /// Returns the [`u32`]s of this [`Rect`].
     #[inline]
     pub const fn dimensions(&self) -> (u32, u32) {
@@ -293,6 +369,90 @@ impl Rect {
             && self.contains((other_br.0 - 1, other_br.1 - 1))
     }
 
+    /// Returns this [`Rect`] with it's top-left point offset by `(dx, dy)`, keeping it's
+    /// dimensions. Returns [`None`] if a coordinate of the top-left point would be negative or if
+    /// the coordinates of the bottom-right point would not fit into [`u32`]s.
+    #[inline]
+    pub const fn translated(&self, dx: i64, dy: i64) -> Option<Rect> {
+        const fn offset(coord: u32, delta: i64) -> Option<u32> {
+            let Some(result) = (coord as i64).checked_add(delta) else {
+                return None;
+            };
+
+            if result >= 0 && result <= u32::MAX as i64 {
+                Some(result as u32)
+            } else {
+                None
+            }
+        }
+
+        let (Some(x), Some(y)) = (offset(self.top_left.0, dx), offset(self.top_left.1, dy)) else {
+            return None;
+        };
+
+        Rect::try_new((x, y), self.dimensions)
+    }
+
+    /// Offsets the top-left point of this [`Rect`] by `(dx, dy)`, keeping it's dimensions. Returns
+    /// whether it was moved: if [`translated`][Rect::translated] would return [`None`], this
+    /// [`Rect`] is left unchanged and `false` is returned.
+    #[inline]
+    pub fn translate(&mut self, dx: i64, dy: i64) -> bool {
+        match self.translated(dx, dy) {
+            Some(translated) => {
+                *self = translated;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns an iterator over every [`Point`] contained in this [`Rect`], in row-major
+    /// (top-left to bottom-right) order.
+    #[inline]
+    pub const fn points(&self) -> RectPoints {
+        RectPoints {
+            rect: *self,
+            front: 0,
+            back: self.len(),
+        }
+    }
+
+    /// Returns the part of this [`Rect`] that fits within `container`, or [`None`] if they don't
+    /// overlap. This is the same as [`intersection`][Rect::intersection], and is useful for
+    /// clipping regions to the bounds of a view, e.g. `img.view(region.clamped(&img.bounds())?)`.
+    #[inline]
+    pub const fn clamped(&self, container: &Rect) -> Option<Rect> {
+        self.intersection(container)
+    }
+
+    /// Returns the smallest [`Rect`] that contains both this [`Rect`] and another one.
+    ///
+    /// Empty [`Rect`]s don't contain any points, so they are ignored: if one of the [`Rect`]s is
+    /// empty, the other one is returned. If both are empty, this [`Rect`] is returned.
+    #[inline]
+    pub const fn union(&self, other: &Rect) -> Rect {
+        if other.is_empty() {
+            return *self;
+        }
+
+        if self.is_empty() {
+            return *other;
+        }
+
+        let (self_br, other_br) = (self.bottom_right(), other.bottom_right());
+        Rect::from_extremes(
+            (
+                const_min(self.top_left.0, other.top_left.0),
+                const_min(self.top_left.1, other.top_left.1),
+            ),
+            (
+                const_max(self_br.0, other_br.0),
+                const_max(self_br.1, other_br.1),
+            ),
+        )
+    }
+
     /// Returns whether this [`Rect`] contains another [`Rect`] that is relative to the top-left point
     /// of this [`Rect`].
     #[inline]
@@ -333,8 +493,140 @@ impl Rect {
             || self.is_completely_to_the_right(other)
             || other.is_completely_to_the_right(self))
     }
+
+    /// Returns the [`Rect`] where this [`Rect`] and another one overlap, or [`None`] if they don't
+    /// overlap (including when they only share an edge, since the bottom-right point is exclusive).
+    #[inline]
+    pub const fn intersection(&self, other: &Rect) -> Option<Rect> {
+        if !self.overlaps(other) {
+            return None;
+        }
+
+        let (self_br, other_br) = (self.bottom_right(), other.bottom_right());
+        Some(Rect::from_extremes(
+            (
+                const_max(self.top_left.0, other.top_left.0),
+                const_max(self.top_left.1, other.top_left.1),
+            ),
+            (
+                const_min(self_br.0, other_br.0),
+                const_min(self_br.1, other_br.1),
+            ),
+        ))
+    }
+
+    /// Returns an iterator over tiles of this [`Rect`] with the given dimensions, in row-major
+    /// order, along with the region around each of them extended by `halo` points in every
+    /// direction. Both are absolute and clipped to this [`Rect`].
+    ///
+    /// This is useful for tiled processing that reads neighbours, like convolutions: every tile
+    /// reads it's extended region and writes only to it's core. Tiles on the right and bottom
+    /// edges may be smaller than `tile`, and the extended regions of tiles on the edges are
+    /// smaller than the full halo.
+    ///
+    /// # Panics
+    /// Panics if any of the tile dimensions is zero.
+    pub fn tiles_with_halo(
+        &self,
+        tile: (u32, u32),
+        halo: u32,
+    ) -> impl Iterator<Item = (Rect, Rect)> {
+        let (tile_width, tile_height) = tile;
+        assert!(
+            tile_width != 0 && tile_height != 0,
+            "tile dimensions must be non-zero"
+        );
+
+        let bounds = *self;
+        let (left, top) = bounds.top_left;
+        let (right, bottom) = bounds.bottom_right();
+
+        (top..bottom)
+            .step_by(tile_height as usize)
+            .flat_map(move |y| {
+                (left..right)
+                    .step_by(tile_width as usize)
+                    .map(move |x| (x, y))
+            })
+            .map(move |(x, y)| {
+                let core = Rect::from_extremes(
+                    (x, y),
+                    (
+                        x.saturating_add(tile_width).min(right),
+                        y.saturating_add(tile_height).min(bottom),
+                    ),
+                );
+
+                let (core_right, core_bottom) = core.bottom_right();
+                let read = Rect::from_extremes(
+                    (
+                        x.saturating_sub(halo).max(left),
+                        y.saturating_sub(halo).max(top),
+                    ),
+                    (
+                        core_right.saturating_add(halo).min(right),
+                        core_bottom.saturating_add(halo).min(bottom),
+                    ),
+                );
+
+                (core, read)
+            })
+    }
+}
+
+/// Iterator over the points contained in a [`Rect`]. See [`Rect::points`].
+#[derive(Debug, Clone)]
+pub struct RectPoints {
+    rect: Rect,
+    front: u64,
+    back: u64,
+}
+
+impl RectPoints {
+    /// Returns the point with the given row-major index within the rect.
+    #[inline]
+    fn point(&self, index: u64) -> Point {
+        let width = u64::from(self.rect.dimensions.0);
+        let (left, top) = self.rect.top_left;
+
+        // the index is smaller than the length of the rect, so both offsets fit within it
+        (left + (index % width) as u32, top + (index / width) as u32)
+    }
 }
 
+impl Iterator for RectPoints {
+    type Item = Point;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        (self.front < self.back).then(|| {
+            self.front += 1;
+            self.point(self.front - 1)
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match usize::try_from(self.back - self.front) {
+            Ok(remaining) => (remaining, Some(remaining)),
+            Err(_) => (usize::MAX, None),
+        }
+    }
+}
+
+impl DoubleEndedIterator for RectPoints {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        (self.front < self.back).then(|| {
+            self.back -= 1;
+            self.point(self.back)
+        })
+    }
+}
+
+impl ExactSizeIterator for RectPoints {}
+impl FusedIterator for RectPoints {}
+
 #[cfg(test)]
 impl proptest::arbitrary::Arbitrary for Rect {
     type Parameters = ();
@@ -358,41 +650,58 @@ pub enum OpenError {
     Io(#[from] std::io::Error),
     #[error("decoding error: {0}")]
     Decoding(#[from] Box<dyn std::error::Error>),
-    #[error("unknown file extension: {0:?}")]
-    UnknownExt(std::ffi::OsString),
-    #[error("missing file extension")]
-    MissingExt,
+    #[error("unknown image format")]
+    UnknownFormat,
+    #[error("support for the {0:?} format isn't enabled")]
+    UnsupportedFormat(ImageFormat),
 }
 
 /// Open an image at the given path utilizing the given decoder.
-pub fn open_with_decoder<P, D>(path: P, mut decoder: D) -> Result<CommonImgBuf, OpenError>
+pub fn open_with_decoder<P, D>(path: P, decoder: D) -> Result<CommonImgBuf, OpenError>
 where
     P: AsRef<Path>,
     D: CommonImgDecoder,
 {
     let file = std::fs::File::open(path.as_ref())?;
+    decode_with(file, decoder)
+}
+
+/// Decodes an image from a reader utilizing the given decoder.
+fn decode_with<R, D>(reader: R, mut decoder: D) -> Result<CommonImgBuf, OpenError>
+where
+    R: std::io::Read,
+    D: CommonImgDecoder,
+{
     decoder
-        .decode_common(file)
+        .decode_common(reader)
         .map_err(|e| Box::new(e) as _)
         .map_err(OpenError::Decoding)
 }
 
-/// Open an image at the given path guessing the format utilizing the file extension.
+/// Open an image at the given path guessing the format from it's contents (see
+/// [`guess_format`][formats::guess_format]). The file extension is ignored.
 pub fn open<P>(path: P) -> Result<CommonImgBuf, OpenError>
 where
     P: AsRef<Path>,
 {
-    let Some(extension) = path.as_ref().extension() else {
-        return Err(OpenError::MissingExt);
-    };
+    let mut reader = BufReader::new(std::fs::File::open(path)?);
 
-    let Some(extension) = extension.to_str() else {
-        return Err(OpenError::UnknownExt(extension.to_owned()));
+    // peeking at the buffered bytes doesn't consume them, so the decoder still sees the signature
+    let Some(format) = formats::guess_format(reader.fill_buf()?) else {
+        return Err(OpenError::UnknownFormat);
     };
 
-    match extension {
-        "png" => open_with_decoder(path, Decoder),
-        extension => Err(OpenError::UnknownExt(std::ffi::OsString::from(extension))),
+    match format {
+        #[cfg(feature = "png")]
+        ImageFormat::Png => decode_with(reader, formats::png::Decoder::default()),
+        #[cfg(feature = "bmp")]
+        ImageFormat::Bmp => decode_with(reader, formats::bmp::Decoder),
+        #[cfg(feature = "jpeg")]
+        ImageFormat::Jpeg => decode_with(reader, formats::jpeg::Decoder::default()),
+        #[cfg(feature = "pnm")]
+        ImageFormat::Pnm => decode_with(reader, formats::pnm::Decoder),
+        #[allow(unreachable_patterns)]
+        format => Err(OpenError::UnsupportedFormat(format)),
     }
 }
 
@@ -409,7 +718,231 @@ mod tests {
         t.compile_fail("compile-tests/*.rs");
     }
 
+    #[test]
+    fn copy_from_at_clips() {
+        let sprite = Rgb8Img::from_fn(3, 3, |(x, y)| RGB8::new(x as u8 + 1, y as u8 + 1, 0));
+        let mut canvas = Rgb8Img::new(4, 4);
+
+        canvas.copy_from_at(&sprite, (2, 1));
+        assert_eq!(canvas.pixel((2, 1)), Some(&RGB8::new(1, 1, 0)));
+        assert_eq!(canvas.pixel((3, 3)), Some(&RGB8::new(2, 3, 0)));
+        assert_eq!(canvas.pixel((1, 1)), Some(&RGB8::new(0, 0, 0)));
+
+        canvas.copy_from_at(&sprite, (9, 9));
+        assert_eq!(canvas.pixels().filter(|p| p.r != 0).count(), 6);
+    }
+
+    #[test]
+    fn open_guesses_format() {
+        use crate::{
+            buffer::common::CommonImgBuf,
+            formats::{guess_format, ImageFormat},
+            open, OpenError,
+        };
+
+        let bytes = std::fs::read("examples/images/star.png").unwrap();
+        assert_eq!(guess_format(&bytes), Some(ImageFormat::Png));
+        assert_eq!(guess_format(b"\xff\xd8\xff\xe0"), Some(ImageFormat::Jpeg));
+        assert_eq!(guess_format(b"P6\n1 1\n255\n"), Some(ImageFormat::Pnm));
+        assert_eq!(guess_format(b"GIF89a"), None);
+        assert_eq!(guess_format(&[]), None);
+
+        // the extension doesn't matter, only the contents do
+        let path = std::env::temp_dir().join(format!("picture-open-{}.jpg", std::process::id()));
+        std::fs::write(&path, &bytes).unwrap();
+        assert!(matches!(open(&path), Ok(CommonImgBuf::Rgba8(_))));
+
+        std::fs::write(&path, b"not an image").unwrap();
+        assert!(matches!(open(&path), Err(OpenError::UnknownFormat)));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn overlay_clips() {
+        let sprite = Rgb8Img::from_fn(3, 2, |(x, y)| RGB8::new(x as u8 + 1, y as u8 + 1, 0));
+        let mut canvas = Rgb8Img::new(4, 4);
+
+        // clipped at both the right and the bottom edge
+        canvas.overlay(&sprite, (2, 3));
+        assert_eq!(canvas.pixel((2, 3)), Some(&RGB8::new(1, 1, 0)));
+        assert_eq!(canvas.pixel((3, 3)), Some(&RGB8::new(2, 1, 0)));
+        assert_eq!(canvas.pixels().filter(|p| p.r != 0).count(), 2);
+
+        canvas.overlay(&sprite, (0, 0));
+        assert_eq!(canvas.pixel((2, 1)), Some(&RGB8::new(3, 2, 0)));
+        assert_eq!(canvas.pixels().filter(|p| p.r != 0).count(), 8);
+
+        canvas.overlay(&sprite, (4, 0));
+        assert_eq!(canvas.pixels().filter(|p| p.r != 0).count(), 8);
+    }
+
+    #[test]
+    fn overlay_blended_clips() {
+        let sprite = Rgba8Img::from_fn(2, 2, |(x, _)| RGBA8::new(255, 0, 0, [255, 0][x as usize]));
+        let mut canvas = Rgba8Img::from_fn(3, 3, |_| RGBA8::new(0, 0, 255, 255));
+
+        canvas.overlay_blended(&sprite, (1, 2));
+        assert_eq!(canvas.pixel((1, 2)), Some(&RGBA8::new(255, 0, 0, 255)));
+        assert_eq!(canvas.pixel((2, 2)), Some(&RGBA8::new(0, 0, 255, 255)));
+        assert_eq!(canvas.pixels().filter(|p| p.r != 0).count(), 1);
+
+        canvas.overlay_blended(&sprite, (3, 0));
+        assert_eq!(canvas.pixels().filter(|p| p.r != 0).count(), 1);
+    }
+
+    #[test]
+    fn rect_from_center() {
+        assert_eq!(
+            Rect::from_center((10, 5), (3, 2)),
+            Some(Rect::new((7, 3), (6, 4)))
+        );
+        assert_eq!(
+            Rect::from_center((3, 2), (3, 2)),
+            Some(Rect::new((0, 0), (6, 4)))
+        );
+        assert_eq!(Rect::from_center((2, 5), (3, 2)), None);
+        assert_eq!(Rect::from_center((u32::MAX - 1, 5), (2, 2)), None);
+    }
+
+    #[test]
+    fn tiles_with_halo_geometry() {
+        let bounds = Rect::new((2, 1), (10, 7));
+        let tiles: Vec<_> = bounds.tiles_with_halo((4, 4), 2).collect();
+        assert_eq!(tiles.len(), 6);
+
+        // the cores cover the bounds exactly once
+        let covered: u64 = tiles.iter().map(|(core, _)| core.len()).sum();
+        assert_eq!(covered, bounds.len());
+        for (index, (a, _)) in tiles.iter().enumerate() {
+            assert!(tiles[index + 1..].iter().all(|(b, _)| !a.overlaps(b)));
+        }
+
+        for (core, read) in &tiles {
+            assert!(read.contains_rect(core));
+            assert!(bounds.contains_rect(read));
+        }
+
+        assert_eq!(
+            tiles[0],
+            (Rect::new((2, 1), (4, 4)), Rect::new((2, 1), (6, 6)))
+        );
+        assert_eq!(
+            tiles[4],
+            (Rect::new((6, 5), (4, 3)), Rect::new((4, 3), (8, 5)))
+        );
+        assert_eq!(
+            tiles[5],
+            (Rect::new((10, 5), (2, 3)), Rect::new((8, 3), (4, 5)))
+        );
+    }
+
+    #[test]
+    fn img_core_is_object_safe() {
+        let buffer = Rgb8Img::from_fn(4, 2, |(x, y)| RGB8::new(x as u8, y as u8, 0));
+        let view = buffer.view(Rect::new((1, 1), (3, 1))).unwrap();
+
+        let images: Vec<Box<dyn ImgCore<Pixel = RGB8> + '_>> =
+            vec![Box::new(buffer.clone()), Box::new(view)];
+        assert_eq!(images[0].dimensions(), (4, 2));
+        assert_eq!(images[1].dimensions(), (3, 1));
+        assert_eq!(images[1].pixel((0, 0)), Some(&RGB8::new(1, 1, 0)));
+    }
+
+    #[test]
+    fn rect_intersection() {
+        let a = Rect::new((0, 0), (4, 4));
+        assert_eq!(
+            a.intersection(&Rect::new((2, 1), (5, 2))),
+            Some(Rect::new((2, 1), (2, 2)))
+        );
+        assert_eq!(a.intersection(&Rect::new((4, 0), (2, 2))), None);
+        assert_eq!(a.intersection(&Rect::new((1, 1), (0, 2))), None);
+    }
+
+    #[test]
+    fn rect_center() {
+        assert_eq!(Rect::new((2, 3), (5, 4)).center(), (4, 5));
+        assert_eq!(Rect::empty((7, 7)).center(), (7, 7));
+
+        let rect = Rect::centered((10, 10), (5, 4)).unwrap();
+        assert_eq!(rect, Rect::new((8, 8), (5, 4)));
+        assert_eq!(rect.center(), (10, 10));
+        assert_eq!(Rect::centered((1, 10), (5, 4)), None);
+        assert_eq!(
+            Rect::centered((10, 10), (6, 4)),
+            Rect::from_center((10, 10), (3, 2))
+        );
+    }
+
+    #[test]
+    fn rect_points() {
+        let rect = Rect::new((3, 1), (2, 3));
+        let points: Vec<_> = rect.points().collect();
+        assert_eq!(points, [(3, 1), (4, 1), (3, 2), (4, 2), (3, 3), (4, 3)]);
+        assert!(points.iter().all(|p| rect.contains(*p)));
+        assert_eq!(rect.points().len() as u64, rect.len());
+        assert_eq!(rect.points().next_back(), Some((4, 3)));
+
+        assert_eq!(Rect::new((5, 5), (0, 3)).points().len(), 0);
+        assert_eq!(Rect::empty((1, 1)).points().next(), None);
+    }
+
+    #[test]
+    fn rect_translation() {
+        let rect = Rect::new((4, 5), (2, 3));
+        assert_eq!(rect.translated(-4, 2), Some(Rect::new((0, 7), (2, 3))));
+        assert_eq!(rect.translated(-5, 0), None);
+        assert_eq!(rect.translated(0, i64::from(u32::MAX) - 6), None);
+        assert_eq!(rect.translated(i64::MAX, 0), None);
+
+        let mut moved = rect;
+        assert!(moved.translate(1, -1));
+        assert_eq!(moved, Rect::new((5, 4), (2, 3)));
+        assert!(!moved.translate(0, -5));
+        assert_eq!(moved, Rect::new((5, 4), (2, 3)));
+    }
+
+    #[test]
+    fn rect_clamped_view() {
+        let img = Rgb8Img::from_fn(4, 3, |(x, y)| RGB8::new(x as u8, y as u8, 0));
+        let region = Rect::new((2, 1), (10, 10));
+        assert!(img.view(region).is_none());
+
+        let view = img.view(region.clamped(&img.bounds()).unwrap()).unwrap();
+        assert_eq!(view.dimensions(), (2, 2));
+        assert_eq!(view.pixel((0, 0)), Some(&RGB8::new(2, 1, 0)));
+        assert_eq!(Rect::new((4, 0), (1, 1)).clamped(&img.bounds()), None);
+    }
+
+    #[test]
+    fn rect_union() {
+        let a = Rect::new((2, 3), (2, 2));
+        let b = Rect::new((5, 1), (1, 1));
+        assert_eq!(a.union(&b), Rect::new((2, 1), (4, 4)));
+
+        // empty rects don't pull the union towards them
+        assert_eq!(a.union(&Rect::empty((0, 0))), a);
+        assert_eq!(Rect::empty((9, 9)).union(&a), a);
+    }
+
     proptest! {
+        #[cfg(not(miri))]
+        #[test]
+        fn rect_union_contains_both(a: Rect, b: Rect) {
+            let union = a.union(&b);
+            prop_assert!(a.is_empty() || union.contains_rect(&a));
+            prop_assert!(b.is_empty() || union.contains_rect(&b));
+        }
+
+        #[cfg(not(miri))]
+        #[test]
+        fn rect_intersection_is_contained(a: Rect, b: Rect) {
+            let intersection = a.intersection(&b);
+            prop_assert!(intersection.map_or(true, |i| a.contains_rect(&i)));
+            prop_assert!(intersection.map_or(true, |i| b.contains_rect(&i)));
+            prop_assert_eq!(intersection.is_some(), a.overlaps(&b));
+        }
+
         #[cfg(not(miri))]
         #[test]
         fn rect_contains_rect(a: Rect, b: Rect) {
@@ -1,15 +1,17 @@
-use super::Pixel;
+use super::{AlphaColors, Colors, Pixel, WithChannel};
+use crate::processing::Bounded;
 use crate::util::macros::count_tts;
 use bytemuck::{Pod, Zeroable};
 
 macro_rules! impl_pixel {
-    ($pixel:ident => $($field:ident),+) => {
+    ($pixel:ident => $($field:ident),+ $(; alpha $alpha:literal)?) => {
         impl<C> Pixel for $pixel<C>
         where
             [C; count_tts!($($field)+)]: bytemuck::NoUninit,
             C: Pod,
         {
             type Channels = [C; count_tts!($($field)+)];
+            $(const ALPHA_CHANNEL: Option<usize> = Some($alpha);)?
 
             #[inline(always)]
             fn new(channels: Self::Channels) -> Self {
@@ -27,14 +29,34 @@ macro_rules! impl_pixel {
                 bytemuck::must_cast_mut(self)
             }
         }
+
+        impl<C, D> WithChannel<D> for $pixel<C>
+        where
+            [C; count_tts!($($field)+)]: bytemuck::NoUninit,
+            [D; count_tts!($($field)+)]: bytemuck::NoUninit,
+            C: Pod,
+            D: Pod,
+        {
+            type Output = $pixel<D>;
+
+            #[inline]
+            fn map_channels_into<F>(&self, mut f: F) -> Self::Output
+            where
+                F: FnMut(&C) -> D,
+            {
+                let channels = self.channels();
+                <$pixel<D> as Pixel>::new(std::array::from_fn(|i| f(&channels[i])))
+            }
+        }
     };
-    (tuple $pixel:ident => $($field:ident),+) => {
+    (tuple $pixel:ident => $($field:ident),+ $(; alpha $alpha:literal)?) => {
         impl<C> Pixel for $pixel<C>
         where
             [C; count_tts!($($field)+)]: bytemuck::NoUninit,
             C: Pod,
         {
             type Channels = [C; count_tts!($($field)+)];
+            $(const ALPHA_CHANNEL: Option<usize> = Some($alpha);)?
 
             #[inline(always)]
             fn new(channels: Self::Channels) -> Self {
@@ -52,6 +74,25 @@ macro_rules! impl_pixel {
                 bytemuck::must_cast_mut(self)
             }
         }
+
+        impl<C, D> WithChannel<D> for $pixel<C>
+        where
+            [C; count_tts!($($field)+)]: bytemuck::NoUninit,
+            [D; count_tts!($($field)+)]: bytemuck::NoUninit,
+            C: Pod,
+            D: Pod,
+        {
+            type Output = $pixel<D>;
+
+            #[inline]
+            fn map_channels_into<F>(&self, mut f: F) -> Self::Output
+            where
+                F: FnMut(&C) -> D,
+            {
+                let channels = self.channels();
+                <$pixel<D> as Pixel>::new(std::array::from_fn(|i| f(&channels[i])))
+            }
+        }
     };
 }
 
@@ -93,19 +134,360 @@ re_export!(rgb::RGB);
 impl_pixel!(RGB => r, g, b);
 
 re_export!(rgb::RGBA);
-impl_pixel!(RGBA => r, g, b, a);
+impl_pixel!(RGBA => r, g, b, a; alpha 3);
 
 re_export!(rgb::alt::BGR);
 impl_pixel!(BGR => b, g, r);
 
 re_export!(rgb::alt::BGRA);
-impl_pixel!(BGRA => b, g, r, a);
+impl_pixel!(BGRA => b, g, r, a; alpha 3);
 
 re_export!(rgb::alt::Gray);
 impl_pixel!(tuple Gray => g);
 
 re_export!(rgb::alt::GrayAlpha);
-impl_pixel!(tuple GrayAlpha => g, a);
+impl_pixel!(tuple GrayAlpha => g, a; alpha 1);
+
+macro_rules! impl_colors {
+    ($pixel:ident<$count:literal> => black: $black:expr, white: $white:expr $(, transparent: $transparent:expr)?) => {
+        impl<C> Colors for $pixel<C>
+        where
+            [C; $count]: bytemuck::NoUninit,
+            C: Pod + Bounded,
+        {
+            const BLACK: Self = $black;
+            const WHITE: Self = $white;
+        }
+
+        $(
+            impl<C> AlphaColors for $pixel<C>
+            where
+                [C; $count]: bytemuck::NoUninit,
+                C: Pod + Bounded,
+            {
+                const TRANSPARENT: Self = $transparent;
+            }
+        )?
+    };
+}
+
+impl_colors!(RGB<3> =>
+    black: RGB { r: C::MIN_VALUE, g: C::MIN_VALUE, b: C::MIN_VALUE },
+    white: RGB { r: C::MAX_VALUE, g: C::MAX_VALUE, b: C::MAX_VALUE }
+);
+
+impl_colors!(RGBA<4> =>
+    black: RGBA { r: C::MIN_VALUE, g: C::MIN_VALUE, b: C::MIN_VALUE, a: C::MAX_VALUE },
+    white: RGBA { r: C::MAX_VALUE, g: C::MAX_VALUE, b: C::MAX_VALUE, a: C::MAX_VALUE },
+    transparent: RGBA { r: C::MIN_VALUE, g: C::MIN_VALUE, b: C::MIN_VALUE, a: C::MIN_VALUE }
+);
+
+impl_colors!(BGR<3> =>
+    black: BGR { b: C::MIN_VALUE, g: C::MIN_VALUE, r: C::MIN_VALUE },
+    white: BGR { b: C::MAX_VALUE, g: C::MAX_VALUE, r: C::MAX_VALUE }
+);
+
+impl_colors!(BGRA<4> =>
+    black: BGRA { b: C::MIN_VALUE, g: C::MIN_VALUE, r: C::MIN_VALUE, a: C::MAX_VALUE },
+    white: BGRA { b: C::MAX_VALUE, g: C::MAX_VALUE, r: C::MAX_VALUE, a: C::MAX_VALUE },
+    transparent: BGRA { b: C::MIN_VALUE, g: C::MIN_VALUE, r: C::MIN_VALUE, a: C::MIN_VALUE }
+);
+
+impl_colors!(Gray<1> =>
+    black: Gray(C::MIN_VALUE),
+    white: Gray(C::MAX_VALUE)
+);
+
+impl_colors!(GrayAlpha<2> =>
+    black: GrayAlpha(C::MIN_VALUE, C::MAX_VALUE),
+    white: GrayAlpha(C::MAX_VALUE, C::MAX_VALUE),
+    transparent: GrayAlpha(C::MIN_VALUE, C::MIN_VALUE)
+);
+
+/// Trait for pixels with a perceptual luminance.
+pub trait Luminance {
+    /// Returns the relative luminance of this pixel in `[0, 1]`, using the Rec. 709 coefficients
+    /// (`0.2126`, `0.7152` and `0.0722`). Alpha is ignored.
+    fn luminance(&self) -> f32;
+}
+
+macro_rules! impl_luminance {
+    ($($pixel:ident),+ => rgb) => {
+        $(
+            impl<C> Luminance for $pixel<C>
+            where
+                C: Bounded,
+            {
+                #[inline]
+                fn luminance(&self) -> f32 {
+                    0.2126 * self.r.to_normalized_f32()
+                        + 0.7152 * self.g.to_normalized_f32()
+                        + 0.0722 * self.b.to_normalized_f32()
+                }
+            }
+        )+
+    };
+    ($($pixel:ident<$n:literal>),+ => gray) => {
+        $(
+            impl<C> Luminance for $pixel<C>
+            where
+                Self: Pixel<Channels = [C; $n]>,
+                C: Bounded,
+            {
+                #[inline]
+                fn luminance(&self) -> f32 {
+                    self.channels()[0].to_normalized_f32()
+                }
+            }
+        )+
+    };
+}
+
+impl_luminance!(RGB, RGBA, BGR, BGRA => rgb);
+impl_luminance!(Gray<1>, GrayAlpha<2> => gray);
+
+/// Trait for pixels that can be alpha composited.
+pub trait Blend {
+    /// Composites this pixel over `background` using straight-alpha source-over blending, i.e.
+    /// `out = src + dst * (1 - src_a)`.
+    #[must_use]
+    fn over(self, background: Self) -> Self;
+}
+
+macro_rules! impl_blend {
+    ($($channel:ty),+) => {
+        $(
+            impl Blend for RGBA<$channel> {
+                #[inline]
+                fn over(self, background: Self) -> Self {
+                    const MAX: u64 = <$channel>::MAX as u64;
+
+                    match self.a {
+                        <$channel>::MAX => return self,
+                        0 => return background,
+                        _ => (),
+                    }
+
+                    // weights are scaled by MAX², so everything is done in integers
+                    let src_weight = u64::from(self.a) * MAX;
+                    let dst_weight = u64::from(background.a) * (MAX - u64::from(self.a));
+                    let total = src_weight + dst_weight;
+
+                    let channel = |src: $channel, dst: $channel| {
+                        let value = u64::from(src) * src_weight + u64::from(dst) * dst_weight;
+                        ((value + total / 2) / total) as $channel
+                    };
+
+                    RGBA::new(
+                        channel(self.r, background.r),
+                        channel(self.g, background.g),
+                        channel(self.b, background.b),
+                        ((total + MAX / 2) / MAX) as $channel,
+                    )
+                }
+            }
+        )+
+    };
+}
+
+impl_blend!(u8, u16);
+
+/// Trait for pixels that can be converted between straight and premultiplied alpha.
+pub trait Premultiply {
+    /// Converts this pixel from straight to premultiplied alpha, multiplying each color channel by
+    /// the alpha.
+    #[must_use]
+    fn premultiply(self) -> Self;
+
+    /// Converts this pixel from premultiplied to straight alpha, dividing each color channel by the
+    /// alpha. Fully transparent pixels have their color channels set to zero.
+    ///
+    /// Pixels with low alpha lose precision when premultiplied, so round-tripping them is lossy.
+    #[must_use]
+    fn unpremultiply(self) -> Self;
+}
+
+macro_rules! impl_premultiply {
+    ($($channel:ty),+) => {
+        $(
+            impl Premultiply for RGBA<$channel> {
+                #[inline]
+                fn premultiply(self) -> Self {
+                    const MAX: u64 = <$channel>::MAX as u64;
+
+                    let alpha = u64::from(self.a);
+                    let channel = |c: $channel| ((u64::from(c) * alpha + MAX / 2) / MAX) as $channel;
+
+                    RGBA::new(channel(self.r), channel(self.g), channel(self.b), self.a)
+                }
+
+                #[inline]
+                fn unpremultiply(self) -> Self {
+                    const MAX: u64 = <$channel>::MAX as u64;
+
+                    if self.a == 0 {
+                        return RGBA::new(0, 0, 0, 0);
+                    }
+
+                    let alpha = u64::from(self.a);
+                    let channel = |c: $channel| {
+                        ((u64::from(c) * MAX + alpha / 2) / alpha).min(MAX) as $channel
+                    };
+
+                    RGBA::new(channel(self.r), channel(self.g), channel(self.b), self.a)
+                }
+            }
+        )+
+    };
+}
+
+impl_premultiply!(u8, u16);
+
+/// Trait for channel types that can be scaled into the range of another channel type, keeping
+/// black as black and white as white (e.g. `255u8` scales into `65535u16`, not `65280u16`).
+pub trait ScaleChannel<D> {
+    /// Scales this channel into `D`, rounding to the nearest value.
+    fn scale(self) -> D;
+}
+
+impl<C> ScaleChannel<C> for C {
+    #[inline(always)]
+    fn scale(self) -> C {
+        self
+    }
+}
+
+impl ScaleChannel<u16> for u8 {
+    #[inline(always)]
+    fn scale(self) -> u16 {
+        // 65535 / 255 = 257, so this maps 0..=255 evenly onto 0..=65535
+        u16::from(self) * 257
+    }
+}
+
+impl ScaleChannel<u8> for u16 {
+    #[inline(always)]
+    fn scale(self) -> u8 {
+        ((u32::from(self) + 128) / 257) as u8
+    }
+}
+
+/// Trait for pixels that can be split into and built from RGBA components, which allows
+/// converting between them with [`Convert`].
+pub trait RgbaParts: Pixel {
+    /// The type of a channel of this pixel.
+    type Channel: Bounded;
+
+    /// Splits this pixel into it's RGBA components. Grayscale pixels repeat their value in every
+    /// color component and pixels without alpha are fully opaque.
+    fn into_rgba(self) -> [Self::Channel; 4];
+
+    /// Builds a pixel from RGBA components. Grayscale pixels use the [`Luminance`] of the color
+    /// components and pixels without alpha drop it.
+    fn from_rgba(rgba: [Self::Channel; 4]) -> Self;
+}
+
+macro_rules! impl_rgba_parts {
+    ($($pixel:ident<$n:literal>: |$p:ident| [$r:expr, $g:expr, $b:expr, $a:expr] <- |$parts:pat_param| $new:expr;)+) => {
+        $(
+            impl<C> RgbaParts for $pixel<C>
+            where
+                Self: Pixel<Channels = [C; $n]>,
+                C: Bounded,
+            {
+                type Channel = C;
+
+                #[inline]
+                fn into_rgba(self) -> [C; 4] {
+                    let $p = self;
+                    [$r, $g, $b, $a]
+                }
+
+                #[inline]
+                fn from_rgba($parts: [C; 4]) -> Self {
+                    $new
+                }
+            }
+        )+
+    };
+}
+
+/// Returns the [`Luminance`] of RGB components, in the same channel type.
+#[inline]
+fn gray_from_rgb<C>(r: C, g: C, b: C) -> C
+where
+    C: Bounded,
+{
+    C::from_normalized_f32(RGB::new(r, g, b).luminance().clamp(0.0, 1.0))
+}
+
+impl_rgba_parts! {
+    RGB<3>: |p| [p.r, p.g, p.b, C::MAX_VALUE] <- |[r, g, b, _]| RGB { r, g, b };
+    RGBA<4>: |p| [p.r, p.g, p.b, p.a] <- |[r, g, b, a]| RGBA { r, g, b, a };
+    BGR<3>: |p| [p.r, p.g, p.b, C::MAX_VALUE] <- |[r, g, b, _]| BGR { b, g, r };
+    BGRA<4>: |p| [p.r, p.g, p.b, p.a] <- |[r, g, b, a]| BGRA { b, g, r, a };
+    Gray<1>: |p| [p.channels()[0], p.channels()[0], p.channels()[0], C::MAX_VALUE]
+        <- |[r, g, b, _]| <Self as Pixel>::new([gray_from_rgb(r, g, b)]);
+    GrayAlpha<2>: |p| [p.channels()[0], p.channels()[0], p.channels()[0], p.channels()[1]]
+        <- |[r, g, b, a]| <Self as Pixel>::new([gray_from_rgb(r, g, b), a]);
+}
+
+/// Trait for pixels that can be converted into another pixel type, e.g. `rgba8.convert()` into
+/// a [`RGB16`].
+///
+/// This is implemented between every pair of [`RGB`], [`RGBA`], [`BGR`], [`BGRA`], [`Gray`] and
+/// [`GrayAlpha`] pixels whose channels can be scaled into each other with [`ScaleChannel`], i.e.
+/// the same channel type, [`u8`] and [`u16`]:
+/// - Converting a pixel without alpha into a pixel with alpha adds a fully opaque one (use
+///   [`ConvertWithAlpha`] to add a different one) and converting into a pixel without alpha
+///   drops it.
+/// - Converting between RGB and BGR swaps the channel order.
+/// - Converting into grayscale uses the [`Luminance`] of the color, and converting from grayscale
+///   repeats the value in every color channel.
+pub trait Convert<Target> {
+    /// Converts this pixel into `Target`.
+    #[must_use]
+    fn convert(self) -> Target;
+}
+
+/// Trait for pixels that can be converted into another pixel type with a given alpha, e.g.
+/// `rgb8.convert_with_alpha(0)` into a transparent [`RGBA8`]. See [`Convert`].
+pub trait ConvertWithAlpha<Target: RgbaParts>: Convert<Target> {
+    /// Converts this pixel into `Target`, using `alpha` as the alpha of the result if this pixel
+    /// doesn't have an alpha channel. If it does, it's alpha is kept, just like with
+    /// [`convert`][Convert::convert].
+    #[must_use]
+    fn convert_with_alpha(self, alpha: Target::Channel) -> Target;
+}
+
+impl<P, T> Convert<T> for P
+where
+    P: RgbaParts,
+    T: RgbaParts,
+    P::Channel: ScaleChannel<T::Channel>,
+{
+    #[inline]
+    fn convert(self) -> T {
+        T::from_rgba(self.into_rgba().map(ScaleChannel::scale))
+    }
+}
+
+impl<P, T> ConvertWithAlpha<T> for P
+where
+    P: RgbaParts,
+    T: RgbaParts,
+    P::Channel: ScaleChannel<T::Channel>,
+{
+    #[inline]
+    fn convert_with_alpha(self, alpha: T::Channel) -> T {
+        let mut rgba = self.into_rgba().map(ScaleChannel::scale);
+        if P::ALPHA_CHANNEL.is_none() {
+            rgba[3] = alpha;
+        }
+
+        T::from_rgba(rgba)
+    }
+}
 
 re_export!(alias rgb::RGB8);
 re_export!(alias rgb::RGB16);
@@ -122,3 +504,272 @@ gen_pixel!(CMY => c, m, y);
 gen_pixel!(HSV => h, s, v);
 gen_pixel!(HSL => h, s, l);
 gen_pixel!(YCbCr => y, cb, cr);
+
+// YCbCr is left out since it's black and white depend on the middle of the channel range
+impl_colors!(CMY<3> =>
+    black: CMY { c: C::MAX_VALUE, m: C::MAX_VALUE, y: C::MAX_VALUE },
+    white: CMY { c: C::MIN_VALUE, m: C::MIN_VALUE, y: C::MIN_VALUE }
+);
+
+impl_colors!(HSV<3> =>
+    black: HSV { h: C::MIN_VALUE, s: C::MIN_VALUE, v: C::MIN_VALUE },
+    white: HSV { h: C::MIN_VALUE, s: C::MIN_VALUE, v: C::MAX_VALUE }
+);
+
+impl_colors!(HSL<3> =>
+    black: HSL { h: C::MIN_VALUE, s: C::MIN_VALUE, l: C::MIN_VALUE },
+    white: HSL { h: C::MIN_VALUE, s: C::MIN_VALUE, l: C::MAX_VALUE }
+);
+
+/// Returns the hue (in degrees, in `[0, 360)`), the maximum and the minimum of normalized RGB
+/// channels.
+fn hue_max_min([r, g, b]: [f32; 3]) -> (f32, f32, f32) {
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let chroma = max - min;
+
+    let hue = if chroma <= 0.0 {
+        0.0
+    } else if r >= g && r >= b {
+        60.0 * ((g - b) / chroma).rem_euclid(6.0)
+    } else if g >= b {
+        60.0 * ((b - r) / chroma + 2.0)
+    } else {
+        60.0 * ((r - g) / chroma + 4.0)
+    };
+
+    // rem_euclid can round up to exactly 360
+    (if hue >= 360.0 { 0.0 } else { hue }, max, min)
+}
+
+/// Returns normalized RGB channels from a hue (in degrees, any value), a chroma and the amount to
+/// add to every channel.
+fn rgb_from_hue(hue: f32, chroma: f32, offset: f32) -> RGB8 {
+    let sector = hue.rem_euclid(360.0) / 60.0;
+    let x = chroma * (1.0 - (sector.rem_euclid(2.0) - 1.0).abs());
+    let (r, g, b) = match sector as u32 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+
+    let to_u8 = |c: f32| ((c + offset) * 255.0).round().clamp(0.0, 255.0) as u8;
+    RGB8::new(to_u8(r), to_u8(g), to_u8(b))
+}
+
+impl From<RGB8> for HSV<f32> {
+    /// Converts an RGB color into HSV, with the hue in degrees in `[0, 360)` and the saturation
+    /// and value in `[0, 1]`.
+    fn from(color: RGB8) -> Self {
+        let channels = color.channels().map(|c| f32::from(c) / 255.0);
+        let (h, max, min) = hue_max_min(channels);
+        let s = if max <= 0.0 { 0.0 } else { (max - min) / max };
+
+        HSV { h, s, v: max }
+    }
+}
+
+impl From<HSV<f32>> for RGB8 {
+    /// Converts an HSV color, with the hue in degrees and the saturation and value in `[0, 1]`,
+    /// into RGB. Hues outside of `[0, 360)` wrap around.
+    fn from(color: HSV<f32>) -> Self {
+        let (s, v) = (color.s.clamp(0.0, 1.0), color.v.clamp(0.0, 1.0));
+        let chroma = v * s;
+
+        rgb_from_hue(color.h, chroma, v - chroma)
+    }
+}
+
+impl From<RGB8> for HSL<f32> {
+    /// Converts an RGB color into HSL, with the hue in degrees in `[0, 360)` and the saturation
+    /// and lightness in `[0, 1]`.
+    fn from(color: RGB8) -> Self {
+        let channels = color.channels().map(|c| f32::from(c) / 255.0);
+        let (h, max, min) = hue_max_min(channels);
+        let l = (max + min) / 2.0;
+        let s = if max <= min {
+            0.0
+        } else {
+            (max - min) / (1.0 - (2.0 * l - 1.0).abs())
+        };
+
+        HSL {
+            h,
+            s: s.clamp(0.0, 1.0),
+            l,
+        }
+    }
+}
+
+impl From<HSL<f32>> for RGB8 {
+    /// Converts an HSL color, with the hue in degrees and the saturation and lightness in
+    /// `[0, 1]`, into RGB. Hues outside of `[0, 360)` wrap around.
+    fn from(color: HSL<f32>) -> Self {
+        let (s, l) = (color.s.clamp(0.0, 1.0), color.l.clamp(0.0, 1.0));
+        let chroma = (1.0 - (2.0 * l - 1.0).abs()) * s;
+
+        rgb_from_hue(color.h, chroma, l - chroma / 2.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn pixel_colors() {
+        assert_eq!(RGB8::BLACK, RGB8::new(0, 0, 0));
+        assert_eq!(RGB16::WHITE, RGB16::new(u16::MAX, u16::MAX, u16::MAX));
+        assert_eq!(RGBA8::TRANSPARENT, RGBA8::new(0, 0, 0, 0));
+        assert_eq!(RGBA8::OPAQUE_WHITE, RGBA8::new(255, 255, 255, 255));
+        assert_eq!(RGB::<f32>::WHITE, RGB::new(1.0, 1.0, 1.0));
+    }
+
+    #[test]
+    fn pixel_blend_over() {
+        let red = RGBA8::new(255, 0, 0, 255);
+        let half_blue = RGBA8::new(0, 0, 255, 128);
+        assert_eq!(half_blue.over(red), RGBA8::new(127, 0, 128, 255));
+        assert_eq!(red.over(half_blue), red);
+        assert_eq!(RGBA8::TRANSPARENT.over(half_blue), half_blue);
+        assert_eq!(
+            half_blue.over(RGBA8::TRANSPARENT),
+            RGBA8::new(0, 0, 255, 128)
+        );
+        assert_eq!(
+            RGBA16::new(0, 0, 65535, 32768).over(RGBA16::new(65535, 0, 0, 65535)),
+            RGBA16::new(32767, 0, 32768, 65535)
+        );
+    }
+
+    #[test]
+    fn pixel_convert() {
+        let rgba = RGBA8::new(10, 20, 30, 40);
+        let rgb: RGB8 = rgba.convert();
+        let bgra: BGRA<u8> = rgba.convert();
+        let opaque: RGBA8 = BGR { b: 3u8, g: 2, r: 1 }.convert();
+        assert_eq!(rgb, RGB8::new(10, 20, 30));
+        assert_eq!((bgra.b, bgra.g, bgra.r, bgra.a), (30, 20, 10, 40));
+        assert_eq!(opaque, RGBA8::new(1, 2, 3, 255));
+
+        // bit depth conversions keep white as white
+        let wide: RGBA16 = RGBA8::new(0, 1, 128, 255).convert();
+        let narrow: RGB8 = RGB16::new(32767, 32896, 65406).convert();
+        let gray: Gray<u16> = Gray(255u8).convert();
+        assert_eq!(wide, RGBA16::new(0, 257, 32896, 65535));
+        assert_eq!(narrow, RGB8::new(127, 128, 254));
+        assert_eq!(gray, Gray(65535));
+
+        // grayscale conversions use the luminance
+        let green: Gray<u8> = RGB8::new(0, 255, 0).convert();
+        let gray_alpha: GrayAlpha<u8> = rgba.convert();
+        let transparent_white: RGBA16 = GrayAlpha(255u8, 0).convert();
+        assert_eq!(green, Gray(182));
+        assert_eq!(gray_alpha, GrayAlpha(19, 40));
+        assert_eq!(transparent_white, RGBA16::new(65535, 65535, 65535, 0));
+
+        for v in 0..=255 {
+            let gray: Gray<u8> = RGB8::new(v, v, v).convert();
+            let wide: RGB16 = RGB8::new(v, v, v).convert();
+            let round_trip: RGB8 = wide.convert();
+            assert_eq!(gray, Gray(v));
+            assert_eq!(round_trip, RGB8::new(v, v, v));
+        }
+
+        let img = Rgba8Img::from_fn(2, 2, |(x, y)| RGBA8::new(x as u8, y as u8, 0, 0));
+        let converted: Rgb8Img = img.map_vec(Convert::convert);
+        assert_eq!(converted.pixel((1, 1)), Some(&RGB8::new(1, 1, 0)));
+
+        // the given alpha only applies to pixels without one, and is in the target channel type
+        let half: RGBA16 = RGB8::new(1, 2, 3).convert_with_alpha(32768);
+        let gray: GrayAlpha<u8> = Gray(7u8).convert_with_alpha(0);
+        let kept: BGRA<u8> = rgba.convert_with_alpha(255);
+        let dropped: RGB8 = RGB8::new(1, 2, 3).convert_with_alpha(0);
+        assert_eq!(half, RGBA16::new(257, 514, 771, 32768));
+        assert_eq!(gray, GrayAlpha(7, 0));
+        assert_eq!(kept.a, 40);
+        assert_eq!(dropped, RGB8::new(1, 2, 3));
+    }
+
+    #[test]
+    fn pixel_premultiply() {
+        let color = RGBA8::new(200, 100, 50, 128);
+        assert_eq!(color.premultiply(), RGBA8::new(100, 50, 25, 128));
+        assert_eq!(
+            color.premultiply().unpremultiply(),
+            RGBA8::new(199, 100, 50, 128)
+        );
+        assert_eq!(RGBA8::new(9, 9, 9, 0).unpremultiply(), RGBA8::TRANSPARENT);
+
+        for value in 0..=255 {
+            let opaque = RGBA8::new(value, 255 - value, value / 2, 255);
+            assert_eq!(opaque.premultiply(), opaque);
+            assert_eq!(opaque.unpremultiply(), opaque);
+
+            let opaque = RGBA16::new(u16::from(value) * 257, 12345, 0, u16::MAX);
+            assert_eq!(opaque.premultiply().unpremultiply(), opaque);
+        }
+    }
+
+    #[test]
+    fn pixel_map_channels() {
+        let darker = RGBA8::new(5, 100, 255, 200).map_channels(|c| c.saturating_sub(10));
+        assert_eq!(darker, RGBA8::new(0, 90, 245, 190));
+        assert_eq!(Gray(3u16).map_channels(|c| c * 2), Gray(6));
+        assert_eq!([7u8, 250].map_channels(|c| c.min(200)), [7, 200]);
+    }
+
+    #[test]
+    fn pixel_luminance() {
+        assert!(RGB8::BLACK.luminance().abs() < 1e-6);
+        assert!((RGB16::WHITE.luminance() - 1.0).abs() < 1e-6);
+        assert!((RGB8::new(0, 255, 0).luminance() - 0.7152).abs() < 1e-6);
+        assert!((RGBA8::new(0, 0, 255, 0).luminance() - 0.0722).abs() < 1e-6);
+        let red = BGR {
+            b: 0u8,
+            g: 0,
+            r: 255,
+        };
+        assert!((red.luminance() - 0.2126).abs() < 1e-6);
+        assert!((Gray(51u8).luminance() - 0.2).abs() < 1e-6);
+    }
+
+    #[test]
+    fn hsv_hsl_conversions() {
+        let orange = RGB8::new(255, 128, 0);
+        let hsv = HSV::from(orange);
+        assert!((hsv.h - 30.1).abs() < 0.1);
+        assert!((hsv.s - 1.0).abs() < 1e-6 && (hsv.v - 1.0).abs() < 1e-6);
+        let hsl = HSL::from(orange);
+        assert!((hsl.h - hsv.h).abs() < 1e-4);
+        assert!((hsl.s - 1.0).abs() < 1e-6 && (hsl.l - 0.5).abs() < 0.01);
+
+        // hue shifting wraps around
+        let red = HSV::from(RGB8::new(255, 0, 0));
+        let shifted = RGB8::from(HSV {
+            h: red.h + 360.0 * 2.0 + 120.0,
+            ..red
+        });
+        assert_eq!(shifted, RGB8::new(0, 255, 0));
+        let shifted = RGB8::from(HSL {
+            h: -120.0,
+            ..HSL::from(RGB8::new(255, 0, 0))
+        });
+        assert_eq!(shifted, RGB8::new(0, 0, 255));
+
+        for color in [
+            RGB8::BLACK,
+            RGB8::WHITE,
+            RGB8::new(12, 200, 99),
+            RGB8::new(255, 0, 1),
+            RGB8::new(70, 70, 71),
+        ] {
+            assert_eq!(RGB8::from(HSV::from(color)), color);
+            assert_eq!(RGB8::from(HSL::from(color)), color);
+        }
+    }
+}
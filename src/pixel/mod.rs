@@ -16,6 +16,10 @@ pub trait Pixel {
     /// Array that represents the channels and, therefore, specifies the type of a channel and the channel count.
     type Channels: Array;
 
+    /// The index of the alpha channel within [`Channels`][Pixel::Channels], if this pixel type
+    /// has one.
+    const ALPHA_CHANNEL: Option<usize> = None;
+
     /// Creates a new pixel from it's channels.
     fn new(channels: Self::Channels) -> Self;
 
@@ -24,6 +28,23 @@ pub trait Pixel {
 
     /// Returns a mutable reference to the channels of this pixel.
     fn channels_mut(&mut self) -> &mut Self::Channels;
+
+    /// Returns this pixel with every channel mapped through `f`, e.g.
+    /// `pixel.map_channels(|c| c.saturating_sub(10))`. Alpha channels are mapped too.
+    #[inline]
+    #[must_use]
+    fn map_channels<F>(mut self, mut f: F) -> Self
+    where
+        Self: Sized,
+        <Self::Channels as Array>::Elem: Copy,
+        F: FnMut(<Self::Channels as Array>::Elem) -> <Self::Channels as Array>::Elem,
+    {
+        for channel in self.channels_mut().iter_mut() {
+            *channel = f(*channel);
+        }
+
+        self
+    }
 }
 
 impl<T, const SIZE: usize> Pixel for [T; SIZE]
@@ -46,3 +67,94 @@ where
         self
     }
 }
+
+/// Trait for pixel types that are generic over their channel type, such as
+/// [`RGB<C>`][common::RGB]. This allows converting a pixel into the same kind of pixel with
+/// another channel type `D`.
+pub trait WithChannel<D>: Pixel {
+    /// This pixel type, but with `D` as it's channel type.
+    type Output: Pixel;
+
+    /// Converts this pixel into [`Output`][WithChannel::Output] by mapping every channel through
+    /// `f`.
+    fn map_channels_into<F>(&self, f: F) -> Self::Output
+    where
+        F: FnMut(&<Self::Channels as Array>::Elem) -> D;
+}
+
+impl<T, D, const SIZE: usize> WithChannel<D> for [T; SIZE]
+where
+    Self: NoUninit,
+    [D; SIZE]: NoUninit,
+{
+    type Output = [D; SIZE];
+
+    #[inline]
+    fn map_channels_into<F>(&self, mut f: F) -> Self::Output
+    where
+        F: FnMut(&T) -> D,
+    {
+        std::array::from_fn(|i| f(&self[i]))
+    }
+}
+
+/// Marker trait for pixels with exactly three channels, such as [`RGB`][common::RGB] or
+/// [`HSV`][common::HSV].
+///
+/// This is implemented for every [`Pixel`] whose [`Channels`][Pixel::Channels] is `[C; 3]`, and
+/// can be used as a bound to reject pixels with a different amount of channels (e.g. grayscale or
+/// pixels with alpha) at compile time.
+pub trait ThreeChannel: Pixel<Channels = [<Self as ThreeChannel>::Channel; 3]> {
+    /// The type of a channel of this pixel.
+    type Channel;
+}
+
+impl<P, C> ThreeChannel for P
+where
+    P: Pixel<Channels = [C; 3]>,
+{
+    type Channel = C;
+}
+
+/// Marker trait for pixels with exactly four channels, such as [`RGBA`][common::RGBA].
+///
+/// This is implemented for every [`Pixel`] whose [`Channels`][Pixel::Channels] is `[C; 4]`. See
+/// [`ThreeChannel`].
+pub trait FourChannel: Pixel<Channels = [<Self as FourChannel>::Channel; 4]> {
+    /// The type of a channel of this pixel.
+    type Channel;
+}
+
+impl<P, C> FourChannel for P
+where
+    P: Pixel<Channels = [C; 4]>,
+{
+    type Channel = C;
+}
+
+/// Trait for pixel types with well-known black and white values, e.g. `RGB8::BLACK`.
+///
+/// Black has every color channel at [`MIN_VALUE`][crate::processing::Bounded::MIN_VALUE]
+/// and white has every color channel at
+/// [`MAX_VALUE`][crate::processing::Bounded::MAX_VALUE], according to the color model of the
+/// pixel. Pixels with an alpha channel are fully opaque.
+pub trait Colors: Pixel + Sized {
+    /// The black color.
+    const BLACK: Self;
+
+    /// The white color.
+    const WHITE: Self;
+}
+
+/// Trait for pixel types with an alpha channel and well-known colors, e.g. `RGBA8::TRANSPARENT`.
+pub trait AlphaColors: Colors {
+    /// The fully transparent color, with every channel at
+    /// [`MIN_VALUE`][crate::processing::Bounded::MIN_VALUE].
+    const TRANSPARENT: Self;
+
+    /// The fully opaque black color. Same as [`Colors::BLACK`].
+    const OPAQUE_BLACK: Self = Self::BLACK;
+
+    /// The fully opaque white color. Same as [`Colors::WHITE`].
+    const OPAQUE_WHITE: Self = Self::WHITE;
+}
@@ -0,0 +1,111 @@
+use super::Processable;
+use crate::{prelude::*, util::checked_size};
+
+/// A buffer of per-channel [`f64`] sums, used to average many views with the same dimensions
+/// without losing precision, e.g. when stacking frames.
+///
+/// Views are added with [`AccumBuf::add`] and the average is computed with [`AccumBuf::finish`].
+#[derive(Debug, Clone)]
+pub struct AccumBuf<const N: usize> {
+    sums: ImgBuf<[f64; N]>,
+    frames: u32,
+}
+
+impl<const N: usize> AccumBuf<N> {
+    /// Creates a new accumulator for views with the given dimensions, with all sums set to zero.
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            sums: ImgBuf::from_container(
+                vec![[0.0; N]; checked_size(width, height)],
+                width,
+                height,
+            ),
+            frames: 0,
+        }
+    }
+
+    /// The dimensions of the views this accumulator accepts.
+    #[inline]
+    pub fn dimensions(&self) -> (u32, u32) {
+        self.sums.dimensions()
+    }
+
+    /// The amount of views added to this accumulator so far.
+    #[inline]
+    pub fn frames(&self) -> u32 {
+        self.frames
+    }
+
+    /// Returns the current per-channel sums.
+    #[inline]
+    pub fn sums(&self) -> &ImgBuf<[f64; N]> {
+        &self.sums
+    }
+
+    /// Adds the channels of every pixel of a view to the sums.
+    ///
+    /// # Panics
+    /// Panics if the dimensions of the view aren't the same as the accumulator's.
+    pub fn add<I, P, C>(&mut self, view: &I)
+    where
+        I: Img<Pixel = P>,
+        P: Pixel<Channels = [C; N]>,
+        C: Processable,
+    {
+        assert_eq!(
+            view.dimensions(),
+            self.dimensions(),
+            "view must have the same dimensions as the accumulator"
+        );
+
+        for (sum, pixel) in self.sums.pixels_mut().zip(view.pixels()) {
+            for (sum, channel) in sum.iter_mut().zip(pixel.channels().iter()) {
+                *sum += f64::from(channel.to_f32());
+            }
+        }
+
+        self.frames += 1;
+    }
+
+    /// Divides the sums by `count` and converts them into a buffer. Values are clamped to the range
+    /// of the channel type. `count` is usually [`frames`][AccumBuf::frames].
+    ///
+    /// # Panics
+    /// Panics if `count` is zero.
+    #[must_use = "the averaged buffer is returned and the accumulator is consumed"]
+    pub fn finish<P, C>(self, count: u32) -> ImgBuf<P>
+    where
+        P: Pixel<Channels = [C; N]>,
+        C: Processable,
+    {
+        assert!(count != 0, "count must be non-zero");
+
+        let count = f64::from(count);
+        self.sums
+            .map_vec(|sum| P::new(sum.map(|s| C::from_f32((s / count) as f32))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn average_frames() {
+        let mut accum = AccumBuf::new(4, 3);
+
+        // averaging in u8 would lose the fractional parts of each frame
+        for frame in 0..10u8 {
+            let img = ImgBuf::from_fn(4, 3, |(x, _)| RGB8::new(frame, 250 + frame % 2, x as u8));
+            accum.add(&img);
+        }
+
+        assert_eq!(accum.frames(), 10);
+        let average: Rgb8Img = accum.clone().finish(accum.frames());
+        assert_eq!(average.pixel((3, 2)), Some(&RGB8::new(4, 250, 3)));
+
+        // values are clamped to the range of the channel
+        let clamped: Rgb8Img = accum.finish(1);
+        assert_eq!(clamped.pixel((3, 2)), Some(&RGB8::new(45, 255, 30)));
+    }
+}
@@ -0,0 +1,147 @@
+use crate::prelude::*;
+
+/// Lays out images with the same dimensions in a grid with the given amount of columns, separated
+/// by `gap` pixels. Empty space is filled with `background`.
+///
+/// # Panics
+/// Panics if `columns` is zero or if the images don't all have the same dimensions.
+#[must_use = "the packed buffer is returned and the original images are left unmodified"]
+pub fn pack_grid<I, P>(images: &[I], columns: u32, gap: u32, background: P) -> ImgBuf<P, Vec<P>>
+where
+    I: Img<Pixel = P>,
+    P: Pixel + Clone,
+{
+    assert!(columns != 0, "columns must be non-zero");
+    let Some(first) = images.first() else {
+        return ImgBuf::from_container(Vec::new(), 0, 0);
+    };
+
+    let (cell_width, cell_height) = first.dimensions();
+    assert!(
+        images
+            .iter()
+            .all(|img| img.dimensions() == (cell_width, cell_height)),
+        "all images must have the same dimensions"
+    );
+
+    let count = u32::try_from(images.len()).expect("image count fits within u32");
+    let columns = columns.min(count);
+    let rows = (count + columns - 1) / columns;
+
+    let width = columns * cell_width + (columns - 1) * gap;
+    let height = rows * cell_height + (rows - 1) * gap;
+    let mut result = ImgBuf::from_fn(width, height, |_| background.clone());
+
+    for (index, img) in (0..).zip(images) {
+        let (column, row) = (index % columns, index / columns);
+        let bounds = Rect::new(
+            (column * (cell_width + gap), row * (cell_height + gap)),
+            (cell_width, cell_height),
+        );
+
+        result
+            .view_mut(bounds)
+            .expect("cell is within the packed buffer")
+            .copy_from(img);
+    }
+
+    result
+}
+
+/// Packs images of any dimensions into a single buffer using a shelf packer. Returns the packed
+/// buffer along with the placement of each image, in the same order as `images`. Empty space is
+/// filled with [`P::default()`][Default::default].
+#[must_use = "the packed buffer is returned and the original images are left unmodified"]
+pub fn pack_atlas<I, P>(images: &[I]) -> (ImgBuf<P, Vec<P>>, Vec<Rect>)
+where
+    I: Img<Pixel = P>,
+    P: Pixel + Clone + Default,
+{
+    // aim for a roughly square atlas, but never narrower than the widest image
+    let area: u64 = images.iter().map(|img| img.size() as u64).sum();
+    let max_width = images.iter().map(ImgCore::width).max().unwrap_or(0);
+    let atlas_width = max_width.max((area as f64).sqrt().ceil() as u32);
+
+    // place the tallest images first, which keeps shelves tight
+    let mut order: Vec<usize> = (0..images.len()).collect();
+    order.sort_by_key(|&index| std::cmp::Reverse(images[index].height()));
+
+    let mut placements = vec![Rect::empty((0, 0)); images.len()];
+    let (mut x, mut shelf_y, mut shelf_height) = (0, 0, 0);
+    for index in order {
+        let (width, height) = images[index].dimensions();
+        if x + width > atlas_width {
+            x = 0;
+            shelf_y += shelf_height;
+            shelf_height = 0;
+        }
+
+        placements[index] = Rect::new((x, shelf_y), (width, height));
+        x += width;
+        shelf_height = shelf_height.max(height);
+    }
+
+    let mut atlas = ImgBuf::new(atlas_width, shelf_y + shelf_height);
+    for (img, bounds) in images.iter().zip(&placements) {
+        atlas
+            .view_mut(*bounds)
+            .expect("placement is within the atlas")
+            .copy_from(img);
+    }
+
+    (atlas, placements)
+}
+
+/// Splits a view into tiles with the given dimensions, in row-major order. This is the inverse of
+/// [`pack_grid`] with no gap.
+///
+/// Tiles on the right and bottom edges may not fit entirely within the view. If `padding` is
+/// `None`, these are returned clipped to the view. Otherwise, they're returned with the full tile
+/// dimensions and the area outside of the view is filled with the given pixel.
+///
+/// # Panics
+/// Panics if any of the tile dimensions is zero.
+#[must_use = "the tiles are returned and the original view is left unmodified"]
+pub fn split_into_tiles<I, P>(
+    view: &I,
+    tile: (u32, u32),
+    padding: Option<P>,
+) -> Vec<ImgBuf<P, Vec<P>>>
+where
+    I: Img<Pixel = P>,
+    P: Pixel + Clone,
+{
+    let (tile_width, tile_height) = tile;
+    assert!(
+        tile_width != 0 && tile_height != 0,
+        "tile dimensions must be non-zero"
+    );
+
+    let (width, height) = view.dimensions();
+    let mut tiles = Vec::new();
+    for y in (0..height).step_by(tile_height as usize) {
+        for x in (0..width).step_by(tile_width as usize) {
+            let bounds = Rect::new(
+                (x, y),
+                ((width - x).min(tile_width), (height - y).min(tile_height)),
+            );
+            let clipped = view.view(bounds).expect("tile is clipped to the view");
+
+            let tile = match &padding {
+                Some(padding) if bounds.dimensions() != tile => {
+                    let mut padded = ImgBuf::from_fn(tile_width, tile_height, |_| padding.clone());
+                    padded
+                        .view_mut(Rect::new((0, 0), bounds.dimensions()))
+                        .expect("clipped tile fits within a full tile")
+                        .copy_from(&clipped);
+                    padded
+                }
+                _ => clipped.to_buffer(),
+            };
+
+            tiles.push(tile);
+        }
+    }
+
+    tiles
+}
@@ -3,6 +3,15 @@ pub fn box_filter(_: f32) -> f32 {
     1.0
 }
 
+#[inline]
+pub fn nearest(x: f32) -> f32 {
+    if (-0.5..0.5).contains(&x) {
+        1.0
+    } else {
+        0.0
+    }
+}
+
 #[inline]
 pub fn triangle(x: f32) -> f32 {
     if x.abs() < 1.0 {
@@ -0,0 +1,43 @@
+use super::{EdgeMode, Processable};
+use crate::prelude::*;
+
+/// Embosses the view, making it look raised. Usually used with a bias of half the channel range
+/// (e.g. `128.0` for [`u8`]s).
+pub const EMBOSS: [f32; 9] = [-1.0, -1.0, 0.0, -1.0, 0.0, 1.0, 0.0, 1.0, 1.0];
+
+/// Sharpens the view.
+pub const SHARPEN: [f32; 9] = [0.0, -1.0, 0.0, -1.0, 5.0, -1.0, 0.0, -1.0, 0.0];
+
+/// Enhances the edges of the view while keeping the rest of it mostly unchanged.
+pub const EDGE_ENHANCE: [f32; 9] = [-0.5, -0.5, -0.5, -0.5, 5.0, -0.5, -0.5, -0.5, -0.5];
+
+/// Detects edges in the view (laplacian).
+pub const EDGE_DETECT: [f32; 9] = [0.0, 1.0, 0.0, 1.0, -4.0, 1.0, 0.0, 1.0, 0.0];
+
+/// Outlines the view, keeping only the edges.
+pub const OUTLINE: [f32; 9] = [-1.0, -1.0, -1.0, -1.0, 8.0, -1.0, -1.0, -1.0, -1.0];
+
+/// Applies a 3x3 kernel to a view and returns the result.
+///
+/// The kernel is in row-major order: `kernel[4]` weights the pixel itself, `kernel[0]` it's top-left
+/// neighbour and so on. `bias` is added to every channel after the kernel is applied and is in the
+/// units of the channel (e.g. `128.0` for half of the range of a [`u8`]). Neighbours outside of the
+/// view are handled according to `edge`.
+///
+/// Unlike [`convolve`][super::convolve], alpha channels are copied from the view unchanged: most of
+/// these kernels don't sum to `1.0` (e.g. [`EDGE_DETECT`] sums to `0.0`), so convolving alpha would
+/// make opaque pixels transparent.
+#[must_use = "the filtered buffer is returned and the original view is left unmodified"]
+pub fn apply_kernel_3x3<I, P, C, const N: usize>(
+    view: &I,
+    kernel: &[f32; 9],
+    bias: f32,
+    edge: EdgeMode,
+) -> ImgBuf<P, Vec<P>>
+where
+    I: Img<Pixel = P>,
+    P: Pixel<Channels = [C; N]>,
+    C: Processable,
+{
+    super::convolve_with(view, kernel, 3, bias, true, edge)
+}
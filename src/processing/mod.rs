@@ -1,8 +1,24 @@
+#[cfg(feature = "rayon")]
+use crate::buffer::{view::ImgBufViewMut, TilesMut};
+use crate::pixel::{
+    common::{Gray, Luminance},
+    ThreeChannel,
+};
 use crate::prelude::*;
 use crate::util::{checked_size, index_point};
 
+/// Accumulation of many views into per-channel sums, e.g. for averaging frames.
+pub mod accumulate;
+/// Packing of multiple images into a single one.
+pub mod atlas;
 /// Common sampling filters.
 pub mod filters;
+/// Common 3x3 kernels, such as sharpen and emboss.
+pub mod filters3x3;
+/// Deterministic noise generation.
+pub mod noise;
+/// Color quantization, such as median cut.
+pub mod quantize;
 
 // TODO: maybe think of a better name?
 /// Trait for channel types that can be processed.
@@ -18,8 +34,89 @@ pub trait Processable: Copy {
     fn from_f32(value: f32) -> Self;
 }
 
+mod private {
+    pub trait Sealed {}
+}
+
+/// Trait for [`Processable`] channel types with a well-known range of intensities, such as `0..=255`
+/// for [`u8`] and `0.0..=1.0` for [`f32`].
+///
+/// This trait is sealed: it's implemented for every primitive integer and floating point type and
+/// can't be implemented outside of this crate. Operations that only need [`Processable`] (e.g.
+/// resizing and blurring) don't require it.
+pub trait Bounded: Processable + private::Sealed {
+    /// The value that represents no intensity. This is the minimum value for integer types and
+    /// `0.0` for floating point types.
+    const MIN_VALUE: Self;
+
+    /// The value that represents full intensity. This is the maximum value for integer types and
+    /// `1.0` for floating point types.
+    const MAX_VALUE: Self;
+
+    /// Converts this value to a [`f32`] where [`MIN_VALUE`][Bounded::MIN_VALUE] maps to `0.0`
+    /// and [`MAX_VALUE`][Bounded::MAX_VALUE] maps to `1.0`.
+    #[inline(always)]
+    fn to_normalized_f32(self) -> f32 {
+        let min = Self::MIN_VALUE.to_f32();
+        (self.to_f32() - min) / (Self::MAX_VALUE.to_f32() - min)
+    }
+
+    /// Converts a [`f32`] where `0.0` represents [`MIN_VALUE`][Bounded::MIN_VALUE] and `1.0`
+    /// represents [`MAX_VALUE`][Bounded::MAX_VALUE] into [`Self`]. This is the inverse of
+    /// [`to_normalized_f32`][Bounded::to_normalized_f32].
+    #[inline(always)]
+    fn from_normalized_f32(value: f32) -> Self {
+        let min = Self::MIN_VALUE.to_f32();
+        Self::from_f32(value * (Self::MAX_VALUE.to_f32() - min) + min)
+    }
+
+    /// Returns the inverse of this value, i.e. the value at the same distance from
+    /// [`MAX_VALUE`][Bounded::MAX_VALUE] as this one is from [`MIN_VALUE`][Bounded::MIN_VALUE]
+    /// (e.g. `255 - c` for [`u8`]s).
+    #[inline(always)]
+    fn inverted(self) -> Self {
+        Self::from_normalized_f32(1.0 - self.to_normalized_f32())
+    }
+}
+
 macro_rules! impl_processable {
-    ($($type:ty),*) => {
+    (int $($type:ty),*) => {
+        $(
+            impl Processable for $type {
+                #[inline(always)]
+                fn to_f32(self) -> f32 {
+                    self as f32
+                }
+
+                #[inline(always)]
+                fn from_f32(value: f32) -> Self {
+                    value.clamp(Self::MIN as f32, Self::MAX as f32) as Self
+                }
+            }
+
+            impl private::Sealed for $type {}
+
+            impl Bounded for $type {
+                const MIN_VALUE: Self = Self::MIN;
+                const MAX_VALUE: Self = Self::MAX;
+
+                #[inline(always)]
+                fn from_normalized_f32(value: f32) -> Self {
+                    // round to the nearest integer so that normalizing and then denormalizing
+                    // gives back the original value
+                    let min = Self::MIN_VALUE.to_f32();
+                    Self::from_f32((value * (Self::MAX_VALUE.to_f32() - min) + min).round())
+                }
+
+                #[inline(always)]
+                fn inverted(self) -> Self {
+                    // MIN + MAX - self, which is exact and never overflows
+                    !self
+                }
+            }
+        )*
+    };
+    (float $($type:ty),*) => {
         $(
             impl Processable for $type {
                 #[inline(always)]
@@ -32,16 +129,105 @@ macro_rules! impl_processable {
                     value.clamp(Self::MIN as f32, Self::MAX as f32) as Self
                 }
             }
+
+            impl private::Sealed for $type {}
+
+            impl Bounded for $type {
+                const MIN_VALUE: Self = 0.0;
+                const MAX_VALUE: Self = 1.0;
+
+                #[inline(always)]
+                fn inverted(self) -> Self {
+                    Self::MAX_VALUE - self
+                }
+            }
         )*
     };
 }
 
-impl_processable!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64, usize, isize);
+impl_processable!(int u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, usize, isize);
+impl_processable!(float f32, f64);
 
 // useful resources:
 // - https://entropymine.com/imageworsener
 // - https://cs1230.graphics/lectures - specifically image processing I, II and III
 
+/// Filter weights for resampling along a single axis, precalculated for every target coordinate.
+struct AxisWeights {
+    weights: Vec<f32>,
+    /// The first source coordinate and the range within `weights` of every target coordinate.
+    taps: Vec<(u32, std::ops::Range<usize>)>,
+}
+
+impl AxisWeights {
+    fn new<F>(len: u32, new_len: u32, filter: F, window: f32) -> Self
+    where
+        F: Fn(f32) -> f32,
+    {
+        // find the ratio between the source length and the target length
+        let ratio = len as f32 / new_len as f32;
+        let sampling_ratio = ratio.max(1.0);
+        let inverse_sampling_ratio = 1.0 / sampling_ratio;
+
+        // if we're upsampling (ratio < 1), there's no need to scale things.
+        // however, if we're downsampling (ratio > 1), we need to scale stuff
+        // by the ratio so that we can preserve information.
+        // sampling_ratio's purpose is exactly that: it's value is 1.0 if
+        // upsampling, ratio if downsampling.
+
+        // scale the window accordingly
+        let window = window * sampling_ratio;
+
+        let offset_constant = 0.5 * (ratio - 1.0);
+        let max_src_f32 = (len - 1) as f32;
+        let mut weights = Vec::with_capacity((2 * (window as usize) + 1) * (new_len as usize));
+        let mut taps = Vec::with_capacity(new_len as usize);
+        for target in 0..new_len {
+            let equivalent_src = target as f32 * ratio + offset_constant;
+
+            let min_src_pixel = (equivalent_src - window).clamp(0.0, max_src_f32) as u32;
+            let max_src_pixel = (equivalent_src + window).clamp(0.0, max_src_f32) as u32;
+
+            let start = weights.len();
+            for src_pixel in min_src_pixel..(max_src_pixel + 1) {
+                weights.push(filter(
+                    (src_pixel as f32 - equivalent_src) * inverse_sampling_ratio,
+                ));
+            }
+
+            taps.push((min_src_pixel, start..weights.len()));
+        }
+
+        Self { weights, taps }
+    }
+
+    /// Resamples the target coordinate `target`, given a function that returns the channels of the
+    /// source pixel at a source coordinate.
+    #[inline]
+    fn sample<P, C, const N: usize>(
+        &self,
+        target: u32,
+        mut channels_at: impl FnMut(u32) -> [C; N],
+    ) -> P
+    where
+        P: Pixel<Channels = [C; N]>,
+        C: Processable,
+    {
+        let (min_src_pixel, range) = self.taps[target as usize].clone();
+
+        let mut weight_sum = 0f32;
+        let mut channel_value_sum = [0f32; N];
+        for (src_pixel, weight) in (min_src_pixel..).zip(&self.weights[range]) {
+            weight_sum += weight;
+            for (sum, channel) in channel_value_sum.iter_mut().zip(channels_at(src_pixel)) {
+                *sum += weight * channel.to_f32();
+            }
+        }
+
+        P::new(channel_value_sum.map(|v| C::from_f32(v / weight_sum)))
+    }
+}
+
 /// Resamples a view horizontally to the given width using the given filter.
 /// Height is kept the same.
 ///
@@ -55,89 +241,37 @@ pub fn resample_horizontal<I, P, C, F, const N: usize>(
     window: f32,
 ) -> ImgBuf<P, Vec<P>>
 where
-    I: Img<Pixel = P>,
+    I: LazyImg<Pixel = P>,
     P: Pixel<Channels = [C; N]>,
     C: Processable,
     F: Fn(f32) -> f32,
 {
+    let (width, height) = view.lazy_dimensions();
     if new_width == 0 {
-        return ImgBuf::from_container(Vec::new(), new_width, view.height());
+        return ImgBuf::from_container(Vec::new(), new_width, height);
     }
 
     // create container for result
-    let container_size = checked_size(new_width, view.height());
+    let container_size = checked_size(new_width, height);
     let mut container = Vec::with_capacity(container_size);
     let container_pixels = container.spare_capacity_mut();
 
-    // find the ratio between the source width and the target width
-    let ratio = view.width() as f32 / new_width as f32;
-    let sampling_ratio = ratio.max(1.0);
-    let inverse_sampling_ratio = 1.0 / sampling_ratio;
-
-    // if we're upsampling (ratio < 1), there's no need to scale things.
-    // however, if we're downsampling (ratio > 1), we need to scale stuff
-    // by the ratio so that we can preserve information.
-    // sampling_ratio's purpose is exactly that: it's value is 1.0 if
-    // upsampling, ratio if downsampling.
-
-    // scale the window accordingly
-    let window = window * sampling_ratio;
-
-    // precalculate weights
-    let offset_constant = 0.5 * (ratio - 1.0);
-    let max_src_x_f32 = (view.width() - 1) as f32;
-    let mut weights = Vec::with_capacity((2 * (window as usize) + 1) * (new_width as usize));
-    let mut weights_start_index = Vec::with_capacity(new_width as usize);
-    for target_x in 0..new_width {
-        let equivalent_src_x = target_x as f32 * ratio + offset_constant;
-
-        let left_src_pixel_x = (equivalent_src_x - window).clamp(0.0, max_src_x_f32) as u32;
-        let right_src_pixel_x = (equivalent_src_x + window).clamp(0.0, max_src_x_f32) as u32;
-
-        weights_start_index.push(weights.len());
-        for src_pixel_x in left_src_pixel_x..(right_src_pixel_x + 1) {
-            weights.push(filter(
-                (src_pixel_x as f32 - equivalent_src_x) * inverse_sampling_ratio,
-            ));
-        }
-    }
-
-    // now actually resample
-    let weights = weights.as_slice();
+    // precalculate weights, then actually resample
+    let weights = AxisWeights::new(width, new_width, filter, window);
     for target_x in 0..new_width {
-        // these could be cached as well, but it makes no performance difference (and increases
-        // memory usage), so we just calculate them again
-        let equivalent_src_x = target_x as f32 * ratio + offset_constant;
-
-        let min_src_pixel_x = (equivalent_src_x - window).clamp(0.0, max_src_x_f32) as u32;
-        let max_src_pixel_x = (equivalent_src_x + window).clamp(0.0, max_src_x_f32) as u32;
-
-        let weights_start = weights_start_index[target_x as usize];
-        for target_y in 0..view.height() {
-            let mut weight_sum = 0f32;
-            let mut channel_value_sum = [0f32; N];
-            for (index, src_pixel_x) in (min_src_pixel_x..max_src_pixel_x + 1).enumerate() {
+        for target_y in 0..height {
+            let pixel = weights.sample(target_x, |src_pixel_x| {
                 // SAFETY: target_y is in the 0..img.height() range and src_pixel_x is clamped
                 // between 0 and img.width() - 1. therefore, this coordinate is always in bounds.
-                let src_pixel = unsafe { view.pixel_unchecked((src_pixel_x, target_y)) };
-                let channels = src_pixel.channels();
-                let weight = weights[weights_start + index];
-                weight_sum += weight;
-
-                for (sum, channel) in channel_value_sum.iter_mut().zip(channels.iter()) {
-                    let value = weight * channel.to_f32();
-                    *sum += value;
-                }
-            }
-
-            let result = channel_value_sum.map(|v| C::from_f32(v / weight_sum));
+                *unsafe { view.lazy_pixel_unchecked((src_pixel_x, target_y)) }.channels()
+            });
 
             // SAFETY: this index will always be valid since target_x and target_y are always in
             // the correct range.
             unsafe {
                 container_pixels
                     .get_unchecked_mut(index_point((target_x, target_y), new_width))
-                    .write(P::new(result));
+                    .write(pixel);
             }
         }
     }
@@ -147,7 +281,7 @@ where
         container.set_len(container_size);
     }
 
-    ImgBuf::from_container(container, new_width, view.height())
+    ImgBuf::from_container(container, new_width, height)
 }
 
 /// Resamples a view vertically to the given height using the given filter.
@@ -177,75 +311,22 @@ where
     let mut container = Vec::with_capacity(container_size);
     let container_pixels = container.spare_capacity_mut();
 
-    // find the ratio between the source height and the target height
-    let ratio = view.height() as f32 / new_height as f32;
-    let sampling_ratio = ratio.max(1.0);
-    let inverse_sampling_ratio = 1.0 / sampling_ratio;
-
-    // if we're upsampling (ratio < 1), there's no need to scale things.
-    // however, if we're downsampling (ratio > 1), we need to scale stuff
-    // by the ratio so that we can preserve information.
-    // sampling_ratio's purpose is exactly that: it's value is 1.0 if
-    // upsampling, ratio if downsampling.
-
-    // scale the window accordingly
-    let window = window * sampling_ratio;
-
-    // precalculate weights
-    let offset_constant = 0.5 * (ratio - 1.0);
-    let max_src_y_f32 = (view.height() - 1) as f32;
-    let mut weights = Vec::with_capacity((2 * (window as usize) + 1) * (new_height as usize));
-    let mut weights_start_index = Vec::with_capacity(new_height as usize);
-    for target_y in 0..new_height {
-        let equivalent_src_y = target_y as f32 * ratio + offset_constant;
-
-        let min_src_pixel_y = (equivalent_src_y - window).clamp(0.0, max_src_y_f32) as u32;
-        let max_src_pixel_y = (equivalent_src_y + window).clamp(0.0, max_src_y_f32) as u32;
-
-        weights_start_index.push(weights.len());
-        for src_pixel_y in min_src_pixel_y..(max_src_pixel_y + 1) {
-            weights.push(filter(
-                (src_pixel_y as f32 - equivalent_src_y) * inverse_sampling_ratio,
-            ));
-        }
-    }
-
-    // now actually resample
-    let weights = weights.as_slice();
+    // precalculate weights, then actually resample
+    let weights = AxisWeights::new(view.height(), new_height, filter, window);
     for target_y in 0..new_height {
-        // these could be cached as well, but it makes no performance difference (and increases
-        // memory usage), so we just calculate them again
-        let equivalent_src_y = target_y as f32 * ratio + offset_constant;
-
-        let min_src_pixel_y = (equivalent_src_y - window).clamp(0.0, max_src_y_f32) as u32;
-        let max_src_pixel_y = (equivalent_src_y + window).clamp(0.0, max_src_y_f32) as u32;
-
-        let weights_start = weights_start_index[target_y as usize];
         for target_x in 0..view.width() {
-            let mut weight_sum = 0f32;
-            let mut channel_value_sum = [0f32; N];
-            for (index, src_pixel_y) in (min_src_pixel_y..max_src_pixel_y + 1).enumerate() {
+            let pixel = weights.sample(target_y, |src_pixel_y| {
                 // SAFETY: target_x is in the 0..img.width() range and src_pixel_y is clamped
                 // between 0 and img.height() - 1. therefore, this coordinate is always in bounds.
-                let src_pixel = unsafe { view.pixel_unchecked((target_x, src_pixel_y)) };
-                let channels = src_pixel.channels();
-                let weight = weights[weights_start + index];
-                weight_sum += weight;
-
-                for (sum, channel) in channel_value_sum.iter_mut().zip(channels.iter()) {
-                    let value = weight * channel.to_f32();
-                    *sum += value;
-                }
-            }
-
-            let result = channel_value_sum.map(|v| C::from_f32(v / weight_sum));
+                *unsafe { view.pixel_unchecked((target_x, src_pixel_y)) }.channels()
+            });
 
             // SAFETY: this index will always be valid since target_x and target_y are always in
             // the correct range.
             unsafe {
                 container_pixels
                     .get_unchecked_mut(index_point((target_x, target_y), view.width()))
-                    .write(P::new(result));
+                    .write(pixel);
             }
         }
     }
@@ -258,6 +339,125 @@ where
     ImgBuf::from_container(container, view.width(), new_height)
 }
 
+/// Parallel version of [`resample_horizontal`], which resamples the rows of the view in parallel.
+#[cfg(feature = "rayon")]
+#[must_use = "the resampled buffer is returned and the original view is left unmodified"]
+pub fn resample_horizontal_par<I, P, C, F, const N: usize>(
+    view: &I,
+    new_width: u32,
+    filter: F,
+    window: f32,
+) -> ImgBuf<P, Vec<P>>
+where
+    I: LazyImg<Pixel = P> + Sync,
+    P: Pixel<Channels = [C; N]> + Send,
+    C: Processable,
+    F: Fn(f32) -> f32,
+{
+    use rayon::prelude::*;
+
+    let (width, height) = view.lazy_dimensions();
+    if new_width == 0 {
+        return ImgBuf::from_container(Vec::new(), new_width, height);
+    }
+
+    let container_size = checked_size(new_width, height);
+    let mut container = Vec::with_capacity(container_size);
+
+    // every row of the result is a distinct slice of the container, so they can be written to in
+    // parallel
+    let weights = AxisWeights::new(width, new_width, filter, window);
+    container.spare_capacity_mut()[..container_size]
+        .par_chunks_mut(new_width as usize)
+        .zip(0..height)
+        .for_each(|(row, target_y)| {
+            for (pixel, target_x) in row.iter_mut().zip(0..) {
+                pixel.write(weights.sample(target_x, |src_pixel_x| {
+                    // SAFETY: target_y is in the 0..img.height() range and src_pixel_x is clamped
+                    // between 0 and img.width() - 1. therefore, this coordinate is always in
+                    // bounds.
+                    *unsafe { view.lazy_pixel_unchecked((src_pixel_x, target_y)) }.channels()
+                }));
+            }
+        });
+
+    // SAFETY: every row, and therefore every pixel, has already been initialized.
+    unsafe {
+        container.set_len(container_size);
+    }
+
+    ImgBuf::from_container(container, new_width, height)
+}
+
+/// Parallel version of [`resample_vertical`], which resamples the rows of the result in parallel.
+#[cfg(feature = "rayon")]
+#[must_use = "the resampled buffer is returned and the original view is left unmodified"]
+pub fn resample_vertical_par<I, P, C, F, const N: usize>(
+    view: &I,
+    new_height: u32,
+    filter: F,
+    window: f32,
+) -> ImgBuf<P, Vec<P>>
+where
+    I: Img<Pixel = P> + Sync,
+    P: Pixel<Channels = [C; N]> + Send,
+    C: Processable,
+    F: Fn(f32) -> f32,
+{
+    use rayon::prelude::*;
+
+    if new_height == 0 || view.width() == 0 {
+        return ImgBuf::from_container(Vec::new(), view.width(), new_height);
+    }
+
+    let container_size = checked_size(view.width(), new_height);
+    let mut container = Vec::with_capacity(container_size);
+
+    // every row of the result is a distinct slice of the container, so they can be written to in
+    // parallel
+    let weights = AxisWeights::new(view.height(), new_height, filter, window);
+    container.spare_capacity_mut()[..container_size]
+        .par_chunks_mut(view.width() as usize)
+        .zip(0..new_height)
+        .for_each(|(row, target_y)| {
+            for (pixel, target_x) in row.iter_mut().zip(0..) {
+                pixel.write(weights.sample(target_y, |src_pixel_y| {
+                    // SAFETY: target_x is in the 0..img.width() range and src_pixel_y is clamped
+                    // between 0 and img.height() - 1. therefore, this coordinate is always in
+                    // bounds.
+                    *unsafe { view.pixel_unchecked((target_x, src_pixel_y)) }.channels()
+                }));
+            }
+        });
+
+    // SAFETY: every row, and therefore every pixel, has already been initialized.
+    unsafe {
+        container.set_len(container_size);
+    }
+
+    ImgBuf::from_container(container, view.width(), new_height)
+}
+
+/// Parallel version of [`resample`].
+#[cfg(feature = "rayon")]
+#[must_use = "the resampled buffer is returned and the original view is left unmodified"]
+pub fn resample_par<I, P, C, F, const N: usize>(
+    view: &I,
+    dimensions: (u32, u32),
+    filter: F,
+    window: f32,
+) -> ImgBuf<P, Vec<P>>
+where
+    I: LazyImg<Pixel = P> + Sync,
+    P: Pixel<Channels = [C; N]> + Send + Sync,
+    C: Processable,
+    F: Fn(f32) -> f32,
+{
+    let (width, height) = dimensions;
+    let horizontal = resample_horizontal_par(view, width, &filter, window);
+    resample_vertical_par(&horizontal, height, filter, window)
+}
+
 /// Resamples a view to the given dimensions using the given filter. This is
 /// equivalent to doing a horizontal resample followed by a vertical one.
 ///
@@ -271,7 +471,7 @@ pub fn resample<I, P, C, F, const N: usize>(
     window: f32,
 ) -> ImgBuf<P, Vec<P>>
 where
-    I: Img<Pixel = P>,
+    I: LazyImg<Pixel = P>,
     P: Pixel<Channels = [C; N]>,
     C: Processable,
     F: Fn(f32) -> f32,
@@ -282,37 +482,310 @@ where
 }
 
 /// Performs a box blur in a view and returns the result.
+///
+/// `strength` is the radius, in pixels, of the box: every output pixel is the average of the
+/// pixels at most `strength` pixels away from it in each axis. Near the edges, only the pixels
+/// inside the view are averaged. See [`box_blur_radius`] for a faster version with an integer
+/// radius.
 #[must_use = "the blurred buffer is returned and the original view is left unmodified"]
 pub fn box_blur<I, P, C, const N: usize>(view: &I, strength: f32) -> ImgBuf<P, Vec<P>>
 where
-    I: Img<Pixel = P>,
+    I: LazyImg<Pixel = P>,
     P: Pixel<Channels = [C; N]>,
     C: Processable,
 {
     assert!(strength > 0.0);
-    resample(view, view.dimensions(), filters::box_filter, strength)
+    resample(view, view.lazy_dimensions(), filters::box_filter, strength)
 }
 
 /// Performs a gaussian blur in a view and returns the result.
+///
+/// `strength` is the standard deviation (sigma) of the gaussian, in pixels. The kernel is cut off
+/// at a distance of `2 * strength` pixels. See [`gaussian_blur_radius`] to specify the blur by
+/// it's radius instead.
 #[must_use = "the blurred buffer is returned and the original view is left unmodified"]
 pub fn gaussian_blur<I, P, C, const N: usize>(view: &I, strength: f32) -> ImgBuf<P, Vec<P>>
 where
-    I: Img<Pixel = P>,
+    I: LazyImg<Pixel = P>,
     P: Pixel<Channels = [C; N]>,
     C: Processable,
 {
     assert!(strength > 0.0);
     resample(
         view,
-        view.dimensions(),
+        view.lazy_dimensions(),
         |x| filters::gaussian(x, strength),
         2.0 * strength,
     )
 }
 
+/// Performs a gaussian blur in a view whose amount is controlled, per pixel, by a mask and returns
+/// the result. Returns `None` if the view and the mask have different dimensions.
+///
+/// The view is fully blurred as in [`gaussian_blur`] with `sigma` as it's strength, then every
+/// pixel is interpolated between the original and the blurred one by the value of the mask: `0`
+/// keeps the original pixel and `255` uses the fully blurred one.
+#[must_use = "the blurred buffer is returned and the original view is left unmodified"]
+pub fn masked_gaussian_blur<I, M, P, C, const N: usize>(
+    view: &I,
+    mask: &M,
+    sigma: f32,
+) -> Option<ImgBuf<P, Vec<P>>>
+where
+    I: Img<Pixel = P>,
+    M: Img<Pixel = Gray<u8>>,
+    P: Pixel<Channels = [C; N]> + Clone,
+    C: Processable,
+{
+    if view.dimensions() != mask.dimensions() {
+        return None;
+    }
+
+    let blurred = gaussian_blur(view, sigma);
+    let result = blurred.map_with_coords(|coords, blurred| {
+        // SAFETY: the view, the mask and the blurred buffer all have the same dimensions.
+        let (original, [amount]) = unsafe {
+            (
+                view.pixel_unchecked(coords).channels(),
+                *mask.pixel_unchecked(coords).channels(),
+            )
+        };
+
+        let amount = f32::from(amount) / 255.0;
+        let blurred = blurred.channels();
+        P::new(std::array::from_fn(|i| {
+            let (original, blurred) = (original[i].to_f32(), blurred[i].to_f32());
+            C::from_f32(original + (blurred - original) * amount)
+        }))
+    });
+
+    Some(result)
+}
+
+/// Performs a box blur with the given radius, in pixels, in a view and returns the result.
+///
+/// Every output pixel is the average of the `(2 * radius + 1)²` pixels around it. Near the edges,
+/// only the pixels inside the view are averaged. This uses running sums, so it's cost per pixel
+/// doesn't depend on the radius.
+#[must_use = "the blurred buffer is returned and the original view is left unmodified"]
+pub fn box_blur_radius<I, P, C, const N: usize>(view: &I, radius: u32) -> ImgBuf<P, Vec<P>>
+where
+    I: Img<Pixel = P>,
+    P: Pixel<Channels = [C; N]>,
+    C: Processable,
+{
+    let (width, height) = view.dimensions();
+    let radius = radius as usize;
+
+    // averages the values in the window around every element of 'line' using prefix sums
+    let blur_line = |line: &[[f64; N]], prefix: &mut Vec<[f64; N]>, out: &mut Vec<[f64; N]>| {
+        prefix.clear();
+        prefix.push([0.0; N]);
+        for value in line {
+            let last = prefix[prefix.len() - 1];
+            prefix.push(std::array::from_fn(|i| last[i] + value[i]));
+        }
+
+        out.clear();
+        for index in 0..line.len() {
+            let start = index.saturating_sub(radius);
+            let end = (index + radius + 1).min(line.len());
+            let count = (end - start) as f64;
+            out.push(std::array::from_fn(|i| {
+                (prefix[end][i] - prefix[start][i]) / count
+            }));
+        }
+    };
+
+    let mut line = Vec::with_capacity(width.max(height) as usize);
+    let mut prefix = Vec::with_capacity(width.max(height) as usize + 1);
+    let mut out = Vec::with_capacity(width.max(height) as usize);
+
+    // horizontal pass
+    let mut horizontal = Vec::with_capacity(checked_size(width, height));
+    for y in 0..height {
+        line.clear();
+        line.extend((0..width).map(|x| {
+            // SAFETY: x and y are within the bounds of the view.
+            let channels = unsafe { view.pixel_unchecked((x, y)) }.channels();
+            channels.map(|c| f64::from(c.to_f32()))
+        }));
+
+        blur_line(&line, &mut prefix, &mut out);
+        horizontal.extend_from_slice(&out);
+    }
+
+    // vertical pass
+    let mut result = Vec::with_capacity(checked_size(width, height));
+    for x in 0..width {
+        line.clear();
+        line.extend((0..height).map(|y| horizontal[index_point((x, y), width)]));
+
+        blur_line(&line, &mut prefix, &mut out);
+        result.extend_from_slice(&out);
+    }
+
+    // 'result' is column-major
+    ImgBuf::from_fn(width, height, |(x, y)| {
+        let channels = result[index_point((y, x), height)];
+        P::new(channels.map(|c| C::from_f32(c as f32)))
+    })
+}
+
+/// Computes the integral image (also known as summed-area table) of a view.
+///
+/// The result has dimensions `(width + 1, height + 1)` and the pixel at `(x, y)` holds the sum of
+/// every channel of the pixels in the rect with top-left `(0, 0)` and dimensions `(x, y)`. This
+/// means the sum over any rect of the view can be computed with four lookups.
+#[must_use = "the integral image is returned and the original view is left unmodified"]
+pub fn integral_image<I, P, C, const N: usize>(view: &I) -> ImgBuf<[f64; N], Vec<[f64; N]>>
+where
+    I: Img<Pixel = P>,
+    P: Pixel<Channels = [C; N]>,
+    C: Processable,
+{
+    let (width, height) = (view.width() + 1, view.height() + 1);
+    let mut table =
+        ImgBuf::from_container(vec![[0f64; N]; checked_size(width, height)], width, height);
+    let data = table.as_mut_pixel_slice();
+
+    for y in 1..height {
+        let mut row_sum = [0f64; N];
+        for x in 1..width {
+            // SAFETY: x - 1 and y - 1 are within the bounds of the view.
+            let channels = unsafe { view.pixel_unchecked((x - 1, y - 1)) }.channels();
+            let above = data[index_point((x, y - 1), width)];
+            for (sum, channel) in row_sum.iter_mut().zip(channels) {
+                *sum += f64::from(channel.to_f32());
+            }
+
+            data[index_point((x, y), width)] = std::array::from_fn(|i| above[i] + row_sum[i]);
+        }
+    }
+
+    table
+}
+
+/// Performs a box blur with the given radius, in pixels, in a view and returns the result.
+///
+/// This has the same result as [`box_blur_radius`], but computes each output pixel with four
+/// lookups into the [`integral_image`] of the view.
+#[must_use = "the blurred buffer is returned and the original view is left unmodified"]
+pub fn box_blur_integral<I, P, C, const N: usize>(view: &I, radius: u32) -> ImgBuf<P, Vec<P>>
+where
+    I: Img<Pixel = P>,
+    P: Pixel<Channels = [C; N]>,
+    C: Processable,
+{
+    let table = integral_image(view);
+    let table_width = table.width();
+    let data = table.as_pixel_slice();
+
+    ImgBuf::from_fn(view.width(), view.height(), |(x, y)| {
+        let (left, top) = (x.saturating_sub(radius), y.saturating_sub(radius));
+        let right = x.saturating_add(radius).min(view.width() - 1) + 1;
+        let bottom = y.saturating_add(radius).min(view.height() - 1) + 1;
+        let area = f64::from(right - left) * f64::from(bottom - top);
+
+        let at = |x, y| data[index_point((x, y), table_width)];
+        let (a, b, c, d) = (
+            at(left, top),
+            at(right, top),
+            at(left, bottom),
+            at(right, bottom),
+        );
+
+        P::new(std::array::from_fn(|i| {
+            C::from_f32(((d[i] - b[i] - c[i] + a[i]) / area) as f32)
+        }))
+    })
+}
+
+/// Performs a gaussian blur with the given radius, in pixels, in a view and returns the result.
+///
+/// The standard deviation (sigma) of the gaussian is `radius / 3`, so that almost all of the
+/// kernel's weight is inside the radius. The kernel is cut off at the radius.
+#[must_use = "the blurred buffer is returned and the original view is left unmodified"]
+pub fn gaussian_blur_radius<I, P, C, const N: usize>(view: &I, radius: u32) -> ImgBuf<P, Vec<P>>
+where
+    I: LazyImg<Pixel = P>,
+    P: Pixel<Channels = [C; N]>,
+    C: Processable,
+{
+    if radius == 0 {
+        return view.lazy_to_buffer();
+    }
+
+    let sigma = radius as f32 / 3.0;
+    resample(
+        view,
+        view.lazy_dimensions(),
+        |x| filters::gaussian(x, sigma),
+        radius as f32,
+    )
+}
+
+/// Performs a motion blur in a view and returns the result.
+///
+/// Every output pixel is the average of `length` samples, one pixel apart, along a line centered
+/// on it. `angle` is the direction of the line in radians, counterclockwise from the positive x
+/// axis (i.e. as seen on screen, where y grows downwards). Samples are bilinearly interpolated and
+/// samples outside of the view are clamped to it's edges.
+#[must_use = "the blurred buffer is returned and the original view is left unmodified"]
+pub fn motion_blur<I, P, C, const N: usize>(view: &I, length: u32, angle: f32) -> ImgBuf<P, Vec<P>>
+where
+    I: Img<Pixel = P>,
+    P: Pixel<Channels = [C; N]>,
+    C: Processable,
+{
+    let samples = length.max(1);
+    let (sin, cos) = angle.sin_cos();
+    let offsets: Vec<(f32, f32)> = (0..samples)
+        .map(|i| {
+            let t = i as f32 - (samples - 1) as f32 / 2.0;
+            (t * cos, -t * sin)
+        })
+        .collect();
+
+    ImgBuf::from_fn(view.width(), view.height(), |(x, y)| {
+        let mut channel_value_sum = [0f32; N];
+        for &(offset_x, offset_y) in &offsets {
+            let (src_x, src_y) = (x as f32 + offset_x, y as f32 + offset_y);
+            let (base_x, base_y) = (src_x.floor(), src_y.floor());
+            let (fraction_x, fraction_y) = (src_x - base_x, src_y - base_y);
+
+            for (kx, ky, weight) in [
+                (0, 0, (1.0 - fraction_x) * (1.0 - fraction_y)),
+                (1, 0, fraction_x * (1.0 - fraction_y)),
+                (0, 1, (1.0 - fraction_x) * fraction_y),
+                (1, 1, fraction_x * fraction_y),
+            ] {
+                let src = (base_x as i64 + kx, base_y as i64 + ky);
+                let src = EdgeMode::Clamp
+                    .resolve_point(src, view.dimensions())
+                    .expect("clamping always resolves within a non-empty view");
+
+                // SAFETY: resolved points are always within the bounds of the view.
+                let src_pixel = unsafe { view.pixel_unchecked(src) };
+                for (sum, channel) in channel_value_sum
+                    .iter_mut()
+                    .zip(src_pixel.channels().iter())
+                {
+                    *sum += weight * channel.to_f32();
+                }
+            }
+        }
+
+        P::new(channel_value_sum.map(|v| C::from_f32(v / samples as f32)))
+    })
+}
+
 /// Filter type to use when resizing a view using the [`resize`] function.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ResizeFilter {
+    /// Copies the nearest source pixel without any blending, which keeps hard edges (e.g. for
+    /// pixel art and masks). See [`resize_nearest`].
+    Nearest,
     Box,
     Triangle,
     BSpline,
@@ -322,6 +795,25 @@ pub enum ResizeFilter {
     Lanczos3,
 }
 
+impl ResizeFilter {
+    /// Returns the kernel function of this filter and it's support (i.e. the distance after which
+    /// the kernel is zero). The box kernel is constant, so it's support depends on the use:
+    /// [`resample`] scales the window by the sampling ratio and expects `0.0`, while sampling at a
+    /// 1:1 scale needs `0.5`. [`ResizeFilter::Nearest`] is better served by [`resize_nearest`].
+    fn kernel(self, box_support: f32) -> (fn(f32) -> f32, f32) {
+        match self {
+            ResizeFilter::Nearest => (filters::nearest, 0.5),
+            ResizeFilter::Box => (filters::box_filter, box_support),
+            ResizeFilter::Triangle => (filters::triangle, 1.0),
+            ResizeFilter::BSpline => (filters::b_spline, 2.0),
+            ResizeFilter::Mitchell => (filters::mitchell, 2.0),
+            ResizeFilter::CatmullRom => (filters::catmull_rom, 2.0),
+            ResizeFilter::Lanczos2 => (filters::lanczos2, 2.0),
+            ResizeFilter::Lanczos3 => (filters::lanczos3, 3.0),
+        }
+    }
+}
+
 /// Resizes a view to the given dimensions using the given resizing filter.
 #[must_use = "the resized buffer is returned and the original view is left unmodified"]
 pub fn resize<I, P, C, const N: usize>(
@@ -330,27 +822,393 @@ pub fn resize<I, P, C, const N: usize>(
     filter: ResizeFilter,
 ) -> ImgBuf<P, Vec<P>>
 where
-    I: Img<Pixel = P>,
+    I: LazyImg<Pixel = P>,
     P: Pixel<Channels = [C; N]>,
     C: Processable,
 {
-    match filter {
-        ResizeFilter::Box => resample(view, dimensions, filters::box_filter, 0.0),
-        ResizeFilter::Triangle => resample(view, dimensions, filters::triangle, 1.0),
-        ResizeFilter::BSpline => resample(view, dimensions, filters::b_spline, 2.0),
-        ResizeFilter::Mitchell => resample(view, dimensions, filters::mitchell, 2.0),
-        ResizeFilter::CatmullRom => resample(view, dimensions, filters::catmull_rom, 2.0),
-        ResizeFilter::Lanczos2 => resample(view, dimensions, filters::lanczos2, 2.0),
-        ResizeFilter::Lanczos3 => resample(view, dimensions, filters::lanczos3, 3.0),
+    if filter == ResizeFilter::Nearest {
+        return resize_nearest(view, dimensions);
     }
+
+    let (filter, window) = filter.kernel(0.0);
+    resample(view, dimensions, filter, window)
 }
 
-/// Flips the given view horizontally.
-pub fn flip_horizontal<I>(view: &mut I)
+/// Parallel version of [`resize`].
+#[cfg(feature = "rayon")]
+#[must_use = "the resized buffer is returned and the original view is left unmodified"]
+pub fn resize_par<I, P, C, const N: usize>(
+    view: &I,
+    dimensions: (u32, u32),
+    filter: ResizeFilter,
+) -> ImgBuf<P, Vec<P>>
 where
-    I: ImgMut,
+    I: LazyImg<Pixel = P> + Sync,
+    P: Pixel<Channels = [C; N]> + Send + Sync,
+    C: Processable,
 {
-    for y in 0..view.height() {
+    if filter == ResizeFilter::Nearest {
+        return resize_nearest(view, dimensions);
+    }
+
+    let (filter, window) = filter.kernel(0.0);
+    resample_par(view, dimensions, filter, window)
+}
+
+/// Resizes a view to the given dimensions using nearest-neighbor sampling: every pixel of the
+/// result is a copy of the source pixel closest to it's center, with no blending. Unlike
+/// [`resize`], this doesn't require the channels to be [`Processable`].
+///
+/// # Panics
+/// Panics if the view is empty but `dimensions` isn't.
+#[must_use = "the resized buffer is returned and the original view is left unmodified"]
+pub fn resize_nearest<I, P>(view: &I, dimensions: (u32, u32)) -> ImgBuf<P, Vec<P>>
+where
+    I: LazyImg<Pixel = P>,
+{
+    let (width, height) = dimensions;
+    let (src_width, src_height) = view.lazy_dimensions();
+    assert!(
+        (src_width != 0 && src_height != 0) || width == 0 || height == 0,
+        "an empty view can only be resized to empty dimensions"
+    );
+
+    // maps the center of a target pixel to the source pixel that contains it
+    let nearest = |coord: u32, len: u32, src_len: u32| {
+        let src = (2 * u64::from(coord) + 1) * u64::from(src_len) / (2 * u64::from(len));
+        src as u32
+    };
+
+    ImgBuf::from_fn(width, height, |(x, y)| {
+        let src = (nearest(x, width, src_width), nearest(y, height, src_height));
+
+        // SAFETY: src is always within the bounds of the view, since coord < len.
+        unsafe { view.lazy_pixel_unchecked(src) }
+    })
+}
+
+/// Downscales an RGBA view into a square icon with the given size, in pixels.
+///
+/// Every output pixel is the area-weighted average of the source pixels it covers, computed with
+/// premultiplied alpha so that the color of fully transparent pixels doesn't bleed into the edges
+/// (which [`resize`] does, causing dark or colored fringes). Views that aren't square keep their
+/// aspect ratio and are centered, with the rest of the icon left transparent.
+#[must_use = "the icon is returned and the original view is left unmodified"]
+pub fn downscale_icon<I>(view: &I, size: u32) -> Rgba8Img
+where
+    I: Img<Pixel = RGBA8>,
+{
+    let (width, height) = view.dimensions();
+    if width == 0 || height == 0 || size == 0 {
+        return ImgBuf::from_fn(size, size, |_| RGBA8::default());
+    }
+
+    // source pixels per icon pixel, and the offset (in source pixels) that centers the view
+    let scale = f64::from(width.max(height)) / f64::from(size);
+    let offset_x = (f64::from(size) * scale - f64::from(width)) / 2.0;
+    let offset_y = (f64::from(size) * scale - f64::from(height)) / 2.0;
+
+    // the source pixels overlapped by the span [start, end), along with their coverage
+    let overlaps = |start: f64, end: f64, len: u32| {
+        let first = start.max(0.0).floor() as u32;
+        let last = (end.min(f64::from(len)).ceil() as u32).max(first);
+        (first..last).map(move |i| {
+            let coverage = end.min(f64::from(i + 1)) - start.max(f64::from(i));
+            (i, coverage.max(0.0))
+        })
+    };
+
+    ImgBuf::from_fn(size, size, |(x, y)| {
+        let (start_x, start_y) = (
+            f64::from(x) * scale - offset_x,
+            f64::from(y) * scale - offset_y,
+        );
+
+        let mut premultiplied = [0f64; 3];
+        let mut alpha = 0.0;
+        for (src_y, coverage_y) in overlaps(start_y, start_y + scale, height) {
+            for (src_x, coverage_x) in overlaps(start_x, start_x + scale, width) {
+                // SAFETY: overlapped pixels are always within the bounds of the view.
+                let pixel = unsafe { view.pixel_unchecked((src_x, src_y)) };
+                let weight = coverage_x * coverage_y * f64::from(pixel.a) / 255.0;
+
+                premultiplied[0] += f64::from(pixel.r) * weight;
+                premultiplied[1] += f64::from(pixel.g) * weight;
+                premultiplied[2] += f64::from(pixel.b) * weight;
+                alpha += weight;
+            }
+        }
+
+        if alpha <= 0.0 {
+            return RGBA8::default();
+        }
+
+        let [r, g, b] = premultiplied.map(|c| (c / alpha).round().clamp(0.0, 255.0) as u8);
+        let a = (alpha / (scale * scale) * 255.0).round().clamp(0.0, 255.0) as u8;
+        RGBA8::new(r, g, b, a)
+    })
+}
+
+/// Resizes a view to the largest dimensions that fit within `dimensions` while keeping it's aspect
+/// ratio, like `thumbnail` from the `image` crate.
+///
+/// This uses the [`Triangle`][ResizeFilter::Triangle] filter, which is fast and good enough for
+/// downscaling, but not the highest quality. Use [`resize_to_fit`] with another filter otherwise.
+#[must_use = "the resized buffer is returned and the original view is left unmodified"]
+pub fn thumbnail<I, P, C, const N: usize>(view: &I, dimensions: (u32, u32)) -> ImgBuf<P, Vec<P>>
+where
+    I: LazyImg<Pixel = P>,
+    P: Pixel<Channels = [C; N]>,
+    C: Processable,
+{
+    resize_to_fit(view, dimensions, ResizeFilter::Triangle)
+}
+
+/// Resizes a view to the largest dimensions that fit within `max` while keeping it's aspect ratio,
+/// using the given resizing filter.
+///
+/// The dimensions of the result are never zero, even if the view is very thin, unless the view is
+/// empty (in which case an empty buffer is returned).
+#[must_use = "the resized buffer is returned and the original view is left unmodified"]
+pub fn resize_to_fit<I, P, C, const N: usize>(
+    view: &I,
+    max: (u32, u32),
+    filter: ResizeFilter,
+) -> ImgBuf<P, Vec<P>>
+where
+    I: LazyImg<Pixel = P>,
+    P: Pixel<Channels = [C; N]>,
+    C: Processable,
+{
+    let (width, height) = view.lazy_dimensions();
+    let (max_width, max_height) = max;
+    if width == 0 || height == 0 {
+        return ImgBuf::from_container(Vec::new(), 0, 0);
+    }
+
+    let ratio =
+        (f64::from(max_width) / f64::from(width)).min(f64::from(max_height) / f64::from(height));
+    let fit = |len: u32, max: u32| ((f64::from(len) * ratio).round() as u32).clamp(1, max.max(1));
+
+    resize(
+        view,
+        (fit(width, max_width), fit(height, max_height)),
+        filter,
+    )
+}
+
+/// Resizes a view to exactly `max` while keeping it's aspect ratio, by scaling it to the smallest
+/// dimensions that cover `max` and then cropping the center of the result, using the given
+/// resizing filter.
+///
+/// The crop happens before resampling, so no intermediate buffer is allocated. If the view is
+/// empty, an empty buffer is returned.
+#[must_use = "the resized buffer is returned and the original view is left unmodified"]
+pub fn resize_to_fill<I, P, C, const N: usize>(
+    view: &I,
+    max: (u32, u32),
+    filter: ResizeFilter,
+) -> ImgBuf<P, Vec<P>>
+where
+    I: Img<Pixel = P>,
+    P: Pixel<Channels = [C; N]> + Clone,
+    C: Processable,
+{
+    let (width, height) = view.dimensions();
+    let (max_width, max_height) = max;
+    if width == 0 || height == 0 {
+        return ImgBuf::from_container(Vec::new(), 0, 0);
+    }
+
+    // the region of the view that, once scaled, exactly covers 'max'
+    let ratio =
+        (f64::from(max_width) / f64::from(width)).max(f64::from(max_height) / f64::from(height));
+    let cover = |len: u32, max: u32| {
+        if ratio == 0.0 {
+            len
+        } else {
+            ((f64::from(max) / ratio).round() as u32).clamp(1, len)
+        }
+    };
+
+    let (crop_width, crop_height) = (cover(width, max_width), cover(height, max_height));
+    let region = Rect::new(
+        ((width - crop_width) / 2, (height - crop_height) / 2),
+        (crop_width, crop_height),
+    );
+
+    resize(
+        &view
+            .view(region)
+            .expect("centered region is within the view"),
+        max,
+        filter,
+    )
+}
+
+/// Crops a region of interest from a view and resizes it to the given dimensions using the given
+/// resizing filter.
+///
+/// This is equivalent to calling [`resize`] with a cropped copy of the view, but samples the region
+/// directly instead of allocating an intermediate buffer. Parts of `roi` that lie outside of the
+/// view are clamped away. If `roi` doesn't overlap the view at all, every pixel of the result is
+/// [`P::default()`][Default::default].
+#[must_use = "the resized buffer is returned and the original view is left unmodified"]
+pub fn crop_resize<I, P, C, const N: usize>(
+    view: &I,
+    roi: Rect,
+    dimensions: (u32, u32),
+    filter: ResizeFilter,
+) -> ImgBuf<P, Vec<P>>
+where
+    I: Img<Pixel = P>,
+    P: Pixel<Channels = [C; N]> + Clone + Default,
+    C: Processable,
+{
+    let Some(clamped) = roi.intersection(&view.bounds()) else {
+        return ImgBuf::from_fn(dimensions.0, dimensions.1, |_| P::default());
+    };
+
+    let region = view.view(clamped).expect("clamped roi is within the view");
+    resize(&region, dimensions, filter)
+}
+
+/// Computes a perceptual hash (average hash) of a view, meant for finding similar images.
+///
+/// The view is converted to grayscale by averaging it's color channels (alpha is ignored) and
+/// downscaled to 8x8 by averaging the area covered by each cell, so that noise and dithering don't
+/// change the hash much. Each bit of the hash, in row-major order starting from the most
+/// significant one, is set if the corresponding cell is brighter than the mean. Similar images have
+/// hashes with a small hamming distance, i.e. `(a ^ b).count_ones()`. Empty views hash to `0`.
+///
+/// See [`ImgBuf::content_hash`] for finding exact duplicates instead.
+pub fn phash<I, P, C, const N: usize>(view: &I) -> u64
+where
+    I: LazyImg<Pixel = P>,
+    P: Pixel<Channels = [C; N]>,
+    C: Bounded,
+{
+    const CELLS: u32 = 8;
+
+    let (width, height) = view.lazy_dimensions();
+    if width == 0 || height == 0 {
+        return 0;
+    }
+
+    let gray = |pixel: P| {
+        let (sum, count) = pixel
+            .channels()
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| P::ALPHA_CHANNEL != Some(*i))
+            .fold((0.0, 0.0), |(sum, count), (_, c)| {
+                (sum + f64::from(c.to_normalized_f32()), count + 1.0)
+            });
+
+        if count == 0.0 {
+            0.0
+        } else {
+            sum / count
+        }
+    };
+
+    // the source pixels overlapped by the span [start, end), along with their coverage
+    let overlaps = |start: f64, end: f64, len: u32| {
+        let first = start.floor() as u32;
+        let last = (end.ceil() as u32).min(len).max(first);
+        (first..last).map(move |i| {
+            let coverage = end.min(f64::from(i + 1)) - start.max(f64::from(i));
+            (i, coverage.max(0.0))
+        })
+    };
+
+    let scale_x = f64::from(width) / f64::from(CELLS);
+    let scale_y = f64::from(height) / f64::from(CELLS);
+    let gray: Vec<f64> = (0..CELLS * CELLS)
+        .map(|cell| {
+            let (start_x, start_y) = (
+                f64::from(cell % CELLS) * scale_x,
+                f64::from(cell / CELLS) * scale_y,
+            );
+
+            let mut sum = 0.0;
+            for (src_y, coverage_y) in overlaps(start_y, start_y + scale_y, height) {
+                for (src_x, coverage_x) in overlaps(start_x, start_x + scale_x, width) {
+                    // SAFETY: overlapped pixels are always within the bounds of the view.
+                    let pixel = unsafe { view.lazy_pixel_unchecked((src_x, src_y)) };
+                    sum += gray(pixel) * coverage_x * coverage_y;
+                }
+            }
+
+            sum / (scale_x * scale_y)
+        })
+        .collect();
+
+    let mean = gray.iter().sum::<f64>() / gray.len() as f64;
+    gray.iter()
+        .fold(0, |hash, &value| (hash << 1) | u64::from(value > mean))
+}
+
+/// Resizes a view to the largest size, keeping it's aspect ratio, whose encoded form fits in
+/// `max_bytes`. Returns the resized buffer along with it's encoded bytes, or `None` if not even a
+/// one pixel wide version fits.
+///
+/// `encode` is called with every candidate and must return it's encoded bytes. The encoded size is
+/// assumed to grow with the dimensions of the image, which allows a binary search over the width:
+/// `encode` is called at most `log2(width) + 2` times.
+pub fn resize_to_budget<I, P, C, F, const N: usize>(
+    view: &I,
+    max_bytes: usize,
+    filter: ResizeFilter,
+    mut encode: F,
+) -> Option<(ImgBuf<P>, Vec<u8>)>
+where
+    I: LazyImg<Pixel = P>,
+    P: Pixel<Channels = [C; N]>,
+    C: Processable,
+    F: FnMut(&ImgBuf<P>) -> Vec<u8>,
+{
+    let (width, height) = view.lazy_dimensions();
+    if width == 0 || height == 0 {
+        return None;
+    }
+
+    let mut attempt = |target_width: u32| {
+        let ratio = f64::from(target_width) / f64::from(width);
+        let target_height = ((f64::from(height) * ratio).round() as u32).max(1);
+
+        let resized = resize(view, (target_width, target_height), filter);
+        let encoded = encode(&resized);
+        (encoded.len() <= max_bytes).then_some((resized, encoded))
+    };
+
+    if let Some(result) = attempt(width) {
+        return Some(result);
+    }
+
+    // binary search the largest width that fits
+    let mut best = None;
+    let (mut low, mut high) = (1, width - 1);
+    while low <= high {
+        let middle = low + (high - low) / 2;
+        match attempt(middle) {
+            Some(result) => {
+                best = Some(result);
+                low = middle + 1;
+            }
+            None => high = middle - 1,
+        }
+    }
+
+    best
+}
+
+/// Flips the given view horizontally.
+pub fn flip_horizontal<I>(view: &mut I)
+where
+    I: ImgMut,
+{
+    for y in 0..view.height() {
         for x in 0..(view.width() / 2) {
             let left_pixel_bounds = Rect::new((x, y), (1, 1));
             let right_pixel_bounds = Rect::new((view.width() - 1 - x, y), (1, 1));
@@ -388,3 +1246,1667 @@ where
         }
     }
 }
+
+/// Returns a copy of the given view flipped horizontally. See [`flip_horizontal`] to flip a view in
+/// place instead.
+#[must_use = "the flipped buffer is returned and the original view is left unmodified"]
+pub fn flipped_horizontal<I, P>(view: &I) -> ImgBuf<P, Vec<P>>
+where
+    I: Img<Pixel = P>,
+    P: Pixel + Clone,
+{
+    let (width, height) = view.dimensions();
+
+    // SAFETY: (width - 1 - x, y) is within the view for every (x, y) within the result.
+    ImgBuf::from_fn(width, height, |(x, y)| unsafe {
+        view.pixel_unchecked((width - 1 - x, y)).clone()
+    })
+}
+
+/// Returns a copy of the given view flipped vertically. See [`flip_vertical`] to flip a view in
+/// place instead.
+#[must_use = "the flipped buffer is returned and the original view is left unmodified"]
+pub fn flipped_vertical<I, P>(view: &I) -> ImgBuf<P, Vec<P>>
+where
+    I: Img<Pixel = P>,
+    P: Pixel + Clone,
+{
+    let (width, height) = view.dimensions();
+
+    // SAFETY: (x, height - 1 - y) is within the view for every (x, y) within the result.
+    ImgBuf::from_fn(width, height, |(x, y)| unsafe {
+        view.pixel_unchecked((x, height - 1 - y)).clone()
+    })
+}
+
+/// Rotates a view 90 degrees clockwise. The result has the width and height of the view swapped.
+#[must_use = "the rotated buffer is returned and the original view is left unmodified"]
+pub fn rotate90<I, P>(view: &I) -> ImgBuf<P, Vec<P>>
+where
+    I: Img<Pixel = P>,
+    P: Pixel + Clone,
+{
+    let (width, height) = view.dimensions();
+
+    // SAFETY: (y, height - 1 - x) is within the view for every (x, y) within the result.
+    ImgBuf::from_fn(height, width, |(x, y)| unsafe {
+        view.pixel_unchecked((y, height - 1 - x)).clone()
+    })
+}
+
+/// Rotates a view 180 degrees.
+#[must_use = "the rotated buffer is returned and the original view is left unmodified"]
+pub fn rotate180<I, P>(view: &I) -> ImgBuf<P, Vec<P>>
+where
+    I: Img<Pixel = P>,
+    P: Pixel + Clone,
+{
+    let (width, height) = view.dimensions();
+
+    // SAFETY: (width - 1 - x, height - 1 - y) is within the view for every (x, y) within the
+    // result.
+    ImgBuf::from_fn(width, height, |(x, y)| unsafe {
+        view.pixel_unchecked((width - 1 - x, height - 1 - y))
+            .clone()
+    })
+}
+
+/// Rotates a view 270 degrees clockwise (i.e. 90 degrees counterclockwise). The result has the
+/// width and height of the view swapped.
+#[must_use = "the rotated buffer is returned and the original view is left unmodified"]
+pub fn rotate270<I, P>(view: &I) -> ImgBuf<P, Vec<P>>
+where
+    I: Img<Pixel = P>,
+    P: Pixel + Clone,
+{
+    let (width, height) = view.dimensions();
+
+    // SAFETY: (width - 1 - y, x) is within the view for every (x, y) within the result.
+    ImgBuf::from_fn(height, width, |(x, y)| unsafe {
+        view.pixel_unchecked((width - 1 - y, x)).clone()
+    })
+}
+
+/// Transposes a view, turning it's rows into columns. The result has the width and height of the
+/// view swapped. Unlike [`rotate90`], this doesn't mirror the view.
+#[must_use = "the transposed buffer is returned and the original view is left unmodified"]
+pub fn transpose<I, P>(view: &I) -> ImgBuf<P, Vec<P>>
+where
+    I: Img<Pixel = P>,
+    P: Pixel + Clone,
+{
+    let (width, height) = view.dimensions();
+
+    // SAFETY: (y, x) is within the view for every (x, y) within the result.
+    ImgBuf::from_fn(height, width, |(x, y)| unsafe {
+        view.pixel_unchecked((y, x)).clone()
+    })
+}
+
+/// Copies the pixels of a view inside `bounds` into a new, contiguous buffer. If `bounds` doesn't
+/// fit in the view, returns `None`.
+///
+/// Use [`Img::view`] instead if borrowing the region is enough.
+#[must_use = "the cropped buffer is returned and the original view is left unmodified"]
+pub fn crop<I, P>(view: &I, bounds: Rect) -> Option<ImgBuf<P, Vec<P>>>
+where
+    I: Img<Pixel = P>,
+    P: Pixel + Clone,
+{
+    Some(view.view(bounds)?.to_buffer())
+}
+
+/// Crops a region with the given dimensions starting at a fractional top-left point, resampling
+/// the view with the given filter.
+///
+/// This is equivalent to translating the view by a subpixel amount and then cropping it, but
+/// without the precision loss of doing it in separate steps. Parts of the region that lie outside
+/// of the view are sampled according to `edge`.
+#[must_use = "the cropped buffer is returned and the original view is left unmodified"]
+pub fn crop_subpixel<I, P, C, const N: usize>(
+    view: &I,
+    top_left: (f32, f32),
+    dimensions: (u32, u32),
+    filter: ResizeFilter,
+    edge: EdgeMode,
+) -> ImgBuf<P, Vec<P>>
+where
+    I: Img<Pixel = P>,
+    P: Pixel<Channels = [C; N]>,
+    C: Processable,
+{
+    let (kernel, support) = filter.kernel(0.5);
+
+    // every target pixel has the same fractional offset relative to the source grid, so the
+    // weights only need to be calculated once for each axis
+    let taps = |offset: f32| {
+        let base = offset.floor();
+        let fraction = offset - base;
+        let first = (fraction - support).ceil() as i64;
+        let last = (fraction + support).floor() as i64;
+        let weights: Vec<(i64, f32)> = (first..=last)
+            .map(|k| (k, kernel(k as f32 - fraction)))
+            .collect();
+
+        (base as i64, weights)
+    };
+
+    let (base_x, weights_x) = taps(top_left.0);
+    let (base_y, weights_y) = taps(top_left.1);
+    let weight_sum: f32 = weights_x.iter().map(|(_, w)| w).sum::<f32>()
+        * weights_y.iter().map(|(_, w)| w).sum::<f32>();
+
+    ImgBuf::from_fn(dimensions.0, dimensions.1, |(x, y)| {
+        let mut channel_value_sum = [0f32; N];
+        for &(ky, weight_y) in &weights_y {
+            for &(kx, weight_x) in &weights_x {
+                let src = (base_x + i64::from(x) + kx, base_y + i64::from(y) + ky);
+
+                let Some(src) = edge.resolve_point(src, view.dimensions()) else {
+                    continue;
+                };
+
+                // SAFETY: resolved points are always within the bounds of the view.
+                let src_pixel = unsafe { view.pixel_unchecked(src) };
+                let weight = weight_x * weight_y;
+                for (sum, channel) in channel_value_sum
+                    .iter_mut()
+                    .zip(src_pixel.channels().iter())
+                {
+                    *sum += weight * channel.to_f32();
+                }
+            }
+        }
+
+        P::new(channel_value_sum.map(|v| C::from_f32(v / weight_sum)))
+    })
+}
+
+/// Inverts the colors of a view in place, replacing every color channel `c` with
+/// [`c.inverted()`][Bounded::inverted] (e.g. `255 - c` for [`u8`]s). Alpha channels are left
+/// untouched; use [`Pixel::map_channels`] to invert every channel.
+pub fn invert<I, P, C, const N: usize>(view: &mut I)
+where
+    I: ImgMut<Pixel = P>,
+    P: Pixel<Channels = [C; N]>,
+    C: Bounded,
+{
+    for pixel in view.pixels_mut() {
+        for (index, channel) in pixel.channels_mut().iter_mut().enumerate() {
+            if P::ALPHA_CHANNEL != Some(index) {
+                *channel = channel.inverted();
+            }
+        }
+    }
+}
+
+/// Binarizes a view, setting every color channel to [`MAX_VALUE`][Bounded::MAX_VALUE] if it's
+/// normalized value is greater than `level` and to [`MIN_VALUE`][Bounded::MIN_VALUE]
+/// otherwise. Alpha channels are copied from the view unchanged.
+#[must_use = "the binarized buffer is returned and the original view is left unmodified"]
+pub fn threshold<I, P, C, const N: usize>(view: &I, level: f32) -> ImgBuf<P, Vec<P>>
+where
+    I: Img<Pixel = P>,
+    P: Pixel<Channels = [C; N]>,
+    C: Bounded,
+{
+    ImgBuf::from_fn(view.width(), view.height(), |coords| {
+        // SAFETY: coords is within the bounds of the view.
+        let channels = unsafe { view.pixel_unchecked(coords) }.channels();
+        P::new(std::array::from_fn(|i| {
+            if P::ALPHA_CHANNEL == Some(i) {
+                channels[i]
+            } else if channels[i].to_normalized_f32() > level {
+                C::MAX_VALUE
+            } else {
+                C::MIN_VALUE
+            }
+        }))
+    })
+}
+
+/// Binarizes a view based on the [luminance][Luminance::luminance] of it's pixels, turning them
+/// white if it's greater than `level` and black otherwise. Alpha channels are copied from the view
+/// unchanged.
+#[must_use = "the binarized buffer is returned and the original view is left unmodified"]
+pub fn threshold_luminance<I, P, C, const N: usize>(view: &I, level: f32) -> ImgBuf<P, Vec<P>>
+where
+    I: Img<Pixel = P>,
+    P: Pixel<Channels = [C; N]> + Luminance,
+    C: Bounded,
+{
+    ImgBuf::from_fn(view.width(), view.height(), |coords| {
+        // SAFETY: coords is within the bounds of the view.
+        let pixel = unsafe { view.pixel_unchecked(coords) };
+        let value = if pixel.luminance() > level {
+            C::MAX_VALUE
+        } else {
+            C::MIN_VALUE
+        };
+
+        let channels = pixel.channels();
+        P::new(std::array::from_fn(|i| {
+            if P::ALPHA_CHANNEL == Some(i) {
+                channels[i]
+            } else {
+                value
+            }
+        }))
+    })
+}
+
+/// Maps every color channel of the pixels in a view through a 256-entry lookup table. Alpha
+/// channels are left untouched.
+///
+/// This is the primitive behind arbitrary tone curves: build the table once (e.g. from a spline)
+/// and then apply it to the whole view.
+pub fn apply_lut<I, P, const N: usize>(view: &mut I, lut: &[u8; 256])
+where
+    I: ImgMut<Pixel = P>,
+    P: Pixel<Channels = [u8; N]>,
+{
+    for pixel in view.pixels_mut() {
+        for (index, channel) in pixel.channels_mut().iter_mut().enumerate() {
+            if P::ALPHA_CHANNEL != Some(index) {
+                *channel = lut[usize::from(*channel)];
+            }
+        }
+    }
+}
+
+/// Maps every color channel of the pixels in a view through a 65536-entry lookup table. Alpha
+/// channels are left untouched.
+///
+/// This is the 16-bit equivalent of [`apply_lut`].
+pub fn apply_lut16<I, P, const N: usize>(view: &mut I, lut: &[u16; 65536])
+where
+    I: ImgMut<Pixel = P>,
+    P: Pixel<Channels = [u16; N]>,
+{
+    for pixel in view.pixels_mut() {
+        for (index, channel) in pixel.channels_mut().iter_mut().enumerate() {
+            if P::ALPHA_CHANNEL != Some(index) {
+                *channel = lut[usize::from(*channel)];
+            }
+        }
+    }
+}
+
+/// Applies a sepia tone to the pixels in a view. The channels are assumed to be in RGB order.
+///
+/// Only pixels with three channels are accepted, so grayscale views and views with an alpha
+/// channel are rejected at compile time.
+pub fn sepia<I, P>(view: &mut I)
+where
+    I: ImgMut<Pixel = P>,
+    P: ThreeChannel,
+    P::Channel: Bounded,
+{
+    for pixel in view.pixels_mut() {
+        let [r, g, b] = pixel.channels().map(Bounded::to_normalized_f32);
+        *pixel.channels_mut() = [
+            0.393 * r + 0.769 * g + 0.189 * b,
+            0.349 * r + 0.686 * g + 0.168 * b,
+            0.272 * r + 0.534 * g + 0.131 * b,
+        ]
+        .map(|v| Bounded::from_normalized_f32(v.min(1.0)));
+    }
+}
+
+/// Applies contrast-limited adaptive histogram equalization (CLAHE) to a view.
+///
+/// The view is divided into a grid of `tiles` (columns, rows) and each tile is equalized on it's
+/// own, using a histogram with 256 bins per channel. Histogram bins are clipped at `clip_limit`
+/// times the average bin count before equalizing, with the excess redistributed across all bins,
+/// which limits how much noise gets amplified in flat areas: `1.0` disables equalization entirely
+/// and bigger values approach plain histogram equalization. Values between `2.0` and `4.0` are a
+/// common choice. To avoid visible seams, every pixel is mapped through the transforms of the four
+/// nearest tiles and the results are bilinearly interpolated.
+///
+/// Each color channel is equalized independently and alpha channels are left untouched. For
+/// color images, equalizing only the lightness (e.g. the `l` channel of
+/// [`HSL`][crate::pixel::common::HSL]) preserves hues better.
+///
+/// # Panics
+/// Panics if any of the tile counts is zero.
+pub fn clahe<I, P, C, const N: usize>(view: &mut I, tiles: (u32, u32), clip_limit: f32)
+where
+    I: ImgMut<Pixel = P>,
+    P: Pixel<Channels = [C; N]>,
+    C: Bounded,
+{
+    const BINS: usize = 256;
+    let bin = |channel: C| {
+        (channel.to_normalized_f32().clamp(0.0, 1.0) * (BINS - 1) as f32).round() as usize
+    };
+
+    assert!(tiles.0 != 0 && tiles.1 != 0, "tile counts must be non-zero");
+    let (width, height) = view.dimensions();
+    if width == 0 || height == 0 {
+        return;
+    }
+
+    // there can't be more tiles than pixels
+    let (columns, rows) = (tiles.0.min(width), tiles.1.min(height));
+    let tile_start = |index: u32, tiles: u32, len: u32| {
+        (u64::from(index) * u64::from(len) / u64::from(tiles)) as u32
+    };
+
+    // build the clipped, equalized transform of every channel of every tile
+    let mut luts = vec![[[0f32; BINS]; N]; (columns * rows) as usize];
+    for (tile_index, lut) in luts.iter_mut().enumerate() {
+        let (column, row) = (tile_index as u32 % columns, tile_index as u32 / columns);
+        let (x0, x1) = (
+            tile_start(column, columns, width),
+            tile_start(column + 1, columns, width),
+        );
+        let (y0, y1) = (
+            tile_start(row, rows, height),
+            tile_start(row + 1, rows, height),
+        );
+
+        let mut histograms = [[0u32; BINS]; N];
+        for y in y0..y1 {
+            for x in x0..x1 {
+                // SAFETY: tiles are always within the bounds of the view.
+                let pixel = unsafe { view.pixel_unchecked((x, y)) };
+                for (histogram, channel) in histograms.iter_mut().zip(pixel.channels()) {
+                    histogram[bin(*channel)] += 1;
+                }
+            }
+        }
+
+        let total = (x1 - x0) * (y1 - y0);
+        let limit = ((clip_limit * total as f32 / BINS as f32) as u32).max(1);
+        for (histogram, lut) in histograms.iter_mut().zip(lut.iter_mut()) {
+            let mut excess = 0;
+            for count in histogram.iter_mut() {
+                excess += count.saturating_sub(limit);
+                *count = (*count).min(limit);
+            }
+
+            // the remainder is spread evenly too, so it doesn't bias the lower bins
+            let (share, remainder) = (excess / BINS as u32, excess as usize % BINS);
+            let mut cdf = 0;
+            for (index, (count, value)) in histogram.iter().zip(lut.iter_mut()).enumerate() {
+                let extra = (index + 1) * remainder / BINS - index * remainder / BINS;
+                cdf += count + share + extra as u32;
+                *value = cdf as f32 / total as f32;
+            }
+        }
+    }
+
+    // position of a pixel in tile space, where tile centers are at integer coordinates
+    let tile_coords = |coord: u32, tiles: u32, len: u32| {
+        let position = (coord as f32 + 0.5) * tiles as f32 / len as f32 - 0.5;
+        let first = (position.floor().max(0.0) as u32).min(tiles - 1);
+        let second = (first + 1).min(tiles - 1);
+        let weight = (position - first as f32).clamp(0.0, 1.0);
+        (first, second, weight)
+    };
+
+    for (index, pixel) in view.pixels_mut().enumerate() {
+        let (x, y) = (
+            (index % width as usize) as u32,
+            (index / width as usize) as u32,
+        );
+        let (column_a, column_b, weight_x) = tile_coords(x, columns, width);
+        let (row_a, row_b, weight_y) = tile_coords(y, rows, height);
+        let lut_at = |column: u32, row: u32| &luts[(row * columns + column) as usize];
+        let corners = [
+            lut_at(column_a, row_a),
+            lut_at(column_b, row_a),
+            lut_at(column_a, row_b),
+            lut_at(column_b, row_b),
+        ];
+
+        for (channel_index, channel) in pixel.channels_mut().iter_mut().enumerate() {
+            if P::ALPHA_CHANNEL == Some(channel_index) {
+                continue;
+            }
+
+            let bin = bin(*channel);
+            let [a, b, c, d] = corners.map(|lut| lut[channel_index][bin]);
+            let top = a + (b - a) * weight_x;
+            let bottom = c + (d - c) * weight_x;
+            *channel = C::from_normalized_f32(top + (bottom - top) * weight_y);
+        }
+    }
+}
+
+/// Returns the per-channel average of the pixels in a view. Values are accumulated in [`f64`]s
+/// to avoid precision loss on large views.
+///
+/// Returns [`None`] if the view is empty.
+pub fn mean_color<I, P, C, const N: usize>(view: &I) -> Option<[f32; N]>
+where
+    I: Img<Pixel = P>,
+    P: Pixel<Channels = [C; N]>,
+    C: Processable,
+{
+    if view.size() == 0 {
+        return None;
+    }
+
+    let mut sums = [0f64; N];
+    for pixel in view.pixels() {
+        for (sum, channel) in sums.iter_mut().zip(pixel.channels().iter()) {
+            *sum += f64::from(channel.to_f32());
+        }
+    }
+
+    let count = view.size() as f64;
+    Some(sums.map(|sum| (sum / count) as f32))
+}
+
+/// Returns the per-channel average of the pixels of a view within the given bounds. See
+/// [`mean_color`].
+///
+/// Returns [`None`] if the bounds are empty or don't fit in the view.
+pub fn mean_color_in<I, P, C, const N: usize>(view: &I, bounds: Rect) -> Option<[f32; N]>
+where
+    I: Img<Pixel = P>,
+    P: Pixel<Channels = [C; N]>,
+    C: Processable,
+{
+    view.view(bounds).and_then(|view| mean_color(&view))
+}
+
+/// Per-channel statistics of a view, as returned by [`channel_stats`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChannelStats<const N: usize> {
+    /// The minimum value of each channel.
+    pub min: [f32; N],
+    /// The maximum value of each channel.
+    pub max: [f32; N],
+    /// The mean value of each channel.
+    pub mean: [f32; N],
+    /// The (population) standard deviation of each channel.
+    pub std_dev: [f32; N],
+}
+
+impl<const N: usize> ChannelStats<N> {
+    /// Returns the (population) variance of each channel.
+    #[inline]
+    pub fn variance(&self) -> [f32; N] {
+        self.std_dev.map(|std_dev| std_dev * std_dev)
+    }
+}
+
+/// Computes the per-channel minimum, maximum, mean and standard deviation of the pixels in a view
+/// in a single pass, using Welford's algorithm for numerical stability.
+///
+/// Returns [`None`] if the view is empty.
+pub fn channel_stats<I, P, C, const N: usize>(view: &I) -> Option<ChannelStats<N>>
+where
+    I: Img<Pixel = P>,
+    P: Pixel<Channels = [C; N]>,
+    C: Processable,
+{
+    if view.size() == 0 {
+        return None;
+    }
+
+    let mut min = [f32::INFINITY; N];
+    let mut max = [f32::NEG_INFINITY; N];
+    let mut mean = [0f64; N];
+    let mut m2 = [0f64; N];
+
+    for (index, pixel) in view.pixels().enumerate() {
+        let count = (index + 1) as f64;
+        for (channel_index, channel) in pixel.channels().iter().enumerate() {
+            let value = channel.to_f32();
+            min[channel_index] = min[channel_index].min(value);
+            max[channel_index] = max[channel_index].max(value);
+
+            let value = f64::from(value);
+            let delta = value - mean[channel_index];
+            mean[channel_index] += delta / count;
+            m2[channel_index] += delta * (value - mean[channel_index]);
+        }
+    }
+
+    let count = view.size() as f64;
+    Some(ChannelStats {
+        min,
+        max,
+        mean: mean.map(|mean| mean as f32),
+        std_dev: m2.map(|m2| (m2 / count).sqrt() as f32),
+    })
+}
+
+/// How to treat coordinates that fall outside of a view, e.g. when convolving or padding.
+///
+/// Given the row `a b c d`, the modes extend it as follows:
+/// ```text
+/// Clamp:            a a a | a b c d | d d d
+/// Wrap:             b c d | a b c d | a b c
+/// Zero:             0 0 0 | a b c d | 0 0 0
+/// Reflect:          d c b | a b c d | c b a
+/// ReflectReplicate: c b a | a b c d | d c b
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EdgeMode {
+    /// Repeats the pixel at the edge.
+    Clamp,
+    /// Wraps around to the opposite edge.
+    Wrap,
+    /// Treats out of bounds pixels as zero.
+    Zero,
+    /// Mirrors the view without repeating the pixel at the edge.
+    Reflect,
+    /// Mirrors the view, repeating the pixel at the edge.
+    ReflectReplicate,
+}
+
+impl EdgeMode {
+    /// Maps a (possibly out of bounds) coordinate along an axis with length `len` to a coordinate
+    /// within `0..len`. Returns [`None`] if the coordinate maps to no pixel, which happens for
+    /// [`EdgeMode::Zero`] or if `len` is zero.
+    pub fn resolve(self, coord: i64, len: u32) -> Option<u32> {
+        if len == 0 {
+            return None;
+        }
+
+        let len = i64::from(len);
+        let resolved = match self {
+            EdgeMode::Clamp => coord.clamp(0, len - 1),
+            EdgeMode::Wrap => coord.rem_euclid(len),
+            EdgeMode::Zero => return (0..len).contains(&coord).then_some(coord as u32),
+            EdgeMode::Reflect => {
+                if len == 1 {
+                    0
+                } else {
+                    let period = 2 * (len - 1);
+                    let m = coord.rem_euclid(period);
+                    if m < len {
+                        m
+                    } else {
+                        period - m
+                    }
+                }
+            }
+            EdgeMode::ReflectReplicate => {
+                let period = 2 * len;
+                let m = coord.rem_euclid(period);
+                if m < len {
+                    m
+                } else {
+                    period - 1 - m
+                }
+            }
+        };
+
+        Some(resolved as u32)
+    }
+
+    /// Maps a (possibly out of bounds) point to a point within a view with the given dimensions.
+    /// See [`EdgeMode::resolve`].
+    #[inline]
+    pub fn resolve_point(self, (x, y): (i64, i64), (width, height): (u32, u32)) -> Option<Point> {
+        self.resolve(x, width)
+            .and_then(|x| self.resolve(y, height).map(|y| (x, y)))
+    }
+}
+
+/// Convolves a view with an arbitrary kernel and returns the result.
+///
+/// The kernel is in row-major order and has `kernel_width` columns and
+/// `kernel.len() / kernel_width` rows. It's centered on each pixel, with the center at
+/// `(kernel_width / 2, kernel_height / 2)`, and is applied as-is (i.e. it isn't flipped), like
+/// [`filters3x3::apply_kernel_3x3`]. Neighbours outside of the view are handled according to
+/// `edge`.
+///
+/// Every channel, including alpha, is convolved, so that e.g. a blur also softens the edges of
+/// transparent areas. Kernels that don't sum to `1.0` will change the alpha of opaque pixels too:
+/// use [`filters3x3::apply_kernel_3x3`] for those, which keeps alpha unchanged.
+///
+/// # Panics
+/// Panics if `kernel_width` is zero or if the length of the kernel isn't a multiple of it.
+#[must_use = "the convolved buffer is returned and the original view is left unmodified"]
+pub fn convolve<I, P, C, const N: usize>(
+    view: &I,
+    kernel: &[f32],
+    kernel_width: u32,
+    edge: EdgeMode,
+) -> ImgBuf<P, Vec<P>>
+where
+    I: Img<Pixel = P>,
+    P: Pixel<Channels = [C; N]>,
+    C: Processable,
+{
+    assert!(kernel_width != 0, "kernel width must be non-zero");
+    assert!(
+        kernel.len() % kernel_width as usize == 0,
+        "kernel length must be a multiple of it's width"
+    );
+
+    convolve_with(view, kernel, kernel_width, 0.0, false, edge)
+}
+
+/// Convolves a view with a kernel, like [`convolve`], adding `bias` to every convolved channel.
+/// If `preserve_alpha` is set, alpha channels are copied from the view instead of being
+/// convolved.
+///
+/// `kernel_width` must be non-zero and the length of the kernel must be a multiple of it.
+fn convolve_with<I, P, C, const N: usize>(
+    view: &I,
+    kernel: &[f32],
+    kernel_width: u32,
+    bias: f32,
+    preserve_alpha: bool,
+    edge: EdgeMode,
+) -> ImgBuf<P, Vec<P>>
+where
+    I: Img<Pixel = P>,
+    P: Pixel<Channels = [C; N]>,
+    C: Processable,
+{
+    let kernel_width = i64::from(kernel_width);
+    let kernel_height = kernel.len() as i64 / kernel_width;
+    let (center_x, center_y) = (kernel_width / 2, kernel_height / 2);
+
+    ImgBuf::from_fn(view.width(), view.height(), |(x, y)| {
+        let mut channel_value_sum = [0f32; N];
+        for (index, weight) in (0..).zip(kernel) {
+            let src = (
+                i64::from(x) + index % kernel_width - center_x,
+                i64::from(y) + index / kernel_width - center_y,
+            );
+
+            let Some(src) = edge.resolve_point(src, view.dimensions()) else {
+                continue;
+            };
+
+            // SAFETY: resolved points are always within the bounds of the view.
+            let src_pixel = unsafe { view.pixel_unchecked(src) };
+            for (sum, channel) in channel_value_sum
+                .iter_mut()
+                .zip(src_pixel.channels().iter())
+            {
+                *sum += weight * channel.to_f32();
+            }
+        }
+
+        // SAFETY: (x, y) is within the bounds of the view.
+        let original = unsafe { view.pixel_unchecked((x, y)) }.channels();
+        P::new(std::array::from_fn(|i| {
+            if preserve_alpha && P::ALPHA_CHANNEL == Some(i) {
+                original[i]
+            } else {
+                C::from_f32(channel_value_sum[i] + bias)
+            }
+        }))
+    })
+}
+
+/// Pads a view with `padding.0` pixels on the left and right and `padding.1` pixels on the top
+/// and bottom, filling the new pixels according to the given [`EdgeMode`].
+///
+/// Pixels that map to nothing (i.e. with [`EdgeMode::Zero`]) are set to `P::default()`.
+#[must_use = "the padded buffer is returned and the original view is left unmodified"]
+pub fn pad<I, P>(view: &I, padding: (u32, u32), edge: EdgeMode) -> ImgBuf<P, Vec<P>>
+where
+    I: Img<Pixel = P>,
+    P: Pixel + Clone + Default,
+{
+    let (pad_x, pad_y) = padding;
+    let width = view
+        .width()
+        .checked_add(pad_x.checked_mul(2).expect("padding should fit within u32"))
+        .expect("padded width should fit within u32");
+    let height = view
+        .height()
+        .checked_add(pad_y.checked_mul(2).expect("padding should fit within u32"))
+        .expect("padded height should fit within u32");
+
+    ImgBuf::from_fn(width, height, |(x, y)| {
+        let src = (
+            i64::from(x) - i64::from(pad_x),
+            i64::from(y) - i64::from(pad_y),
+        );
+
+        edge.resolve_point(src, view.dimensions())
+            // SAFETY: resolved points are always within the bounds of the view.
+            .map_or_else(P::default, |p| unsafe { view.pixel_unchecked(p).clone() })
+    })
+}
+
+/// Flattens a view with alpha onto a checkerboard of `cell` by `cell` squares alternating between
+/// `light` and `dark`, starting with `light` at the top-left corner. This is how image viewers
+/// usually display transparent images.
+///
+/// # Panics
+/// Panics if `cell` is zero.
+#[must_use = "the flattened buffer is returned and the original view is left unmodified"]
+pub fn composite_on_checkerboard<I, C>(view: &I, cell: u32, light: RGB8, dark: RGB8) -> Rgb8Img
+where
+    I: Img<Pixel = RGBA<C>>,
+    C: Bounded + bytemuck::Pod,
+{
+    assert!(cell != 0, "cell size must be non-zero");
+
+    ImgBuf::from_fn(view.width(), view.height(), |(x, y)| {
+        let backdrop = if (x / cell + y / cell) % 2 == 0 {
+            light
+        } else {
+            dark
+        };
+
+        // SAFETY: (x, y) is within the bounds of the view.
+        let [r, g, b, a] = unsafe { view.pixel_unchecked((x, y)) }
+            .channels()
+            .map(Bounded::to_normalized_f32);
+        let a = a.clamp(0.0, 1.0);
+        let blend = |src: f32, dst: u8| {
+            u8::from_normalized_f32(src * a + dst.to_normalized_f32() * (1.0 - a))
+        };
+
+        RGB8::new(
+            blend(r, backdrop.r),
+            blend(g, backdrop.g),
+            blend(b, backdrop.b),
+        )
+    })
+}
+
+/// Splits a view into disjoint tiles with the given dimensions and runs `f` on each of them in
+/// parallel. Tiles on the right and bottom edges are clipped to the view, so they may be smaller.
+///
+/// `f` only sees the pixels of the tile it's given: there's no overlap (halo) between tiles. This
+/// means operations that depend on neighbouring pixels, such as blurs and convolutions, will
+/// produce visible seams at tile borders and should not be done with this function.
+///
+/// # Panics
+/// Panics if any of the tile dimensions is zero.
+#[cfg(feature = "rayon")]
+pub fn process_tiles_par<I, P, F>(view: &mut I, tile: (u32, u32), f: F)
+where
+    I: TilesMut<Pixel = P>,
+    P: Pixel + Send,
+    F: Fn(&mut ImgBufViewMut<'_, P>) + Sync,
+{
+    use rayon::prelude::*;
+
+    view.tiles_mut(tile)
+        .into_par_iter()
+        .for_each(|mut tile| f(&mut tile));
+}
+
+/// Which pixels of a mask are considered foreground by [`distance_transform`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Foreground {
+    /// Pixels whose color channels are all zero (i.e. [`MIN_VALUE`][Bounded::MIN_VALUE]) are
+    /// foreground.
+    Zero,
+    /// Pixels with any color channel above zero (i.e. [`MIN_VALUE`][Bounded::MIN_VALUE]) are
+    /// foreground.
+    NonZero,
+}
+
+/// Computes the squared euclidean distance transform of a single row or column from `values`
+/// into `output`, using the lower envelope of parabolas described by Felzenszwalb and
+/// Huttenlocher.
+fn distance_transform_1d(
+    values: &[f32],
+    output: &mut [f32],
+    vertices: &mut [usize],
+    boundaries: &mut [f32],
+) {
+    let intersection = |a: usize, b: usize| {
+        let parabola = |index: usize| values[index] + (index * index) as f32;
+        (parabola(b) - parabola(a)) / (2 * (b - a)) as f32
+    };
+
+    let mut count = 0;
+    vertices[0] = 0;
+    boundaries[0] = f32::NEG_INFINITY;
+    boundaries[1] = f32::INFINITY;
+    for index in 1..values.len() {
+        let mut boundary = intersection(vertices[count], index);
+        while boundary <= boundaries[count] {
+            count -= 1;
+            boundary = intersection(vertices[count], index);
+        }
+
+        count += 1;
+        vertices[count] = index;
+        boundaries[count] = boundary;
+        boundaries[count + 1] = f32::INFINITY;
+    }
+
+    count = 0;
+    for (index, value) in output.iter_mut().enumerate() {
+        while boundaries[count + 1] < index as f32 {
+            count += 1;
+        }
+
+        let offset = index.abs_diff(vertices[count]) as f32;
+        *value = offset * offset + values[vertices[count]];
+    }
+}
+
+/// Computes, for every pixel of a mask, the euclidean distance to the nearest foreground pixel.
+/// Foreground pixels have a distance of `0.0`, and the alpha channel of the mask is ignored.
+///
+/// This uses the two-pass algorithm by Felzenszwalb and Huttenlocher, which is exact and linear
+/// on the amount of pixels. If the mask has no foreground pixels, every distance is
+/// [`f32::INFINITY`].
+#[must_use = "the distances are returned and the original view is left unmodified"]
+pub fn distance_transform<I, P, C, const N: usize>(
+    view: &I,
+    foreground: Foreground,
+) -> ImgBuf<[f32; 1], Vec<[f32; 1]>>
+where
+    I: Img<Pixel = P>,
+    P: Pixel<Channels = [C; N]>,
+    C: Bounded,
+{
+    // big enough to never be the nearest, but small enough to not overflow when squared distances
+    // are added to it.
+    const FAR: f32 = 1e20;
+
+    let (width, height) = view.dimensions();
+    let mut distances: Vec<f32> =
+        view.pixels()
+            .map(|pixel| {
+                let nonzero = pixel.channels().iter().enumerate().any(|(index, c)| {
+                    P::ALPHA_CHANNEL != Some(index) && c.to_normalized_f32() > 0.0
+                });
+
+                if nonzero == (foreground == Foreground::NonZero) {
+                    0.0
+                } else {
+                    FAR
+                }
+            })
+            .collect();
+
+    let longest = width.max(height) as usize;
+    let (mut line, mut transformed) = (vec![0f32; longest], vec![0f32; longest]);
+    let mut vertices = vec![0usize; longest];
+    let mut boundaries = vec![0f32; longest + 1];
+
+    // columns first, then rows
+    let (width_usize, height_usize) = (width as usize, height as usize);
+    for x in 0..width_usize {
+        for (y, value) in line[..height_usize].iter_mut().enumerate() {
+            *value = distances[y * width_usize + x];
+        }
+
+        distance_transform_1d(
+            &line[..height_usize],
+            &mut transformed[..height_usize],
+            &mut vertices,
+            &mut boundaries,
+        );
+
+        for (y, value) in transformed[..height_usize].iter().enumerate() {
+            distances[y * width_usize + x] = *value;
+        }
+    }
+
+    if width != 0 {
+        for row in distances.chunks_exact_mut(width_usize) {
+            line[..width_usize].copy_from_slice(row);
+            distance_transform_1d(&line[..width_usize], row, &mut vertices, &mut boundaries);
+        }
+    }
+
+    let distances = distances
+        .into_iter()
+        .map(|d| [if d >= FAR { f32::INFINITY } else { d.sqrt() }])
+        .collect();
+
+    ImgBuf::from_container(distances, width, height)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn right_angle_rotations() {
+        // 0 1 2
+        // 3 4 5
+        let img = ImgBuf::from_container(vec![[0u8], [1], [2], [3], [4], [5]], 3, 2);
+        let values =
+            |img: &ImgBuf<[u8; 1], Vec<[u8; 1]>>| img.pixels().map(|[v]| *v).collect::<Vec<_>>();
+
+        let rotated = rotate90(&img);
+        assert_eq!(rotated.dimensions(), (2, 3));
+        assert_eq!(values(&rotated), [3, 0, 4, 1, 5, 2]);
+        assert_eq!(values(&rotate180(&img)), [5, 4, 3, 2, 1, 0]);
+        assert_eq!(values(&rotate270(&img)), [2, 5, 1, 4, 0, 3]);
+    }
+
+    #[test]
+    fn flipped_copies() {
+        let img = ImgBuf::from_fn(3, 2, |(x, y)| [x as u8, y as u8]);
+
+        let flipped = flipped_horizontal(&img);
+        assert_eq!(flipped.pixel((0, 1)), Some(&[2, 1]));
+        let flipped = flipped_vertical(&img);
+        assert_eq!(flipped.pixel((0, 1)), Some(&[0, 0]));
+
+        let mut in_place = img.clone();
+        flip_horizontal(&mut in_place);
+        assert!(in_place.pixels().eq(flipped_horizontal(&img).pixels()));
+        flip_vertical(&mut in_place);
+        assert!(in_place.pixels().eq(rotate180(&img).pixels()));
+    }
+
+    #[test]
+    fn lazy_flips_match_copies() {
+        let img = ImgBuf::from_fn(5, 3, |(x, y)| [x as u8, y as u8]);
+
+        assert!(img.flip_h().pixels().eq(flipped_horizontal(&img).pixels()));
+        assert!(img.flip_v().pixels().eq(flipped_vertical(&img).pixels()));
+        assert_eq!(img.transpose().dimensions(), (3, 5));
+        assert!(img.transpose().pixels().eq(transpose(&img).pixels()));
+
+        let flipped = img.flip_h();
+        let sub = flipped.view(Rect::new((1, 1), (2, 2))).unwrap();
+        let expected = crop(&flipped_horizontal(&img), Rect::new((1, 1), (2, 2))).unwrap();
+        assert!(sub.pixels().eq(expected.pixels()));
+
+        let resized = resize(&img.transpose(), (3, 5), ResizeFilter::Nearest);
+        assert!(resized.pixels().eq(transpose(&img).pixels()));
+    }
+
+    #[test]
+    fn transpose_swaps_axes() {
+        let img = ImgBuf::from_fn(5, 3, |(x, y)| [x as u8, y as u8]);
+        let transposed = transpose(&img);
+
+        assert_eq!(transposed.dimensions(), (3, 5));
+        assert_eq!(transposed.pixel((2, 4)), Some(&[4, 2]));
+        let identity = transpose(&transposed);
+        assert_eq!(identity.dimensions(), img.dimensions());
+        assert!(identity.pixels().eq(img.pixels()));
+    }
+
+    #[test]
+    fn convolve_kernels() {
+        let img = ImgBuf::from_fn(4, 3, |(x, y)| [(x * 10 + y) as u8]);
+
+        // a shifted identity kernel moves the view by one pixel
+        let shifted = convolve(&img, &[0.0, 0.0, 1.0], 3, EdgeMode::Zero);
+        assert_eq!(shifted.pixel((0, 1)), Some(&[11]));
+        assert_eq!(shifted.pixel((3, 1)), Some(&[0]));
+
+        let kernel = filters3x3::SHARPEN;
+        let sharpened = convolve(&img, &kernel, 3, EdgeMode::Reflect);
+        let expected = filters3x3::apply_kernel_3x3(&img, &kernel, 0.0, EdgeMode::Reflect);
+        assert!(sharpened.pixels().eq(expected.pixels()));
+
+        // 2x1 kernels are centered on their right column
+        let averaged = convolve(&img, &[0.5, 0.5], 2, EdgeMode::Clamp);
+        assert_eq!(averaged.pixel((1, 0)), Some(&[5]));
+        assert_eq!(averaged.pixel((0, 2)), Some(&[2]));
+
+        // convolve treats alpha like any other channel, while apply_kernel_3x3 keeps it
+        let flat = ImgBuf::from_fn(3, 3, |_| crate::pixel::common::RGBA8::new(10, 20, 30, 40));
+        let edges = convolve(&flat, &filters3x3::EDGE_DETECT, 3, EdgeMode::Clamp);
+        let kept =
+            filters3x3::apply_kernel_3x3(&flat, &filters3x3::EDGE_DETECT, 0.0, EdgeMode::Clamp);
+        assert!(edges.pixels().all(|p| p.a == 0));
+        assert!(kept.pixels().all(|p| p.a == 40 && p.r == 0));
+    }
+
+    #[test]
+    fn resize_to_box() {
+        let img = ImgBuf::from_fn(40, 10, |(x, _)| [x as u8]);
+
+        assert_eq!(
+            resize_to_fit(&img, (8, 8), ResizeFilter::Box).dimensions(),
+            (8, 2)
+        );
+        assert_eq!(
+            resize_to_fit(&img, (100, 1), ResizeFilter::Box).dimensions(),
+            (4, 1)
+        );
+
+        // very thin views never get a zero dimension
+        let thin = ImgBuf::from_fn(1000, 1, |_| [0u8]);
+        assert_eq!(
+            resize_to_fit(&thin, (10, 10), ResizeFilter::Box).dimensions(),
+            (10, 1)
+        );
+
+        // 40x10 covers 8x8 when scaled to 32x8, so only the center 10x10 region is kept
+        let filled = resize_to_fill(&img, (8, 8), ResizeFilter::Nearest);
+        assert_eq!(filled.dimensions(), (8, 8));
+        assert_eq!(filled.pixel((0, 0)), Some(&[15]));
+        assert_eq!(filled.pixel((7, 7)), Some(&[24]));
+
+        let empty = ImgBuf::from_fn(0, 3, |_| [0u8]);
+        assert_eq!(
+            resize_to_fill(&empty, (8, 8), ResizeFilter::Box).dimensions(),
+            (0, 0)
+        );
+    }
+
+    #[test]
+    fn nearest_resize() {
+        let img = ImgBuf::from_container(vec![[1u8], [2], [3], [4]], 2, 2);
+
+        let upscaled = resize(&img, (4, 6), ResizeFilter::Nearest);
+        assert!(upscaled
+            .pixels()
+            .map(|[v]| *v)
+            .eq([1, 1, 2, 2, 1, 1, 2, 2, 1, 1, 2, 2, 3, 3, 4, 4, 3, 3, 4, 4, 3, 3, 4, 4]));
+
+        let downscaled = resize_nearest(&upscaled, (2, 1));
+        assert!(downscaled.pixels().map(|[v]| *v).eq([3, 4]));
+
+        // works with pixels that aren't processable
+        let labels = ImgBuf::from_container(vec![['a'], ['b']], 2, 1);
+        let labels = resize_nearest(&labels, (3, 1));
+        assert!(labels.pixels().map(|[c]| *c).eq(['a', 'b', 'b']));
+    }
+
+    #[test]
+    fn threshold_levels() {
+        let img = ImgBuf::from_container(
+            vec![RGBA8::new(10, 200, 128, 50), RGBA8::new(0, 0, 255, 255)],
+            2,
+            1,
+        );
+
+        let binarized = threshold(&img, 0.5);
+        assert_eq!(binarized.pixel((0, 0)), Some(&RGBA8::new(0, 255, 255, 50)));
+
+        // pure blue is dark, so it's turned black
+        let binarized = threshold_luminance(&img, 0.5);
+        assert_eq!(
+            binarized.pixel((0, 0)),
+            Some(&RGBA8::new(255, 255, 255, 50))
+        );
+        assert_eq!(binarized.pixel((1, 0)), Some(&RGBA8::new(0, 0, 0, 255)));
+    }
+
+    #[test]
+    fn invert_colors() {
+        let mut img = ImgBuf::from_fn(2, 1, |(x, _)| RGBA8::new(x as u8, 100, 255, 7));
+        invert(&mut img);
+        assert_eq!(img.pixel((1, 0)), Some(&RGBA8::new(254, 155, 0, 7)));
+
+        let mut img = ImgBuf::from_container(vec![[0.25f32], [1.0]], 2, 1);
+        invert(&mut img);
+        assert!(img.pixels().map(|[v]| *v).eq([0.75, 0.0]));
+
+        assert_eq!(i8::MIN.inverted(), i8::MAX);
+        assert_eq!(0u16.inverted(), u16::MAX);
+    }
+
+    proptest! {
+        #[cfg(not(miri))]
+        #[test]
+        fn rotate90_four_times_is_identity(width in 0u32..12, height in 0u32..12) {
+            let img = ImgBuf::from_fn(width, height, |(x, y)| [x as u8, y as u8]);
+            let rotated = rotate90(&rotate90(&rotate90(&rotate90(&img))));
+
+            prop_assert_eq!(rotated.dimensions(), img.dimensions());
+            prop_assert!(rotated.pixels().eq(img.pixels()));
+            prop_assert!(rotate180(&img).pixels().eq(rotate90(&rotate90(&img)).pixels()));
+            prop_assert!(rotate270(&rotate90(&img)).pixels().eq(img.pixels()));
+        }
+    }
+
+    #[test]
+    fn crop_copies_region() {
+        let img = ImgBuf::from_fn(4, 3, |(x, y)| [x as u8, y as u8]);
+        let cropped = crop(&img, Rect::new((1, 1), (3, 2))).unwrap();
+
+        assert_eq!(cropped.dimensions(), (3, 2));
+        assert_eq!(cropped.pixel((0, 0)), Some(&[1, 1]));
+        assert_eq!(cropped.pixel((2, 1)), Some(&[3, 2]));
+        assert!(crop(&img, Rect::new((2, 0), (3, 1))).is_none());
+    }
+
+    #[test]
+    fn crop_subpixel_box() {
+        let img = ImgBuf::from_fn(4, 3, |(x, y)| [(x * 10 + y * 40) as u8]);
+        let values = |img: &ImgBuf<[u8; 1]>| img.pixels().map(|[v]| *v).collect::<Vec<_>>();
+        let crop_box = |top_left, dimensions, edge| {
+            crop_subpixel(&img, top_left, dimensions, ResizeFilter::Box, edge)
+        };
+
+        // integer offsets are the same as cropping
+        let cropped = crop_box((1.0, 1.0), (3, 2), EdgeMode::Clamp);
+        let expected = crop(&img, Rect::new((1, 1), (3, 2))).unwrap();
+        assert_eq!(cropped.dimensions(), (3, 2));
+        assert!(cropped.pixels().eq(expected.pixels()));
+
+        // half pixel offsets average the two neighbouring pixels
+        let cropped = crop_box((0.5, 0.0), (3, 1), EdgeMode::Clamp);
+        assert_eq!(values(&cropped), [5, 15, 25]);
+        let cropped = crop_box((1.0, 0.5), (1, 2), EdgeMode::Clamp);
+        assert_eq!(values(&cropped), [30, 70]);
+
+        // pixels outside of the view are either the pixel at the edge or zero
+        assert_eq!(
+            values(&crop_box((-1.0, 1.0), (2, 1), EdgeMode::Clamp)),
+            [40, 40]
+        );
+        assert_eq!(
+            values(&crop_box((-1.0, 1.0), (2, 1), EdgeMode::Zero)),
+            [0, 40]
+        );
+        assert_eq!(
+            values(&crop_box((-0.5, 1.0), (1, 1), EdgeMode::Clamp)),
+            [40]
+        );
+        assert_eq!(values(&crop_box((-0.5, 1.0), (1, 1), EdgeMode::Zero)), [20]);
+        assert_eq!(
+            values(&crop_box((3.0, 2.0), (2, 2), EdgeMode::Clamp)),
+            [110, 110, 110, 110]
+        );
+        assert_eq!(
+            values(&crop_box((3.0, 2.0), (2, 2), EdgeMode::Zero)),
+            [110, 0, 0, 0]
+        );
+    }
+
+    #[test]
+    fn channel_stats_known_values() {
+        let values = [2u8, 4, 4, 4, 5, 5, 7, 9];
+        let img = ImgBuf::from_fn(4, 2, |(x, y)| [values[(y * 4 + x) as usize]]);
+
+        let stats = channel_stats(&img).unwrap();
+        assert!((stats.mean[0] - 5.0).abs() < 1e-6);
+        assert!((stats.std_dev[0] - 2.0).abs() < 1e-6);
+        assert!((stats.variance()[0] - 4.0).abs() < 1e-5);
+        assert!((stats.min[0] - 2.0).abs() < f32::EPSILON);
+        assert!((stats.max[0] - 9.0).abs() < f32::EPSILON);
+
+        let empty = ImgBuf::<[u8; 1]>::from_fn(0, 0, |_| [0]);
+        assert!(channel_stats(&empty).is_none());
+    }
+
+    #[test]
+    fn lut_skips_alpha() {
+        let lut: [u8; 256] = std::array::from_fn(|i| 255 - i as u8);
+        let mut img = Rgba8Img::from_fn(2, 1, |(x, _)| RGBA8::new(x as u8, 10, 200, 77));
+        apply_lut(&mut img, &lut);
+        assert_eq!(img.pixel((0, 0)), Some(&RGBA8::new(255, 245, 55, 77)));
+        assert_eq!(img.pixel((1, 0)), Some(&RGBA8::new(254, 245, 55, 77)));
+
+        let lut16: Box<[u16; 65536]> = (0..=u16::MAX)
+            .map(|v| u16::MAX - v)
+            .collect::<Vec<_>>()
+            .into_boxed_slice()
+            .try_into()
+            .unwrap();
+        let mut img = Rgba16Img::from_fn(1, 1, |_| RGBA16::new(0, 1000, 65535, 1234));
+        apply_lut16(&mut img, &lut16);
+        assert_eq!(img.pixel((0, 0)), Some(&RGBA16::new(65535, 64535, 0, 1234)));
+    }
+
+    #[test]
+    fn pad_edge_modes() {
+        let row = |img: &ImgBuf<[u8; 1]>| img.pixels().map(|[v]| *v).collect::<Vec<_>>();
+
+        // padding wider than the view repeats the patterns from the table in the EdgeMode docs
+        let img = ImgBuf::from_container(vec![[1u8], [2], [3]], 3, 1);
+        let padded = |edge| row(&pad(&img, (4, 0), edge));
+        assert_eq!(padded(EdgeMode::Clamp), [1, 1, 1, 1, 1, 2, 3, 3, 3, 3, 3]);
+        assert_eq!(padded(EdgeMode::Wrap), [3, 1, 2, 3, 1, 2, 3, 1, 2, 3, 1]);
+        assert_eq!(padded(EdgeMode::Zero), [0, 0, 0, 0, 1, 2, 3, 0, 0, 0, 0]);
+        assert_eq!(padded(EdgeMode::Reflect), [1, 2, 3, 2, 1, 2, 3, 2, 1, 2, 3]);
+        assert_eq!(
+            padded(EdgeMode::ReflectReplicate),
+            [3, 3, 2, 1, 1, 2, 3, 3, 2, 1, 1]
+        );
+
+        let img = ImgBuf::from_container(vec![[1u8]], 1, 1);
+        let padded = |edge| row(&pad(&img, (3, 0), edge));
+        assert_eq!(padded(EdgeMode::Zero), [0, 0, 0, 1, 0, 0, 0]);
+        for edge in [
+            EdgeMode::Clamp,
+            EdgeMode::Wrap,
+            EdgeMode::Reflect,
+            EdgeMode::ReflectReplicate,
+        ] {
+            assert_eq!(padded(edge), [1; 7]);
+        }
+
+        // vertical padding works the same way
+        let img = ImgBuf::from_container(vec![[1u8], [2], [3]], 1, 3);
+        let padded = pad(&img, (1, 2), EdgeMode::Reflect);
+        assert_eq!(padded.dimensions(), (3, 7));
+        assert!(padded
+            .pixels()
+            .step_by(3)
+            .map(|[v]| *v)
+            .eq([3, 2, 1, 2, 3, 2, 1]));
+    }
+
+    #[test]
+    fn gaussian_blur_radius_basics() {
+        let img = Rgb8Img::from_fn(7, 5, |(x, y)| RGB8::new(x as u8 * 30, y as u8 * 50, 7));
+        let copy = gaussian_blur_radius(&img, 0);
+        assert_eq!(copy.as_pixel_slice(), img.as_pixel_slice());
+
+        // f32 channels, since the resampler truncates when converting back to integers
+        let flat = ImgBuf::from_fn(9, 6, |_| [0.4f32, 0.0, 1.0]);
+        for radius in [1, 3, 10] {
+            let blurred = gaussian_blur_radius(&flat, radius);
+            assert_eq!(blurred.dimensions(), flat.dimensions());
+            assert!(blurred.pixels().all(|p| p
+                .iter()
+                .zip([0.4, 0.0, 1.0])
+                .all(|(a, b)| (a - b).abs() < 1e-5)));
+        }
+    }
+
+    #[test]
+    fn edge_mode_resolve() {
+        let resolve_all = |edge: EdgeMode, len: u32| {
+            (-6..10)
+                .map(|c| edge.resolve(c, len).map_or(-1, i64::from))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            resolve_all(EdgeMode::Reflect, 4),
+            [0, 1, 2, 3, 2, 1, 0, 1, 2, 3, 2, 1, 0, 1, 2, 3]
+        );
+        assert_eq!(
+            resolve_all(EdgeMode::ReflectReplicate, 4),
+            [2, 3, 3, 2, 1, 0, 0, 1, 2, 3, 3, 2, 1, 0, 0, 1]
+        );
+        assert_eq!(resolve_all(EdgeMode::Reflect, 1), [0; 16]);
+        assert_eq!(resolve_all(EdgeMode::ReflectReplicate, 1), [0; 16]);
+        assert_eq!(
+            resolve_all(EdgeMode::Reflect, 2),
+            [0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1]
+        );
+        assert_eq!(
+            resolve_all(EdgeMode::Zero, 4),
+            [-1, -1, -1, -1, -1, -1, 0, 1, 2, 3, -1, -1, -1, -1, -1, -1]
+        );
+        assert_eq!(EdgeMode::Clamp.resolve(0, 0), None);
+    }
+
+    #[test]
+    fn noise_is_deterministic() {
+        use crate::pixel::common::{RGB8, RGBA16};
+        use noise::NoiseKind;
+
+        for kind in [
+            NoiseKind::Uniform,
+            NoiseKind::Gaussian {
+                mean: 0.5,
+                std_dev: 0.2,
+            },
+            NoiseKind::Perlin { scale: 8.0 },
+        ] {
+            let a = ImgBuf::<RGB8>::from_noise(32, 32, 42, kind);
+            let b = ImgBuf::<RGB8>::from_noise(32, 32, 42, kind);
+            let c = ImgBuf::<RGB8>::from_noise(32, 32, 43, kind);
+            assert!(a.pixels().eq(b.pixels()));
+            assert!(!a.pixels().eq(c.pixels()));
+
+            let alpha = ImgBuf::<RGBA16>::from_noise(8, 8, 42, kind);
+            assert!(alpha.pixels().all(|p| p.a == u16::MAX));
+        }
+    }
+
+    #[test]
+    fn box_blur_radius_matches_box_blur() {
+        use crate::pixel::common::RGB8;
+        use noise::NoiseKind;
+
+        let img = ImgBuf::<RGB8>::from_noise(37, 23, 7, NoiseKind::Uniform);
+        for radius in [0, 1, 4] {
+            let fast = box_blur_radius(&img, radius);
+            let slow = if radius == 0 {
+                img.clone()
+            } else {
+                box_blur(&img, radius as f32)
+            };
+
+            for (a, b) in fast.pixels().zip(slow.pixels()) {
+                assert!(a.r.abs_diff(b.r) <= 1 && a.g.abs_diff(b.g) <= 1 && a.b.abs_diff(b.b) <= 1);
+            }
+        }
+    }
+
+    #[test]
+    fn box_blur_integral_matches_radius() {
+        use crate::pixel::common::RGB16;
+        use noise::NoiseKind;
+
+        let img = ImgBuf::<RGB16>::from_noise(29, 41, 3, NoiseKind::Uniform);
+        for radius in [0, 2, 50] {
+            let a = box_blur_radius(&img, radius);
+            let b = box_blur_integral(&img, radius);
+
+            for (a, b) in a.pixels().zip(b.pixels()) {
+                assert!(a.r.abs_diff(b.r) <= 1 && a.g.abs_diff(b.g) <= 1 && a.b.abs_diff(b.b) <= 1);
+            }
+        }
+    }
+
+    #[test]
+    fn motion_blur_direction() {
+        use crate::pixel::common::RGB8;
+
+        // a single vertical line: a horizontal blur spreads it, a vertical one doesn't
+        let img = ImgBuf::from_fn(9, 9, |(x, _)| RGB8::new(if x == 4 { 90 } else { 0 }, 0, 0));
+
+        let horizontal = motion_blur(&img, 3, 0.0);
+        assert_eq!(horizontal.pixel((3, 4)).unwrap().r, 30);
+        assert_eq!(horizontal.pixel((4, 4)).unwrap().r, 30);
+
+        let vertical = motion_blur(&img, 3, std::f32::consts::FRAC_PI_2);
+        assert_eq!(vertical.pixel((3, 4)).unwrap().r, 0);
+        assert_eq!(vertical.pixel((4, 4)).unwrap().r, 90);
+    }
+
+    #[test]
+    fn kernel_3x3_presets() {
+        use crate::pixel::common::RGBA8;
+
+        let flat = ImgBuf::from_fn(5, 4, |_| RGBA8::new(10, 20, 30, 40));
+        let sharpened =
+            filters3x3::apply_kernel_3x3(&flat, &filters3x3::SHARPEN, 0.0, EdgeMode::Clamp);
+        assert!(sharpened.pixels().eq(flat.pixels()));
+
+        let embossed =
+            filters3x3::apply_kernel_3x3(&flat, &filters3x3::EMBOSS, 128.0, EdgeMode::Reflect);
+        assert!(embossed
+            .pixels()
+            .all(|p| *p == RGBA8::new(128, 128, 128, 40)));
+    }
+
+    #[test]
+    fn resize_to_budget_fits() {
+        use crate::pixel::common::RGB8;
+
+        let img = ImgBuf::from_fn(300, 200, |(x, y)| RGB8::new(x as u8, y as u8, 0));
+        let encode = |img: &ImgBuf<RGB8>| vec![0; img.size() * 3];
+
+        let (resized, encoded) =
+            resize_to_budget(&img, 10_000, ResizeFilter::Triangle, encode).unwrap();
+        assert!(encoded.len() <= 10_000);
+        assert_eq!(resized.dimensions(), (70, 47));
+
+        assert!(resize_to_budget(&img, 2, ResizeFilter::Triangle, encode).is_none());
+    }
+
+    #[test]
+    fn masked_blur_interpolates() {
+        let img = ImgBuf::from_fn(16, 16, |(x, y)| {
+            let v = if (x + y) % 2 == 0 { 255 } else { 0 };
+            RGB8::new(v, v, v)
+        });
+        let mask = ImgBuf::from_fn(16, 16, |(x, _)| Gray(if x < 8 { 0 } else { 255 }));
+
+        let blurred = gaussian_blur(&img, 2.0);
+        let masked = masked_gaussian_blur(&img, &mask, 2.0).unwrap();
+        assert_eq!(masked.pixel((2, 5)), img.pixel((2, 5)));
+        assert_eq!(masked.pixel((12, 5)), blurred.pixel((12, 5)));
+
+        let wrong_mask = ImgBuf::from_fn(15, 16, |_| Gray(0u8));
+        assert!(masked_gaussian_blur(&img, &wrong_mask, 2.0).is_none());
+    }
+
+    #[test]
+    fn phash_near_duplicates() {
+        let img = ImgBuf::from_fn(64, 48, |(x, y)| {
+            let v = if (x / 16 + y / 12) % 2 == 0 { 200 } else { 40 };
+            RGBA8::new(v, v, v, 255)
+        });
+        let brighter = img
+            .clone()
+            .map_vec(|p| RGBA8::new(p.r + 20, p.g + 20, p.b + 20, p.a));
+        let inverted = img
+            .clone()
+            .map_vec(|p| RGBA8::new(255 - p.r, 255 - p.g, 255 - p.b, p.a));
+
+        assert_eq!((phash(&img) ^ phash(&brighter)).count_ones(), 0);
+        assert!((phash(&img) ^ phash(&inverted)).count_ones() > 32);
+    }
+
+    #[test]
+    fn phash_ignores_noise() {
+        // a horizontal gradient, so that the cells around the middle are close to the mean
+        let img = ImgBuf::from_fn(128, 96, |(x, _)| {
+            let v = (40 + x * 4 / 3) as u8;
+            RGBA8::new(v, v, v, 255)
+        });
+
+        // deterministic noise of up to ±40 on every pixel
+        let noisy = ImgBuf::from_fn(128, 96, |(x, y)| {
+            let hash = (x.wrapping_mul(73_856_093) ^ y.wrapping_mul(19_349_663)) % 81;
+            let v = i32::from(img.pixel((x, y)).unwrap().r) + hash as i32 - 40;
+            let v = v.clamp(0, 255) as u8;
+            RGBA8::new(v, v, v, 255)
+        });
+
+        assert!((phash(&img) ^ phash(&noisy)).count_ones() <= 2);
+        assert_eq!(phash(&ImgBuf::<RGBA8>::new(0, 4)), 0);
+    }
+
+    #[test]
+    fn downscale_icon_without_fringes() {
+        // an opaque red square surrounded by transparent green
+        let img = ImgBuf::from_fn(64, 32, |(x, y)| {
+            if (16..48).contains(&x) && (8..24).contains(&y) {
+                RGBA8::new(255, 0, 0, 255)
+            } else {
+                RGBA8::new(0, 255, 0, 0)
+            }
+        });
+
+        let icon = downscale_icon(&img, 16);
+        assert_eq!(icon.dimensions(), (16, 16));
+
+        // partially covered pixels keep the pure color, with partial alpha
+        for pixel in icon.pixels().filter(|p| p.a != 0) {
+            assert_eq!((pixel.r, pixel.g, pixel.b), (255, 0, 0));
+        }
+
+        // the view is centered vertically: rows 4..12 hold it
+        assert_eq!(icon.pixel((8, 7)), Some(&RGBA8::new(255, 0, 0, 255)));
+        assert_eq!(icon.pixel((8, 2)), Some(&RGBA8::default()));
+        assert_eq!(icon.pixel((8, 13)), Some(&RGBA8::default()));
+    }
+
+    #[test]
+    fn thumbnail_keeps_aspect_ratio() {
+        let img = ImgBuf::from_fn(400, 100, |(x, _)| RGB8::new(x as u8, 0, 0));
+        assert_eq!(thumbnail(&img, (64, 64)).dimensions(), (64, 16));
+        assert_eq!(thumbnail(&img, (800, 50)).dimensions(), (200, 50));
+        assert_eq!(thumbnail(&img, (1, 1)).dimensions(), (1, 1));
+    }
+
+    #[test]
+    fn crop_resize_matches_crop_then_resize() {
+        let img = ImgBuf::from_fn(16, 12, |(x, y)| RGB8::new(x as u8 * 15, y as u8 * 20, 7));
+        let roi = Rect::new((3, 2), (8, 6));
+
+        let cropped = img.view(roi).unwrap().to_buffer();
+        let expected = resize(&cropped, (5, 4), ResizeFilter::Lanczos3);
+        let fused = crop_resize(&img, roi, (5, 4), ResizeFilter::Lanczos3);
+        assert_eq!(fused.as_pixel_slice(), expected.as_pixel_slice());
+
+        // parts outside of the view are clamped away
+        let clamped = crop_resize(
+            &img,
+            Rect::new((8, 6), (100, 100)),
+            (4, 3),
+            ResizeFilter::Box,
+        );
+        let expected = resize(
+            &img.view(Rect::new((8, 6), (8, 6))).unwrap(),
+            (4, 3),
+            ResizeFilter::Box,
+        );
+        assert_eq!(clamped.as_pixel_slice(), expected.as_pixel_slice());
+    }
+
+    #[test]
+    fn resize_map_view() {
+        use crate::pixel::common::{RGB8, RGBA8};
+
+        let img = ImgBuf::from_fn(8, 4, |(x, y)| RGB8::new(x as u8 * 10, y as u8 * 10, 0));
+        let lazy = img.map_view(|p| RGBA8::new(p.r, p.g, p.b, 255));
+        let eager = ImgBuf::from_fn(8, 4, |coords| {
+            img.lazy_pixel(coords).unwrap().with_alpha(255)
+        });
+
+        let a = resize(&lazy, (5, 3), ResizeFilter::CatmullRom);
+        let b = resize(&eager, (5, 3), ResizeFilter::CatmullRom);
+        assert!(a.pixels().eq(b.pixels()));
+    }
+
+    #[test]
+    fn pack_atlas_placements() {
+        use crate::pixel::common::RGB8;
+
+        let images: Vec<_> = [(3, 5), (7, 2), (4, 4), (1, 1), (6, 3)]
+            .into_iter()
+            .enumerate()
+            .map(|(i, (w, h))| ImgBuf::from_fn(w, h, |_| RGB8::new(i as u8 + 1, 0, 0)))
+            .collect();
+
+        let (atlas, placements) = atlas::pack_atlas(&images);
+        for (index, (img, bounds)) in images.iter().zip(&placements).enumerate() {
+            assert_eq!(bounds.dimensions(), img.dimensions());
+            assert!(atlas.bounds().contains_rect(bounds));
+            assert!(placements[index + 1..]
+                .iter()
+                .all(|other| !bounds.overlaps(other)));
+            assert!(atlas.view(*bounds).unwrap().pixels().eq(img.pixels()));
+        }
+
+        let grid = atlas::pack_grid(&vec![images[3].clone(); 5], 2, 1, RGB8::new(0, 0, 0));
+        assert_eq!(grid.dimensions(), (3, 5));
+    }
+
+    #[test]
+    fn split_into_tiles_edges() {
+        use crate::pixel::common::RGB8;
+
+        let img = ImgBuf::from_fn(5, 3, |(x, y)| RGB8::new(x as u8, y as u8, 0));
+        let tiles = atlas::split_into_tiles(&img, (2, 2), None);
+        let dimensions: Vec<_> = tiles.iter().map(ImgCore::dimensions).collect();
+        assert_eq!(dimensions, [(2, 2), (2, 2), (1, 2), (2, 1), (2, 1), (1, 1)]);
+        assert_eq!(tiles[5].pixel((0, 0)), Some(&RGB8::new(4, 2, 0)));
+
+        let padded = atlas::split_into_tiles(&img, (2, 2), Some(RGB8::new(9, 9, 9)));
+        assert!(padded.iter().all(|tile| tile.dimensions() == (2, 2)));
+        assert_eq!(padded[5].pixel((0, 0)), Some(&RGB8::new(4, 2, 0)));
+        assert_eq!(padded[5].pixel((1, 1)), Some(&RGB8::new(9, 9, 9)));
+    }
+
+    #[test]
+    fn clahe_stretches_contrast() {
+        use crate::pixel::common::RGBA8;
+
+        let mut img = ImgBuf::from_fn(64, 64, |(x, y)| {
+            let v = 100 + ((x + y) / 8) as u8;
+            RGBA8::new(v, v, v, 77)
+        });
+        clahe(&mut img, (2, 2), 4.0);
+
+        let (min, max) = img.pixels().fold((u8::MAX, u8::MIN), |(min, max), p| {
+            (min.min(p.r), max.max(p.r))
+        });
+        assert!(max - min > 30, "range is only {min}..={max}");
+        assert!(img.pixels().all(|p| p.a == 77 && p.r == p.g && p.g == p.b));
+    }
+
+    #[test]
+    fn distance_transform_euclidean() {
+        use crate::pixel::common::RGB8;
+
+        let mask = ImgBuf::from_fn(9, 7, |p| {
+            if p == (1, 2) {
+                RGB8::new(0, 255, 0)
+            } else {
+                RGB8::new(0, 0, 0)
+            }
+        });
+
+        let distances = distance_transform(&mask, Foreground::NonZero);
+        assert_eq!(distances.pixel((1, 2)), Some(&[0.0]));
+        assert_eq!(distances.pixel((4, 6)), Some(&[5.0]));
+        assert_eq!(distances.pixel((8, 2)), Some(&[7.0]));
+
+        let inverted = distance_transform(&mask, Foreground::Zero);
+        assert_eq!(inverted.pixel((1, 2)), Some(&[1.0]));
+        assert_eq!(inverted.pixel((4, 6)), Some(&[0.0]));
+
+        let empty = distance_transform(&Rgb8Img::new(3, 3), Foreground::NonZero);
+        assert!(empty.pixels().all(|d| d[0] == f32::INFINITY));
+    }
+
+    #[test]
+    fn checkerboard_backdrop() {
+        let (light, dark) = (RGB8::new(200, 200, 200), RGB8::new(100, 100, 100));
+        let img = ImgBuf::from_fn(4, 2, |(x, _)| match x {
+            0 | 2 => RGBA8::new(0, 0, 0, 0),
+            1 => RGBA8::new(255, 0, 0, 255),
+            _ => RGBA8::new(0, 0, 255, 128),
+        });
+
+        let flat = composite_on_checkerboard(&img, 2, light, dark);
+        assert_eq!(flat.pixel((0, 0)), Some(&light));
+        assert_eq!(flat.pixel((2, 1)), Some(&dark));
+        assert_eq!(flat.pixel((1, 0)), Some(&RGB8::new(255, 0, 0)));
+        assert_eq!(flat.pixel((3, 0)), Some(&RGB8::new(50, 50, 178)));
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn parallel_resize_matches_serial() {
+        let img = ImgBuf::from_fn(37, 23, |(x, y)| {
+            RGBA8::new(x as u8 * 7, y as u8 * 11, 3, 200)
+        });
+        for (dimensions, filter) in [
+            ((11, 50), ResizeFilter::Lanczos3),
+            ((80, 9), ResizeFilter::Triangle),
+            ((0, 5), ResizeFilter::Box),
+            ((5, 0), ResizeFilter::Mitchell),
+        ] {
+            let serial = resize(&img, dimensions, filter);
+            let parallel = resize_par(&img, dimensions, filter);
+            assert_eq!(parallel.dimensions(), serial.dimensions());
+            assert!(parallel.pixels().eq(serial.pixels()));
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn process_tiles_in_parallel() {
+        let mut img = Rgb8Img::new(10, 7);
+        process_tiles_par(&mut img, (4, 3), |tile| {
+            let (width, height) = tile.dimensions();
+            tile.pixels_mut()
+                .for_each(|p| *p = RGB8::new(width as u8, height as u8, 1));
+        });
+
+        assert_eq!(img.pixel((0, 0)), Some(&RGB8::new(4, 3, 1)));
+        assert_eq!(img.pixel((9, 6)), Some(&RGB8::new(2, 1, 1)));
+        assert!(img.pixels().all(|p| p.b == 1));
+
+        let mut view = img.view_mut(Rect::new((2, 2), (4, 4))).unwrap();
+        process_tiles_par(&mut view, (3, 3), |tile| {
+            tile.pixels_mut().for_each(|p| p.b = 2);
+        });
+        assert_eq!(img.pixels().filter(|p| p.b == 2).count(), 16);
+    }
+
+    #[test]
+    fn sepia_tone() {
+        let mut img = Rgb8Img::from_fn(1, 1, |_| RGB8::new(255, 255, 255));
+        sepia(&mut img);
+        assert_eq!(img.pixel((0, 0)), Some(&RGB8::new(255, 255, 239)));
+    }
+}
@@ -0,0 +1,169 @@
+use crate::{buffer::ImgBuf, pixel::Pixel, processing::Bounded};
+
+/// The kinds of noise that can be generated by [`ImgBuf::from_noise`].
+///
+/// Regardless of the kind, the alpha channel of pixel types that have one (see
+/// [`Pixel::ALPHA_CHANNEL`]) is always set to [`Bounded::MAX_VALUE`], i.e. fully opaque.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NoiseKind {
+    /// Each channel is an independent, uniformly distributed value in the range
+    /// [`MIN_VALUE`][Bounded::MIN_VALUE]..=[`MAX_VALUE`][Bounded::MAX_VALUE].
+    Uniform,
+    /// Each channel is an independent, normally distributed value. `mean` and `std_dev` are
+    /// normalized, where `0.0` represents [`MIN_VALUE`][Bounded::MIN_VALUE] and `1.0`
+    /// represents [`MAX_VALUE`][Bounded::MAX_VALUE]. Values outside of the range are clamped.
+    Gaussian { mean: f32, std_dev: f32 },
+    /// Smooth 2D Perlin noise, with the same value in every channel. `scale` is the size, in
+    /// pixels, of a single noise cell: bigger values produce smoother noise.
+    Perlin { scale: f32 },
+}
+
+/// A small, fast and seedable pseudo-random number generator (`SplitMix64`).
+///
+/// This is not cryptographically secure, but it is more than enough for noise, and it's output
+/// for a given seed is stable.
+#[derive(Debug, Clone)]
+pub(crate) struct Rng(u64);
+
+impl Rng {
+    #[inline]
+    pub(crate) fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    #[inline]
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns an uniformly distributed value in the range `0.0..1.0`.
+    #[inline]
+    pub(crate) fn next_f32(&mut self) -> f32 {
+        // only the upper 24 bits, since that's all the precision a f32 has
+        (self.next_u64() >> 40) as f32 / (1u32 << 24) as f32
+    }
+
+    /// Returns a normally distributed value with mean `0.0` and standard deviation `1.0`.
+    #[inline]
+    pub(crate) fn next_standard_normal(&mut self) -> f32 {
+        // box-muller transform. 'u1' must not be zero, since ln(0) is -inf.
+        let u1 = 1.0 - self.next_f32();
+        let u2 = self.next_f32();
+        (-2.0 * u1.ln()).sqrt() * (std::f32::consts::TAU * u2).cos()
+    }
+}
+
+/// Classic 2D Perlin noise with a seeded permutation table.
+struct Perlin {
+    permutation: [u8; 512],
+}
+
+impl Perlin {
+    fn new(rng: &mut Rng) -> Self {
+        let mut table: [u8; 256] = std::array::from_fn(|i| i as u8);
+        for i in (1..table.len()).rev() {
+            let j = (rng.next_u64() % (i as u64 + 1)) as usize;
+            table.swap(i, j);
+        }
+
+        Self {
+            permutation: std::array::from_fn(|i| table[i % 256]),
+        }
+    }
+
+    #[inline]
+    fn fade(t: f32) -> f32 {
+        t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+    }
+
+    #[inline]
+    fn gradient(hash: u8, x: f32, y: f32) -> f32 {
+        match hash & 7 {
+            0 => x + y,
+            1 => -x + y,
+            2 => x - y,
+            3 => -x - y,
+            4 => x,
+            5 => -x,
+            6 => y,
+            _ => -y,
+        }
+    }
+
+    /// Samples the noise at the given point. The result is in the range `-1.0..=1.0`.
+    fn sample(&self, x: f32, y: f32) -> f32 {
+        let (x_floor, y_floor) = (x.floor(), y.floor());
+        let (xi, yi) = (
+            (x_floor as i64 & 255) as usize,
+            (y_floor as i64 & 255) as usize,
+        );
+        let (xf, yf) = (x - x_floor, y - y_floor);
+        let (u, v) = (Self::fade(xf), Self::fade(yf));
+
+        let p = &self.permutation;
+        let a = usize::from(p[xi]) + yi;
+        let b = usize::from(p[xi + 1]) + yi;
+
+        let lerp = |t: f32, a: f32, b: f32| a + t * (b - a);
+        lerp(
+            v,
+            lerp(
+                u,
+                Self::gradient(p[a], xf, yf),
+                Self::gradient(p[b], xf - 1.0, yf),
+            ),
+            lerp(
+                u,
+                Self::gradient(p[a + 1], xf, yf - 1.0),
+                Self::gradient(p[b + 1], xf - 1.0, yf - 1.0),
+            ),
+        )
+    }
+}
+
+/// Creates a pixel where every channel but alpha is given by `f`, which returns normalized values.
+#[inline]
+fn noise_pixel<P, C, const N: usize>(mut f: impl FnMut() -> f32) -> P
+where
+    P: Pixel<Channels = [C; N]>,
+    C: Bounded,
+{
+    P::new(std::array::from_fn(|i| {
+        if P::ALPHA_CHANNEL == Some(i) {
+            C::MAX_VALUE
+        } else {
+            C::from_normalized_f32(f().clamp(0.0, 1.0))
+        }
+    }))
+}
+
+pub(crate) fn generate<P, C, const N: usize>(
+    width: u32,
+    height: u32,
+    seed: u64,
+    kind: NoiseKind,
+) -> ImgBuf<P>
+where
+    P: Pixel<Channels = [C; N]>,
+    C: Bounded,
+{
+    let mut rng = Rng::new(seed);
+    match kind {
+        NoiseKind::Uniform => ImgBuf::from_fn(width, height, |_| noise_pixel(|| rng.next_f32())),
+        NoiseKind::Gaussian { mean, std_dev } => ImgBuf::from_fn(width, height, |_| {
+            noise_pixel(|| mean + std_dev * rng.next_standard_normal())
+        }),
+        NoiseKind::Perlin { scale } => {
+            assert!(scale > 0.0, "scale must be positive");
+            let perlin = Perlin::new(&mut rng);
+            ImgBuf::from_fn(width, height, |(x, y)| {
+                let value = perlin.sample(x as f32 / scale, y as f32 / scale);
+                noise_pixel(|| 0.5 + 0.5 * value)
+            })
+        }
+    }
+}
@@ -0,0 +1,227 @@
+use crate::{buffer::common::Gray8Img, pixel::common::Gray, prelude::*};
+
+/// Returns the squared euclidean distance between two colors.
+#[inline]
+fn distance_squared(a: [f32; 3], b: RGB8) -> f32 {
+    let [dr, dg, db] = [
+        a[0] - f32::from(b.r),
+        a[1] - f32::from(b.g),
+        a[2] - f32::from(b.b),
+    ];
+
+    dr * dr + dg * dg + db * db
+}
+
+/// Returns the index of the color of the palette closest to the given one.
+#[inline]
+fn closest(palette: &[RGB8], color: [f32; 3]) -> usize {
+    palette
+        .iter()
+        .enumerate()
+        .min_by(|(_, a), (_, b)| {
+            distance_squared(color, **a).total_cmp(&distance_squared(color, **b))
+        })
+        .map(|(index, _)| index)
+        .expect("palette is not empty")
+}
+
+/// Computes a palette of at most `n_colors` colors that represents the colors of a view, using
+/// the median cut algorithm.
+///
+/// The palette has less than `n_colors` colors if the view doesn't have that many distinct colors,
+/// and is empty if the view is empty.
+///
+/// # Panics
+/// Panics if `n_colors` is zero.
+#[must_use = "the palette is returned and the original view is left unmodified"]
+pub fn median_cut<I>(view: &I, n_colors: usize) -> Vec<RGB8>
+where
+    I: Img<Pixel = RGB8>,
+{
+    assert!(n_colors != 0, "n_colors must be non-zero");
+
+    let channel = |color: &RGB8, index: usize| [color.r, color.g, color.b][index];
+
+    // the widest channel of a box and it's range
+    let widest = |colors: &[RGB8]| {
+        (0..3)
+            .map(|index| {
+                let (min, max) = colors.iter().fold((u8::MAX, u8::MIN), |(min, max), c| {
+                    (min.min(channel(c, index)), max.max(channel(c, index)))
+                });
+
+                (index, max.saturating_sub(min))
+            })
+            .max_by_key(|(_, range)| *range)
+            .expect("there are three channels")
+    };
+
+    let colors: Vec<RGB8> = view.pixels().copied().collect();
+    if colors.is_empty() {
+        return Vec::new();
+    }
+
+    let mut boxes = vec![colors];
+    while boxes.len() < n_colors {
+        // split the box with the widest range. boxes with a single color can't be split.
+        let Some((index, (channel_index, _))) = boxes
+            .iter()
+            .map(|colors| widest(colors))
+            .enumerate()
+            .filter(|(_, (_, range))| *range > 0)
+            .max_by_key(|(_, (_, range))| *range)
+        else {
+            break;
+        };
+
+        let mut colors = boxes.swap_remove(index);
+        colors.sort_unstable_by_key(|c| channel(c, channel_index));
+
+        // split at the median, but never between two equal values of the channel, so that
+        // both halves are non-empty and different
+        let median = channel(&colors[colors.len() / 2], channel_index);
+        let split = match colors.partition_point(|c| channel(c, channel_index) < median) {
+            0 => colors.partition_point(|c| channel(c, channel_index) <= median),
+            split => split,
+        };
+
+        let upper = colors.split_off(split);
+        boxes.push(colors);
+        boxes.push(upper);
+    }
+
+    boxes
+        .iter()
+        .map(|colors| {
+            let sum = colors.iter().fold([0u64; 3], |sum, c| {
+                [
+                    sum[0] + u64::from(c.r),
+                    sum[1] + u64::from(c.g),
+                    sum[2] + u64::from(c.b),
+                ]
+            });
+
+            let len = colors.len() as u64;
+            let [r, g, b] = sum.map(|s| ((s + len / 2) / len) as u8);
+            RGB8::new(r, g, b)
+        })
+        .collect()
+}
+
+/// Reduces the colors of a view to at most `n_colors`, returning the palette along with an image
+/// of indices into it, ready for indexed encoding.
+///
+/// The palette is computed with [`median_cut`]. If `dither` is `true`, Floyd-Steinberg error
+/// diffusion is applied against the palette, which trades banding for noise.
+///
+/// # Panics
+/// Panics if `n_colors` is zero or greater than 256.
+#[must_use = "the palette and indices are returned and the original view is left unmodified"]
+pub fn reduce_colors<I>(view: &I, n_colors: usize, dither: bool) -> (Vec<RGB8>, Gray8Img)
+where
+    I: Img<Pixel = RGB8>,
+{
+    assert!(n_colors <= 256, "indices must fit within an u8");
+
+    let palette = median_cut(view, n_colors);
+    let (width, height) = view.dimensions();
+
+    // error of the current and next rows. the rows have one extra pixel on each side so the error
+    // can be diffused without bound checks.
+    let row_len = width as usize + 2;
+    let mut current = vec![[0f32; 3]; row_len];
+    let mut next = vec![[0f32; 3]; row_len];
+
+    let mut indices = Vec::with_capacity(view.size());
+    for y in 0..height {
+        for x in 0..width {
+            // SAFETY: (x, y) is within the bounds of the view.
+            let pixel = unsafe { view.pixel_unchecked((x, y)) };
+            let error = current[x as usize + 1];
+            let color = [
+                f32::from(pixel.r) + error[0],
+                f32::from(pixel.g) + error[1],
+                f32::from(pixel.b) + error[2],
+            ];
+
+            let index = closest(&palette, color);
+            indices.push(Gray(index as u8));
+
+            if dither {
+                let chosen = palette[index];
+                let error = [
+                    color[0] - f32::from(chosen.r),
+                    color[1] - f32::from(chosen.g),
+                    color[2] - f32::from(chosen.b),
+                ];
+
+                let x = x as usize + 1;
+                let diffuse = |target: &mut [f32; 3], weight: f32| {
+                    for (target, error) in target.iter_mut().zip(error) {
+                        *target += error * weight / 16.0;
+                    }
+                };
+
+                diffuse(&mut current[x + 1], 7.0);
+                diffuse(&mut next[x - 1], 3.0);
+                diffuse(&mut next[x], 5.0);
+                diffuse(&mut next[x + 1], 1.0);
+            }
+        }
+
+        std::mem::swap(&mut current, &mut next);
+        next.fill([0.0; 3]);
+    }
+
+    (palette, ImgBuf::from_container(indices, width, height))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn median_cut_distinct_colors() {
+        let colors = [
+            RGB8::new(255, 0, 0),
+            RGB8::new(0, 255, 0),
+            RGB8::new(0, 0, 255),
+            RGB8::new(255, 255, 255),
+        ];
+        let img = ImgBuf::from_fn(4, 4, |(x, _)| colors[x as usize]);
+
+        let mut palette = median_cut(&img, 16);
+        palette.sort_unstable_by_key(|c| (c.r, c.g, c.b));
+        let mut expected = colors.to_vec();
+        expected.sort_unstable_by_key(|c| (c.r, c.g, c.b));
+        assert_eq!(palette, expected);
+
+        assert_eq!(median_cut(&img, 2).len(), 2);
+    }
+
+    #[test]
+    fn reduce_colors_dithering() {
+        let img = ImgBuf::from_fn(32, 8, |(x, _)| {
+            let v = (x * 8) as u8;
+            RGB8::new(v, v, v)
+        });
+
+        let (palette, indices) = reduce_colors(&img, 2, false);
+        assert_eq!(palette.len(), 2);
+        assert_eq!(indices.dimensions(), img.dimensions());
+
+        // dithering preserves the average brightness much better
+        let brightness = |palette: &[RGB8], indices: &Gray8Img| {
+            let sum: u32 = indices
+                .pixels()
+                .map(|i| u32::from(palette[usize::from(*i.channels().first().unwrap())].r))
+                .sum();
+            sum as f32 / indices.size() as f32
+        };
+        let original = img.pixels().map(|p| f32::from(p.r)).sum::<f32>() / img.size() as f32;
+
+        let (palette, dithered) = reduce_colors(&img, 2, true);
+        assert!((brightness(&palette, &dithered) - original).abs() < 4.0);
+        assert!(palette.iter().all(|c| c.r == c.g && c.g == c.b));
+    }
+}
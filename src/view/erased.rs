@@ -0,0 +1,206 @@
+use crate::{
+    view::{Img, ImgCore, ImgMut, ImgMutCore},
+    Rect,
+};
+
+/// The maximum amount of bounds accepted by [`ErasedImgMut::view_mut_multiple_erased`].
+pub const MAX_ERASED_VIEWS: usize = 16;
+
+/// Object-safe version of [`Img`].
+///
+/// This trait has a blanket implementation for every [`Img`], so `&dyn ErasedImg<Pixel = P>` can
+/// be used to pass around images of unknown concrete type, at the cost of dynamic dispatch and
+/// boxed iterators and views.
+pub trait ErasedImg: ImgCore {
+    /// Returns a boxed iterator over the pixels of this view.
+    fn erased_pixels(&self) -> Box<dyn Iterator<Item = &Self::Pixel> + '_>;
+
+    /// Returns a boxed view into this view, without checking bounds.
+    ///
+    /// # Safety
+    /// The bounds must fit in this view.
+    unsafe fn view_unchecked_erased(
+        &self,
+        bounds: Rect,
+    ) -> Box<dyn ErasedImg<Pixel = Self::Pixel> + '_>;
+
+    /// Returns a boxed view into this view. If the bounds don't fit in this view, returns `None`.
+    #[inline]
+    fn view_erased(&self, bounds: Rect) -> Option<Box<dyn ErasedImg<Pixel = Self::Pixel> + '_>> {
+        self.bounds()
+            .contains_rect(&bounds)
+            // SAFETY: safe because 'bounds' is checked to be contained within the view.
+            .then(|| unsafe { self.view_unchecked_erased(bounds) })
+    }
+
+    /// Returns multiple boxed views into this view. If any of the bounds don't fit in this view,
+    /// returns `None`.
+    fn view_multiple_erased(
+        &self,
+        bounds: &[Rect],
+    ) -> Option<Vec<Box<dyn ErasedImg<Pixel = Self::Pixel> + '_>>> {
+        bounds.iter().map(|b| self.view_erased(*b)).collect()
+    }
+}
+
+impl<T> ErasedImg for T
+where
+    T: Img,
+{
+    #[inline]
+    fn erased_pixels(&self) -> Box<dyn Iterator<Item = &Self::Pixel> + '_> {
+        Box::new(self.pixels())
+    }
+
+    #[inline]
+    unsafe fn view_unchecked_erased(
+        &self,
+        bounds: Rect,
+    ) -> Box<dyn ErasedImg<Pixel = Self::Pixel> + '_> {
+        // SAFETY: we trust the caller!
+        Box::new(unsafe { self.view_unchecked(bounds) })
+    }
+}
+
+/// Object-safe version of [`ImgMut`].
+///
+/// This trait has a blanket implementation for every [`ImgMut`], so `&mut dyn ErasedImgMut<Pixel = P>`
+/// can be used to mutate images of unknown concrete type.
+pub trait ErasedImgMut: ErasedImg + ImgMutCore {
+    /// Returns a boxed mutable iterator over the pixels of this view.
+    fn erased_pixels_mut(&mut self) -> Box<dyn Iterator<Item = &mut Self::Pixel> + '_>;
+
+    /// Returns a boxed mutable view into this view, without checking bounds.
+    ///
+    /// # Safety
+    /// The bounds must fit in this view.
+    unsafe fn view_mut_unchecked_erased(
+        &mut self,
+        bounds: Rect,
+    ) -> Box<dyn ErasedImgMut<Pixel = Self::Pixel> + '_>;
+
+    /// Returns a boxed mutable view into this view. If the bounds don't fit in this view, returns
+    /// `None`.
+    #[inline]
+    fn view_mut_erased(
+        &mut self,
+        bounds: Rect,
+    ) -> Option<Box<dyn ErasedImgMut<Pixel = Self::Pixel> + '_>> {
+        self.bounds()
+            .contains_rect(&bounds)
+            // SAFETY: safe because 'bounds' is checked to be contained within the view.
+            .then(|| unsafe { self.view_mut_unchecked_erased(bounds) })
+    }
+
+    /// Returns multiple boxed mutable views into this view, without checking bounds and overlaps.
+    ///
+    /// # Safety
+    /// All bounds must fit in this view and not overlap with each other.
+    ///
+    /// # Panics
+    /// Panics if there are more than [`MAX_ERASED_VIEWS`] bounds.
+    unsafe fn view_mut_multiple_unchecked_erased(
+        &mut self,
+        bounds: &[Rect],
+    ) -> Vec<Box<dyn ErasedImgMut<Pixel = Self::Pixel> + '_>>;
+
+    /// Returns multiple boxed mutable views into this view. If any of the bounds don't fit in this
+    /// view or overlap, or if there are more than [`MAX_ERASED_VIEWS`] bounds, returns `None`.
+    fn view_mut_multiple_erased(
+        &mut self,
+        bounds: &[Rect],
+    ) -> Option<Vec<Box<dyn ErasedImgMut<Pixel = Self::Pixel> + '_>>> {
+        if bounds.len() > MAX_ERASED_VIEWS {
+            return None;
+        }
+
+        for (index, bound_a) in bounds.iter().enumerate() {
+            if !self.bounds().contains_rect(bound_a) {
+                return None;
+            }
+
+            for bound_b in &bounds[index + 1..] {
+                if bound_a.overlaps(bound_b) {
+                    return None;
+                }
+            }
+        }
+
+        // SAFETY: bounds have been checked
+        Some(unsafe { self.view_mut_multiple_unchecked_erased(bounds) })
+    }
+}
+
+impl<T> ErasedImgMut for T
+where
+    T: ImgMut,
+{
+    #[inline]
+    fn erased_pixels_mut(&mut self) -> Box<dyn Iterator<Item = &mut Self::Pixel> + '_> {
+        Box::new(self.pixels_mut())
+    }
+
+    #[inline]
+    unsafe fn view_mut_unchecked_erased(
+        &mut self,
+        bounds: Rect,
+    ) -> Box<dyn ErasedImgMut<Pixel = Self::Pixel> + '_> {
+        // SAFETY: we trust the caller!
+        Box::new(unsafe { self.view_mut_unchecked(bounds) })
+    }
+
+    unsafe fn view_mut_multiple_unchecked_erased(
+        &mut self,
+        bounds: &[Rect],
+    ) -> Vec<Box<dyn ErasedImgMut<Pixel = Self::Pixel> + '_>> {
+        // 'ImgMut' can only create multiple mutable views at once from an array, so dispatch on
+        // the amount of bounds.
+        macro_rules! dispatch {
+            ($($n:literal)*) => {
+                match bounds.len() {
+                    $(
+                        $n => {
+                            let bounds: [Rect; $n] = bounds.try_into().expect("length was matched");
+
+                            // SAFETY: we trust the caller!
+                            unsafe { self.view_mut_multiple_unchecked(bounds) }
+                                .into_iter()
+                                .map(|v| Box::new(v) as Box<dyn ErasedImgMut<Pixel = Self::Pixel>>)
+                                .collect()
+                        }
+                    )*
+                    _ => panic!("at most {MAX_ERASED_VIEWS} views can be created at once"),
+                }
+            };
+        }
+
+        dispatch!(0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    fn fill(img: &mut dyn ErasedImgMut<Pixel = RGB8>, color: RGB8) {
+        img.erased_pixels_mut().for_each(|p| *p = color);
+    }
+
+    #[test]
+    fn erased_views_mut() {
+        let mut buffer = Rgb8Img::new(4, 2);
+        let mut views = buffer
+            .view_mut_multiple_erased(&[Rect::new((0, 0), (2, 2)), Rect::new((2, 0), (2, 2))])
+            .unwrap();
+        fill(&mut *views[0], RGB8::new(1, 1, 1));
+        fill(&mut *views[1], RGB8::new(2, 2, 2));
+        drop(views);
+
+        assert_eq!(buffer.pixel((1, 1)), Some(&RGB8::new(1, 1, 1)));
+        assert_eq!(buffer.pixel((2, 0)), Some(&RGB8::new(2, 2, 2)));
+        assert!(buffer
+            .view_mut_multiple_erased(&[Rect::new((0, 0), (3, 2)), Rect::new((2, 0), (2, 2))])
+            .is_none());
+    }
+}
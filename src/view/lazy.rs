@@ -0,0 +1,111 @@
+use crate::{buffer::ImgBuf, view::Img, Point, Rect};
+
+/// Trait for types that can be treated as a view into some image whose pixels are computed on
+/// demand and returned by value.
+///
+/// Unlike [`Img`], this doesn't require the pixels to be stored anywhere, which allows composing
+/// transformations (see [`LazyImg::map_view`]) without creating intermediate buffers. Every [`Img`]
+/// with [`Clone`] pixels is a [`LazyImg`].
+///
+/// The methods of this trait have a `lazy_` prefix so they don't clash with the ones of [`Img`].
+pub trait LazyImg {
+    /// The pixel type of this view.
+    type Pixel;
+
+    /// The dimensions of this view. Equivalent to `(width, height)`.
+    fn lazy_dimensions(&self) -> (u32, u32);
+
+    /// Returns a [`Rect`] with top-left point `(0, 0)` and dimensions `self.lazy_dimensions()`.
+    #[inline]
+    fn lazy_bounds(&self) -> Rect {
+        Rect::new((0, 0), self.lazy_dimensions())
+    }
+
+    /// Computes the pixel with coordinates `(x, y)` relative to this view. If the coordinates aren't
+    /// within the bounds of this view, returns `None`.
+    #[inline]
+    fn lazy_pixel(&self, coords: Point) -> Option<Self::Pixel> {
+        self.lazy_bounds()
+            .contains_relative(coords)
+            // SAFETY: safe because the pixel is checked to be in bounds
+            .then(|| unsafe { self.lazy_pixel_unchecked(coords) })
+    }
+
+    /// Computes the pixel with coordinates `(x, y)` relative to this view, without checking.
+    ///
+    /// # Safety
+    /// The coordinate must be in the bounds of the view.
+    unsafe fn lazy_pixel_unchecked(&self, coords: Point) -> Self::Pixel;
+
+    /// Creates an [`ImgBuf`] from this view with [`Vec`] as it's container by computing all of it's
+    /// pixels.
+    #[inline]
+    fn lazy_to_buffer(&self) -> ImgBuf<Self::Pixel, Vec<Self::Pixel>> {
+        let (width, height) = self.lazy_dimensions();
+
+        // SAFETY: the coordinates are always going to be in bounds since the
+        // new buffer and self have the same dimensions
+        ImgBuf::from_fn(width, height, |coords| unsafe {
+            self.lazy_pixel_unchecked(coords)
+        })
+    }
+
+    /// Returns a view whose pixels are the pixels of this view mapped through `f`. `f` is called
+    /// every time a pixel is read.
+    #[inline]
+    fn map_view<P, F>(&self, f: F) -> MapView<'_, Self, F>
+    where
+        F: Fn(&Self::Pixel) -> P,
+    {
+        MapView { view: self, f }
+    }
+}
+
+impl<T> LazyImg for T
+where
+    T: Img,
+    T::Pixel: Clone,
+{
+    type Pixel = T::Pixel;
+
+    #[inline]
+    fn lazy_dimensions(&self) -> (u32, u32) {
+        self.dimensions()
+    }
+
+    #[inline]
+    unsafe fn lazy_pixel_unchecked(&self, coords: Point) -> Self::Pixel {
+        // SAFETY: we trust the caller!
+        unsafe { self.pixel_unchecked(coords) }.clone()
+    }
+}
+
+/// A view whose pixels are computed by mapping the pixels of another view through a function. See
+/// [`LazyImg::map_view`].
+#[derive(Clone, Copy)]
+pub struct MapView<'view_ref, I, F>
+where
+    I: ?Sized,
+{
+    view: &'view_ref I,
+    f: F,
+}
+
+impl<'view_ref, I, P, F> LazyImg for MapView<'view_ref, I, F>
+where
+    I: LazyImg + ?Sized,
+    F: Fn(&I::Pixel) -> P,
+{
+    type Pixel = P;
+
+    #[inline]
+    fn lazy_dimensions(&self) -> (u32, u32) {
+        self.view.lazy_dimensions()
+    }
+
+    #[inline]
+    unsafe fn lazy_pixel_unchecked(&self, coords: Point) -> Self::Pixel {
+        // SAFETY: this view has the same bounds as the inner one.
+        (self.f)(&unsafe { self.view.lazy_pixel_unchecked(coords) })
+    }
+}
@@ -1,23 +1,24 @@
+/// Object-safe versions of the view traits.
+pub mod erased;
 /// Default iterator types.
 pub mod iter;
+/// Views whose pixels are computed on demand.
+pub mod lazy;
 
-use crate::{buffer::ImgBuf, pixel::Pixel, Point, Rect};
+use crate::{
+    buffer::ImgBuf,
+    pixel::{common::Blend, Pixel},
+    Point, Rect,
+};
 
 /// Trait for types that can be treated as a view into some image.
-pub trait Img {
+///
+/// This is the minimal, object-safe core of [`Img`]: it can be used as a trait object (e.g.
+/// `Box<dyn ImgCore<Pixel = P>>`) in order to store images of different types together.
+pub trait ImgCore {
     /// The pixel type of this view.
     type Pixel: Pixel;
 
-    /// The type of the iterator through pixels of this view.
-    type Pixels<'view_ref>: Iterator<Item = &'view_ref Self::Pixel>
-    where
-        Self: 'view_ref;
-
-    /// The view type the `view` method returns.
-    type View<'view_ref>: Img<Pixel = Self::Pixel>
-    where
-        Self: 'view_ref;
-
     /// The width of this view.
     fn width(&self) -> u32;
     /// The height of this view.
@@ -56,6 +57,19 @@ pub trait Img {
     /// # Safety
     /// The coordinate must be in the bounds of the view.
     unsafe fn pixel_unchecked(&self, coords: Point) -> &Self::Pixel;
+}
+
+/// Extension of [`ImgCore`] with iterators, subviews and conversions.
+pub trait Img: ImgCore {
+    /// The type of the iterator through pixels of this view.
+    type Pixels<'view_ref>: Iterator<Item = &'view_ref Self::Pixel>
+    where
+        Self: 'view_ref;
+
+    /// The view type the `view` method returns.
+    type View<'view_ref>: Img<Pixel = Self::Pixel>
+    where
+        Self: 'view_ref;
 
     /// Returns an iterator over the pixels of this view.
     fn pixels(&self) -> Self::Pixels<'_>;
@@ -69,6 +83,96 @@ pub trait Img {
         self.pixels().map(std::slice::from_ref)
     }
 
+    /// Returns an iterator over every `step`-th pixel of this view, in row-major (top-left to
+    /// bottom-right) order, starting with the first one.
+    ///
+    /// This is useful for estimating statistics of large views without visiting every pixel.
+    ///
+    /// # Panics
+    /// Panics if `step` is zero.
+    #[inline]
+    fn pixels_step(&self, step: u32) -> impl ExactSizeIterator<Item = &'_ Self::Pixel> {
+        assert!(step != 0, "step must be non-zero");
+        let width = self.width() as usize;
+
+        (0..self.size()).step_by(step as usize).map(move |index| {
+            let coords = ((index % width) as u32, (index / width) as u32);
+            // SAFETY: 'index' is smaller than the size of the view, so 'coords' is within it's
+            // bounds.
+            unsafe { self.pixel_unchecked(coords) }
+        })
+    }
+
+    /// Returns an iterator over the columns of this view, from left to right. Each column is an
+    /// iterator over it's pixels, from top to bottom.
+    ///
+    /// This complements [`Rows::rows`][crate::buffer::Rows::rows] for vertical operations,
+    /// without having to transpose the view first.
+    #[inline]
+    fn columns(
+        &self,
+    ) -> impl ExactSizeIterator<Item = impl ExactSizeIterator<Item = &'_ Self::Pixel>> {
+        (0..self.width()).map(move |x| {
+            // SAFETY: x is in the 0..self.width() range and y is in the 0..self.height() range,
+            // so the coordinates are always within the bounds of the view.
+            (0..self.height()).map(move |y| unsafe { self.pixel_unchecked((x, y)) })
+        })
+    }
+
+    /// Returns a view of this view flipped horizontally, without copying any pixels.
+    #[inline]
+    fn flip_h(&self) -> FlipH<&Self>
+    where
+        Self: Sized,
+    {
+        FlipH::new(self)
+    }
+
+    /// Returns a view of this view flipped vertically, without copying any pixels.
+    #[inline]
+    fn flip_v(&self) -> FlipV<&Self>
+    where
+        Self: Sized,
+    {
+        FlipV::new(self)
+    }
+
+    /// Returns a view of this view transposed (i.e. with it's rows turned into columns), without
+    /// copying any pixels.
+    #[inline]
+    fn transpose(&self) -> Transpose<&Self>
+    where
+        Self: Sized,
+    {
+        Transpose::new(self)
+    }
+
+    /// Returns the pixels of the row with the given y coordinate, from left to right. If the row
+    /// isn't within the bounds of this view, returns `None`.
+    #[inline]
+    fn row_to_vec(&self, y: u32) -> Option<Vec<Self::Pixel>>
+    where
+        Self::Pixel: Clone,
+    {
+        // SAFETY: the row is checked to be within the bounds of the view.
+        (y < self.height())
+            .then(|| (0..self.width()).map(|x| unsafe { self.pixel_unchecked((x, y)) }.clone()))
+            .map(Iterator::collect)
+    }
+
+    /// Returns the pixels of the column with the given x coordinate, from top to bottom. If the
+    /// column isn't within the bounds of this view, returns `None`.
+    #[inline]
+    fn col_to_vec(&self, x: u32) -> Option<Vec<Self::Pixel>>
+    where
+        Self::Pixel: Clone,
+    {
+        // SAFETY: the column is checked to be within the bounds of the view.
+        (x < self.width())
+            .then(|| (0..self.height()).map(|y| unsafe { self.pixel_unchecked((x, y)) }.clone()))
+            .map(Iterator::collect)
+    }
+
     /// Returns a view into this view. If the bounds don't fit in this view, returns `None`.
     #[inline]
     fn view(&self, bounds: Rect) -> Option<Self::View<'_>> {
@@ -142,20 +246,33 @@ pub trait Img {
             self.pixel_unchecked((x, y)).clone()
         })
     }
-}
-
-/// Trait for types that can be treated as a mutable view into some image.
-pub trait ImgMut: Img {
-    /// The type of the iterator through mutable pixels of this view.
-    type PixelsMut<'view_ref>: Iterator<Item = &'view_ref mut Self::Pixel>
-    where
-        Self: 'view_ref;
 
-    /// The mutable view type the `view_mut` method returns.
-    type ViewMut<'view_ref>: ImgMut<Pixel = Self::Pixel>
+    /// Creates an [`ImgBuf`] with [`Vec`] as it's container by applying a function to each pair of
+    /// pixels with the same coordinates in this view and another one.
+    ///
+    /// # Panics
+    /// Panics if `self.dimensions() != other.dimensions()`
+    fn zip_map_ref<I, P, F>(&self, other: &I, mut f: F) -> ImgBuf<P, Vec<P>>
     where
-        Self: 'view_ref;
+        I: Img,
+        F: FnMut(&Self::Pixel, &I::Pixel) -> P,
+    {
+        assert_eq!(self.dimensions(), other.dimensions());
+        ImgBuf::from_container(
+            self.pixels()
+                .zip(other.pixels())
+                .map(|(a, b)| f(a, b))
+                .collect(),
+            self.width(),
+            self.height(),
+        )
+    }
+}
 
+/// Trait for types that can be treated as a mutable view into some image.
+///
+/// This is the minimal, object-safe core of [`ImgMut`].
+pub trait ImgMutCore: ImgCore {
     /// Returns a mutable reference to the pixel with coordinates `(x, y)` relative to this view. If the
     /// coordinates aren't within the bounds of this view, returns `None`.
     #[inline]
@@ -172,6 +289,19 @@ pub trait ImgMut: Img {
     /// # Safety
     /// The coordinate must be in the bounds of the view.
     unsafe fn pixel_mut_unchecked(&mut self, coords: Point) -> &mut Self::Pixel;
+}
+
+/// Extension of [`ImgMutCore`] with mutable iterators and subviews.
+pub trait ImgMut: Img + ImgMutCore {
+    /// The type of the iterator through mutable pixels of this view.
+    type PixelsMut<'view_ref>: Iterator<Item = &'view_ref mut Self::Pixel>
+    where
+        Self: 'view_ref;
+
+    /// The mutable view type the `view_mut` method returns.
+    type ViewMut<'view_ref>: ImgMut<Pixel = Self::Pixel>
+    where
+        Self: 'view_ref;
 
     /// Returns a mutable iterator over the pixels of this view.
     fn pixels_mut(&mut self) -> Self::PixelsMut<'_>;
@@ -258,4 +388,342 @@ pub trait ImgMut: Img {
             .zip(view.pixels_mut())
             .for_each(|(a, b)| std::mem::swap(a, b));
     }
+
+    /// Copies a view into this one, with it's top-left corner at `at`. Parts of `view` that don't
+    /// fit within this view are clipped instead of causing a panic.
+    #[inline]
+    fn copy_from_at<I>(&mut self, view: &I, at: Point)
+    where
+        I: Img<Pixel = Self::Pixel>,
+        Self::Pixel: Clone,
+    {
+        let Some(dimensions) = clipped_dimensions(self.dimensions(), view.dimensions(), at) else {
+            return;
+        };
+
+        // SAFETY: the dimensions are clipped to fit in both views.
+        let source = unsafe { view.view_unchecked(Rect::new((0, 0), dimensions)) };
+        // SAFETY: same as above.
+        let mut target = unsafe { self.view_mut_unchecked(Rect::new(at, dimensions)) };
+        target.copy_from(&source);
+    }
+
+    /// Pastes a view onto this one, with it's top-left corner at `at`. This is the same as
+    /// [`ImgMut::copy_from_at`]: parts of `view` that don't fit within this view are clipped.
+    #[inline]
+    fn overlay<I>(&mut self, view: &I, at: Point)
+    where
+        I: Img<Pixel = Self::Pixel>,
+        Self::Pixel: Clone,
+    {
+        self.copy_from_at(view, at);
+    }
+
+    /// Composites a view over this one using source-over alpha blending, with it's top-left
+    /// corner at `at`. Like [`ImgMut::overlay`], parts of `view` that don't fit within this
+    /// view are clipped instead of causing a panic.
+    #[inline]
+    fn overlay_blended<I>(&mut self, view: &I, at: Point)
+    where
+        I: Img<Pixel = Self::Pixel>,
+        Self::Pixel: Blend + Copy,
+    {
+        let Some(dimensions) = clipped_dimensions(self.dimensions(), view.dimensions(), at) else {
+            return;
+        };
+
+        // SAFETY: the dimensions are clipped to fit in both views.
+        let source = unsafe { view.view_unchecked(Rect::new((0, 0), dimensions)) };
+        // SAFETY: same as above.
+        let mut target = unsafe { self.view_mut_unchecked(Rect::new(at, dimensions)) };
+        target
+            .pixels_mut()
+            .zip(source.pixels())
+            .for_each(|(dst, src)| *dst = src.over(*dst));
+    }
+}
+
+/// Returns the dimensions of the part of a view with dimensions `source` that fits within a view
+/// with dimensions `target` when placed at `at`, or `None` if no part of it fits.
+#[inline]
+fn clipped_dimensions(target: (u32, u32), source: (u32, u32), at: Point) -> Option<(u32, u32)> {
+    let dimensions = (
+        source.0.min(target.0.saturating_sub(at.0)),
+        source.1.min(target.1.saturating_sub(at.1)),
+    );
+
+    (dimensions.0 != 0 && dimensions.1 != 0).then_some(dimensions)
+}
+
+impl<T> ImgCore for &T
+where
+    T: ImgCore + ?Sized,
+{
+    type Pixel = T::Pixel;
+
+    #[inline]
+    fn width(&self) -> u32 {
+        (**self).width()
+    }
+
+    #[inline]
+    fn height(&self) -> u32 {
+        (**self).height()
+    }
+
+    #[inline]
+    fn dimensions(&self) -> (u32, u32) {
+        (**self).dimensions()
+    }
+
+    #[inline]
+    unsafe fn pixel_unchecked(&self, coords: Point) -> &Self::Pixel {
+        // SAFETY: we trust the caller!
+        unsafe { (**self).pixel_unchecked(coords) }
+    }
+}
+
+impl<T> Img for &T
+where
+    T: Img,
+{
+    type Pixels<'view_ref>
+        = T::Pixels<'view_ref>
+    where
+        Self: 'view_ref;
+    type View<'view_ref>
+        = T::View<'view_ref>
+    where
+        Self: 'view_ref;
+
+    #[inline]
+    fn pixels(&self) -> Self::Pixels<'_> {
+        (**self).pixels()
+    }
+
+    #[inline]
+    fn pixel_chunks(&self) -> impl Iterator<Item = &'_ [Self::Pixel]> {
+        (**self).pixel_chunks()
+    }
+
+    #[inline]
+    unsafe fn view_unchecked(&self, bounds: Rect) -> Self::View<'_> {
+        // SAFETY: we trust the caller!
+        unsafe { (**self).view_unchecked(bounds) }
+    }
+}
+
+/// A view of another view flipped horizontally. See [`Img::flip_h`].
+#[derive(Debug, Clone, Copy)]
+pub struct FlipH<V> {
+    view: V,
+}
+
+impl<V> FlipH<V> {
+    /// Creates a view of `view` flipped horizontally.
+    #[inline]
+    pub fn new(view: V) -> Self {
+        Self { view }
+    }
+
+    /// Returns the view this view flips.
+    #[inline]
+    pub fn into_inner(self) -> V {
+        self.view
+    }
+}
+
+impl<V> ImgCore for FlipH<V>
+where
+    V: Img,
+{
+    type Pixel = V::Pixel;
+
+    #[inline]
+    fn width(&self) -> u32 {
+        self.view.width()
+    }
+
+    #[inline]
+    fn height(&self) -> u32 {
+        self.view.height()
+    }
+
+    #[inline]
+    unsafe fn pixel_unchecked(&self, (x, y): Point) -> &Self::Pixel {
+        // SAFETY: mirroring a point within the bounds keeps it within the bounds.
+        unsafe { self.view.pixel_unchecked((self.width() - 1 - x, y)) }
+    }
+}
+
+impl<V> Img for FlipH<V>
+where
+    V: Img,
+{
+    type Pixels<'view_ref>
+        = iter::Pixels<'view_ref, Self>
+    where
+        Self: 'view_ref;
+    type View<'view_ref>
+        = FlipH<V::View<'view_ref>>
+    where
+        Self: 'view_ref;
+
+    #[inline]
+    fn pixels(&self) -> Self::Pixels<'_> {
+        iter::Pixels::new(self)
+    }
+
+    #[inline]
+    unsafe fn view_unchecked(&self, bounds: Rect) -> Self::View<'_> {
+        let ((x, y), (width, height)) = (bounds.top_left(), bounds.dimensions());
+        let mirrored = Rect::new((self.width() - x - width, y), (width, height));
+
+        // SAFETY: mirroring bounds within this view keeps them within the inner view.
+        FlipH::new(unsafe { self.view.view_unchecked(mirrored) })
+    }
+}
+
+/// A view of another view flipped vertically. See [`Img::flip_v`].
+#[derive(Debug, Clone, Copy)]
+pub struct FlipV<V> {
+    view: V,
+}
+
+impl<V> FlipV<V> {
+    /// Creates a view of `view` flipped vertically.
+    #[inline]
+    pub fn new(view: V) -> Self {
+        Self { view }
+    }
+
+    /// Returns the view this view flips.
+    #[inline]
+    pub fn into_inner(self) -> V {
+        self.view
+    }
+}
+
+impl<V> ImgCore for FlipV<V>
+where
+    V: Img,
+{
+    type Pixel = V::Pixel;
+
+    #[inline]
+    fn width(&self) -> u32 {
+        self.view.width()
+    }
+
+    #[inline]
+    fn height(&self) -> u32 {
+        self.view.height()
+    }
+
+    #[inline]
+    unsafe fn pixel_unchecked(&self, (x, y): Point) -> &Self::Pixel {
+        // SAFETY: mirroring a point within the bounds keeps it within the bounds.
+        unsafe { self.view.pixel_unchecked((x, self.height() - 1 - y)) }
+    }
+}
+
+impl<V> Img for FlipV<V>
+where
+    V: Img,
+{
+    type Pixels<'view_ref>
+        = iter::Pixels<'view_ref, Self>
+    where
+        Self: 'view_ref;
+    type View<'view_ref>
+        = FlipV<V::View<'view_ref>>
+    where
+        Self: 'view_ref;
+
+    #[inline]
+    fn pixels(&self) -> Self::Pixels<'_> {
+        iter::Pixels::new(self)
+    }
+
+    #[inline]
+    unsafe fn view_unchecked(&self, bounds: Rect) -> Self::View<'_> {
+        let ((x, y), (width, height)) = (bounds.top_left(), bounds.dimensions());
+        let mirrored = Rect::new((x, self.height() - y - height), (width, height));
+
+        // SAFETY: mirroring bounds within this view keeps them within the inner view.
+        FlipV::new(unsafe { self.view.view_unchecked(mirrored) })
+    }
+}
+
+/// A view of another view transposed, i.e. with it's rows turned into columns. See
+/// [`Img::transpose`].
+#[derive(Debug, Clone, Copy)]
+pub struct Transpose<V> {
+    view: V,
+}
+
+impl<V> Transpose<V> {
+    /// Creates a view of `view` transposed.
+    #[inline]
+    pub fn new(view: V) -> Self {
+        Self { view }
+    }
+
+    /// Returns the view this view transposes.
+    #[inline]
+    pub fn into_inner(self) -> V {
+        self.view
+    }
+}
+
+impl<V> ImgCore for Transpose<V>
+where
+    V: Img,
+{
+    type Pixel = V::Pixel;
+
+    #[inline]
+    fn width(&self) -> u32 {
+        self.view.height()
+    }
+
+    #[inline]
+    fn height(&self) -> u32 {
+        self.view.width()
+    }
+
+    #[inline]
+    unsafe fn pixel_unchecked(&self, (x, y): Point) -> &Self::Pixel {
+        // SAFETY: swapping the coordinates of a point within the bounds gives a point within the
+        // bounds of the inner view.
+        unsafe { self.view.pixel_unchecked((y, x)) }
+    }
+}
+
+impl<V> Img for Transpose<V>
+where
+    V: Img,
+{
+    type Pixels<'view_ref>
+        = iter::Pixels<'view_ref, Self>
+    where
+        Self: 'view_ref;
+    type View<'view_ref>
+        = Transpose<V::View<'view_ref>>
+    where
+        Self: 'view_ref;
+
+    #[inline]
+    fn pixels(&self) -> Self::Pixels<'_> {
+        iter::Pixels::new(self)
+    }
+
+    #[inline]
+    unsafe fn view_unchecked(&self, bounds: Rect) -> Self::View<'_> {
+        let ((x, y), (width, height)) = (bounds.top_left(), bounds.dimensions());
+        let swapped = Rect::new((y, x), (height, width));
+
+        // SAFETY: swapping the axes of bounds within this view gives bounds within the inner view.
+        Transpose::new(unsafe { self.view.view_unchecked(swapped) })
+    }
 }